@@ -0,0 +1,130 @@
+// Copyright 2024 The Lynx Authors. All rights reserved.
+// Licensed under the Apache License Version 2.0 that can be found in the
+// LICENSE file in the root directory of this source tree.
+import { describe, expect, it, vi } from 'vitest';
+
+import {
+  transformBundleResult,
+  transformReactLynx,
+  transformReactLynxDualThread,
+} from '../main.js';
+
+const defaultOptions = {
+  pluginName: '',
+  filename: '',
+  sourcemap: false,
+  cssScope: false,
+  directiveDCE: false,
+  defineDCE: false,
+  shake: false,
+  compat: false,
+  worklet: false,
+  refresh: false,
+};
+
+// Large enough that the transform is still running when it is aborted.
+const code = Array.from(
+  { length: 2000 },
+  (_, i) => `export const App${i} = () => <view id="${i}"><text>{${i}}</text></view>;`,
+).join('\n');
+
+function spySignal(signal) {
+  return {
+    addEventListener: vi.spyOn(signal, 'addEventListener'),
+    removeEventListener: vi.spyOn(signal, 'removeEventListener'),
+  };
+}
+
+describe('AbortSignal', () => {
+  it('should reject an aborted transform and remove the listener', async () => {
+    const controller = new AbortController();
+    const { addEventListener, removeEventListener } = spySignal(
+      controller.signal,
+    );
+
+    const promise = transformReactLynx(code, defaultOptions, controller.signal);
+    expect(addEventListener).toHaveBeenCalledTimes(1);
+    const [type, listener] = addEventListener.mock.calls[0];
+    expect(type).toBe('abort');
+
+    controller.abort();
+    await expect(promise).rejects.toMatchObject({
+      code: 'Cancelled',
+      message: 'AbortError: the transform is aborted',
+    });
+    expect(removeEventListener).toHaveBeenCalledWith('abort', listener);
+  });
+
+  it('should reject an already aborted signal without adding a listener', async () => {
+    const controller = new AbortController();
+    controller.abort();
+    const { addEventListener } = spySignal(controller.signal);
+
+    await expect(transformReactLynx(code, defaultOptions, controller.signal)).rejects
+      .toMatchObject({ code: 'Cancelled' });
+    expect(addEventListener).not.toHaveBeenCalled();
+  });
+
+  it('should remove the listener once the transform finishes', async () => {
+    const controller = new AbortController();
+    const { addEventListener, removeEventListener } = spySignal(
+      controller.signal,
+    );
+
+    const result = await transformReactLynx(
+      'export const a = 1;',
+      defaultOptions,
+      controller.signal,
+    );
+    expect(result.errors).toEqual([]);
+    const [, listener] = addEventListener.mock.calls[0];
+    expect(removeEventListener).toHaveBeenCalledWith('abort', listener);
+  });
+
+  it('should abort the dual-thread transform', async () => {
+    const controller = new AbortController();
+    const { removeEventListener } = spySignal(controller.signal);
+
+    const promise = transformReactLynxDualThread(code, {
+      mainThread: defaultOptions,
+      background: defaultOptions,
+    }, controller.signal);
+    controller.abort();
+    await expect(promise).rejects.toMatchObject({ code: 'Cancelled' });
+    expect(removeEventListener).toHaveBeenCalledTimes(1);
+  });
+
+  it('should abort transformBundleResult', async () => {
+    const controller = new AbortController();
+    controller.abort();
+    await expect(transformBundleResult('let a = 1', {
+      pluginName: '',
+      filename: '',
+      sourcemap: false,
+      extractStr: false,
+    }, controller.signal)).rejects.toMatchObject({
+      code: 'Cancelled',
+      message: 'AbortError: the transform is aborted',
+    });
+  });
+});
+
+describe('timeout', () => {
+  it('should reject a transform that is not finished in time', async () => {
+    await expect(transformReactLynx('let a = 1', {
+      ...defaultOptions,
+      timeout: 0,
+    })).rejects.toMatchObject({
+      code: 'Cancelled',
+      message: 'TimeoutError: the transform is not finished within 0ms',
+    });
+  });
+
+  it('should not reject a transform that is finished in time', async () => {
+    const result = await transformReactLynx('let a = 1', {
+      ...defaultOptions,
+      timeout: 60_000,
+    });
+    expect(result.errors).toEqual([]);
+  });
+});
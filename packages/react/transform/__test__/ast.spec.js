@@ -0,0 +1,136 @@
+// Copyright 2024 The Lynx Authors. All rights reserved.
+// Licensed under the Apache License Version 2.0 that can be found in the
+// LICENSE file in the root directory of this source tree.
+import { describe, expect, it } from 'vitest';
+
+import {
+  transformReactLynx,
+  transformReactLynxAst,
+  transformReactLynxAstSync,
+} from '../main.js';
+
+// The swc `Program` of `source`, as printed by `serde_json`.
+const source = 'export const App = <view />;\n';
+const program = JSON.stringify({
+  type: 'Module',
+  span: { start: 1, end: 29 },
+  body: [{
+    type: 'ExportDeclaration',
+    span: { start: 1, end: 29 },
+    declaration: {
+      type: 'VariableDeclaration',
+      span: { start: 8, end: 29 },
+      ctxt: 0,
+      kind: 'const',
+      declare: false,
+      declarations: [{
+        type: 'VariableDeclarator',
+        span: { start: 14, end: 28 },
+        id: {
+          type: 'Identifier',
+          span: { start: 14, end: 17 },
+          ctxt: 0,
+          value: 'App',
+          optional: false,
+          typeAnnotation: null,
+        },
+        init: {
+          type: 'JSXElement',
+          span: { start: 20, end: 28 },
+          opening: {
+            type: 'JSXOpeningElement',
+            name: {
+              type: 'Identifier',
+              span: { start: 21, end: 25 },
+              ctxt: 0,
+              value: 'view',
+              optional: false,
+            },
+            span: { start: 20, end: 28 },
+            attributes: [],
+            selfClosing: true,
+            typeArguments: null,
+          },
+          children: [],
+          closing: null,
+        },
+        definite: false,
+      }],
+    },
+  }],
+  interpreter: null,
+});
+
+const options = {
+  pluginName: '',
+  filename: 'index.jsx',
+  sourcemap: false,
+  cssScope: false,
+  directiveDCE: false,
+  defineDCE: false,
+  shake: false,
+  compat: false,
+  worklet: false,
+  refresh: false,
+  mode: 'test',
+};
+
+describe('transformReactLynxAst', () => {
+  it('should match transformReactLynx', async () => {
+    const result = await transformReactLynxAst(
+      { program, source, print: true },
+      options,
+    );
+    expect(result.errors).toEqual([]);
+    expect(result.code).toBe((await transformReactLynx(source, options)).code);
+    expect(result.metadata.snapshots).toHaveLength(1);
+    expect(result.metadata.snapshots[0].location.line).toBe(1);
+    expect(JSON.parse(result.program).type).toBe('Module');
+  });
+
+  it('should not print by default', () => {
+    const result = transformReactLynxAstSync({ program, source }, options);
+    expect(result.code).toBeUndefined();
+    expect(result.program).toBeDefined();
+  });
+
+  it('should require source for parsed programs', () => {
+    const result = transformReactLynxAstSync({ program }, options);
+    expect(result.program).toBeUndefined();
+    expect(result.errors).toHaveLength(1);
+    expect(result.errors[0].text).toContain('`source` is required');
+  });
+
+  it('should not require source for programs without spans', () => {
+    const empty = JSON.stringify({
+      type: 'Module',
+      span: { start: 0, end: 0 },
+      body: [],
+      interpreter: null,
+    });
+    const result = transformReactLynxAstSync(
+      { program: empty, print: true },
+      options,
+    );
+    expect(result.errors).toEqual([]);
+    expect(result.program).toBeDefined();
+  });
+
+  it('should report invalid programs', () => {
+    const result = transformReactLynxAstSync({ program: '{}', source }, options);
+    expect(result.program).toBeUndefined();
+    expect(result.errors).toHaveLength(1);
+  });
+
+  it('should reject codemod and recover', () => {
+    const validate = (extra) => () =>
+      transformReactLynxAstSync({ program, source }, { ...options, ...extra });
+    expect(validate({ codemod: true })).toThrow(
+      '`codemod` is not supported by `transformReactLynxAst`',
+    );
+    expect(validate({ recover: true })).toThrow(
+      '`recover` is not supported by `transformReactLynxAst`',
+    );
+    expect(validate({ codemod: false, recover: false })).not.toThrow();
+  });
+});
@@ -0,0 +1,107 @@
+// Copyright 2024 The Lynx Authors. All rights reserved.
+// Licensed under the Apache License Version 2.0 that can be found in the
+// LICENSE file in the root directory of this source tree.
+import { mkdtempSync, readdirSync, rmSync } from 'node:fs';
+import { tmpdir } from 'node:os';
+import path from 'node:path';
+
+import { describe, expect, it } from 'vitest';
+
+import { transformReactLynx, transformReactLynxBatch } from '../main.js';
+
+const options = {
+  pluginName: '',
+  filename: '',
+  sourcemap: false,
+  cssScope: false,
+  directiveDCE: false,
+  defineDCE: false,
+  shake: false,
+  compat: false,
+  worklet: false,
+  refresh: false,
+  mode: 'test',
+};
+
+describe('transformReactLynxBatch', () => {
+  it('should transform files in order', async () => {
+    const outputs = await transformReactLynxBatch([
+      { code: 'export const a = <view />;', filename: 'a.jsx', options },
+      { code: 'export const b: number = 1;', filename: 'b.ts', options },
+    ]);
+    expect(outputs).toHaveLength(2);
+    expect(outputs[0].errors).toEqual([]);
+    expect(outputs[0].code).toBe(
+      (await transformReactLynx('export const a = <view />;', {
+        ...options,
+        filename: 'a.jsx',
+      })).code,
+    );
+    expect(outputs[1].errors).toEqual([]);
+    expect(outputs[1].code).toBe('export const b = 1;\n');
+  });
+
+  it('should report invalid files in their own errors', async () => {
+    const outputs = await transformReactLynxBatch([
+      { code: 'export const a = <view />;', filename: 'a.jsx', options },
+      { code: 'export const b = ;', filename: 'b.jsx', options },
+      {
+        code: 'export const c = <view />;',
+        filename: 'c.jsx',
+        options: { ...options, pipeline: ['snapshot', 'list'] },
+      },
+      {
+        code: 'export const d = <view />;',
+        filename: 'd.jsx',
+        options: { ...options, timeout: 0 },
+      },
+    ]);
+    expect(outputs).toHaveLength(4);
+
+    expect(outputs[0].errors).toEqual([]);
+    expect(outputs[0].code).toContain('createSnapshot');
+
+    expect(outputs[1].code).toBe('');
+    expect(outputs[1].errors).toHaveLength(1);
+    expect(outputs[1].errors[0].location.file).toBe('b.jsx');
+
+    expect(outputs[2].code).toBe('');
+    expect(outputs[2].errors).toHaveLength(1);
+    expect(outputs[2].errors[0].text).toBe(
+      'Invalid pipeline: `list` (at index 1) must run before `snapshot` (at index 0)',
+    );
+    expect(outputs[2].errors[0].location.file).toBe('c.jsx');
+  });
+
+    // a file that times out does not fail the others
+    expect(outputs[3].code).toBe('');
+    expect(outputs[3].errors).toHaveLength(1);
+    expect(outputs[3].errors[0].text).toBe(
+      'TimeoutError: the transform is not finished within 0ms',
+    );
+    expect(outputs[3].errors[0].location.file).toBe('d.jsx');
+  });
+
+  // The cache is not supported in the WebAssembly build.
+  it.skipIf(!process.env['USE_NAPI'])('should cache every file', async () => {
+    const cacheDir = mkdtempSync(
+      path.join(tmpdir(), 'react-transform-batch-'),
+    );
+    try {
+      const files = [
+        { code: 'export const a = 1;', filename: 'a.js' },
+        { code: 'export const b = 2;', filename: 'b.js' },
+      ].map(file => ({
+        ...file,
+        options: { ...options, filename: file.filename, cacheDir },
+      }));
+
+      const outputs = await transformReactLynxBatch(files);
+      const entries = readdirSync(cacheDir, { recursive: true });
+      expect(entries.filter(name => name.endsWith('.json'))).toHaveLength(2);
+      expect(await transformReactLynxBatch(files)).toEqual(outputs);
+    } finally {
+      rmSync(cacheDir, { recursive: true, force: true });
+    }
+  });
+});
@@ -0,0 +1,197 @@
+// Copyright 2024 The Lynx Authors. All rights reserved.
+// Licensed under the Apache License Version 2.0 that can be found in the
+// LICENSE file in the root directory of this source tree.
+import { describe, expect, it } from 'vitest';
+
+import { transformBundleResult } from '../main.js';
+
+const options = {
+  pluginName: '',
+  filename: 'main.js',
+  sourcemap: false,
+  extractStr: false,
+};
+
+describe('minifier', () => {
+  const code = `\
+var _EXTRACT_STR = ["hello"];
+(function () {
+  var _EXTRACT_STR = ["world"];
+  function __CreateListPolyfill(tag) {
+    return __CreateView(tag);
+  }
+  function createPage(pageId) {
+    var element = __CreateListPolyfill(pageId);
+    __SetAttribute(element, "text", _EXTRACT_STR[0]);
+    if (false) {
+      console.log("unreachable");
+    }
+    return element;
+  }
+  registerWorklet("main-thread", "57ba11b1", function (event) {
+    var keepMe = event.target;
+    keepMe.value = keepMe.dataset;
+    return keepMe;
+  });
+  globalThis.createPage = createPage;
+  globalThis.__CreateListPolyfill = __CreateListPolyfill;
+})();
+`;
+  const bundle = async (minifier) =>
+    (await transformBundleResult(code, { ...options, minifier })).code;
+  const lines = output => output.trimEnd().split('\n').length;
+
+  it('should compress and mangle the bundle', async () => {
+    const output = await bundle(true);
+    expect(lines(output)).toBe(1);
+    expect(output).toContain('var _EXTRACT_STR=["world"];');
+    expect(output).toContain('"text",_EXTRACT_STR[0])');
+    expect(output).toContain('function __CreateListPolyfill(');
+    expect(output).toContain(
+      'registerWorklet("main-thread","57ba11b1",function(',
+    );
+    expect(output).not.toContain('unreachable');
+    expect(output).not.toContain('pageId');
+    expect(output).not.toContain('keepMe');
+  });
+
+  it('should keep function names and reserved names', async () => {
+    const output = await bundle({ keepFnames: true, reserved: ['keepMe'] });
+    expect(output).toContain('function createPage(');
+    expect(output).toContain('keepMe.value=keepMe.dataset');
+  });
+
+  it('should only remove whitespaces without compress and mangle', async () => {
+    const output = await bundle({ compress: false, mangle: false });
+    expect(lines(output)).toBe(1);
+    expect(output).toContain('unreachable');
+    expect(output).toContain('pageId');
+  });
+
+  it('should not minify by default', async () => {
+    expect(await bundle(undefined)).toBe(await bundle(false));
+    expect(await bundle(undefined)).toContain('unreachable');
+  });
+});
+
+describe('workletAllowList', () => {
+  const code = `\
+registerWorkletInternal("main-thread", "a77b:test:1", function(event) {
+    console.log("hello");
+});
+loadWorkletRuntime(typeof globDynamicComponentEntry === "undefined" ? undefined : globDynamicComponentEntry) && registerWorkletInternal("main-thread", "a77b:test:2", function(event) {
+    console.log("world");
+});
+const onTap = {
+    _wkltId: "a77b:test:1"
+};
+`;
+
+  it('should drop worklets outside the allow list before extractStr', async () => {
+    const result = await transformBundleResult(code, {
+      ...options,
+      extractStr: { strLength: 1 },
+      workletAllowList: ['a77b:test:1'],
+    });
+    expect(result.worklets).toEqual({
+      worklets: [{ hash: 'a77b:test:1', type: 'main-thread', referenced: true }],
+      count: 1,
+      removed: ['a77b:test:2'],
+    });
+    // The strings of the removed worklet are not extracted either.
+    expect(result.selectStrVec).toContain('a77b:test:1');
+    expect(result.selectStrVec).not.toContain('world');
+    expect(result.code).not.toContain('a77b:test:2');
+  });
+});
+
+describe('syntaxConfig and isModule', () => {
+  const bundle = async (code, extra) => {
+    const result = await transformBundleResult(code, {
+      ...options,
+      extractStr: { strLength: 1 },
+      ...extra,
+    });
+    return [result.code, result.errors.length];
+  };
+
+  it('should parse scripts', async () => {
+    const code = 'with (a) { b("hello"); }';
+    expect((await bundle(code))[1]).toBe(1);
+    expect(await bundle(code, { isModule: false })).toEqual([
+      'var _EXTRACT_STR = [\n    "hello"\n];\nwith (a){\n    b(_EXTRACT_STR[0]);\n}\n',
+      0,
+    ]);
+  });
+
+  it('should parse CommonJS', async () => {
+    const code = 'module.exports = "hello";\nreturn;\n';
+    expect((await bundle(code))[1]).toBe(1);
+    expect((await bundle(code, { isModule: 'commonjs' }))[1]).toBe(0);
+  });
+
+  it('should parse TypeScript', async () => {
+    const code = 'let a: number = <div />;\n';
+    expect((await bundle(code))[1]).toBe(1);
+    const syntaxConfig = JSON.stringify({
+      syntax: 'typescript',
+      tsx: true,
+      decorators: true,
+    });
+    expect((await bundle(code, { syntaxConfig }))[0]).toBe(
+      'var _EXTRACT_STR = [];\nlet a: number = <div/>;\n',
+    );
+  });
+});
+
+describe('snapshotDCE', () => {
+  const snapshot = id => `\
+const ${id} = ReactLynx.createSnapshot("${id}", function() {
+    const el = __CreateView(ReactLynx.__pageId);
+    __SetClasses(el, "");
+    return [
+        el
+    ];
+}, null, null, undefined, globDynamicComponentEntry, null);
+`;
+  // The `__BACKGROUND__` branch is removed from the main thread bundle.
+  const mainThread = snapshot('__snapshot_a') + snapshot('__snapshot_b');
+  const background = `\
+const __snapshot_a = ReactLynx.createSnapshot("__snapshot_a", null, null, null, undefined, globDynamicComponentEntry, null);
+export function App() {
+    if (__BACKGROUND__) {
+        return _jsx(__snapshot_a, {});
+    }
+    return null;
+}
+`;
+  const liveSnapshots = [...background.matchAll(/createSnapshot\("(\w+)"/g)]
+    .map(([, id]) => id);
+
+  it('should only fold PAPI calls without liveSnapshots', async () => {
+    const result = await transformBundleResult(mainThread, {
+      ...options,
+      snapshotDCE: true,
+    });
+    expect(result.snapshotDCE).toEqual({
+      removedSnapshots: [],
+      foldedPapiCalls: 2,
+    });
+    expect(result.code).toContain('"__snapshot_a"');
+    expect(result.code).toContain('"__snapshot_b"');
+  });
+
+  it('should keep the snapshots used by the background bundle', async () => {
+    const result = await transformBundleResult(mainThread, {
+      ...options,
+      snapshotDCE: { liveSnapshots },
+    });
+    expect(liveSnapshots).toEqual(['__snapshot_a']);
+    expect(result.snapshotDCE).toEqual({
+      removedSnapshots: ['__snapshot_b'],
+      foldedPapiCalls: 1,
+    });
+    expect(result.code).toContain('"__snapshot_a"');
+    expect(result.code).not.toContain('"__snapshot_b"');
+  });
+});
@@ -0,0 +1,67 @@
+// Copyright 2024 The Lynx Authors. All rights reserved.
+// Licensed under the Apache License Version 2.0 that can be found in the
+// LICENSE file in the root directory of this source tree.
+import { mkdtempSync, readdirSync, rmSync } from 'node:fs';
+import { tmpdir } from 'node:os';
+import path from 'node:path';
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+
+import { transformReactLynx } from '../main.js';
+
+const options = {
+  pluginName: '',
+  filename: 'index.jsx',
+  sourcemap: false,
+  cssScope: false,
+  directiveDCE: false,
+  defineDCE: false,
+  shake: false,
+  compat: false,
+  worklet: false,
+  refresh: false,
+  mode: 'test',
+};
+
+// The cache is not supported in the WebAssembly build.
+describe.skipIf(!process.env['USE_NAPI'])('cacheDir', () => {
+  let cacheDir;
+  const entries = () =>
+    readdirSync(cacheDir, { recursive: true })
+      .filter(name => name.endsWith('.json'));
+
+  beforeEach(() => {
+    cacheDir = mkdtempSync(path.join(tmpdir(), 'react-transform-cache-'));
+  });
+
+  afterEach(() => {
+    rmSync(cacheDir, { recursive: true, force: true });
+  });
+
+  it('should reuse the cached output', async () => {
+    const code = 'export const App = () => <view />;';
+    const output = await transformReactLynx(code, { ...options, cacheDir });
+    expect(output.errors).toEqual([]);
+    expect(entries()).toHaveLength(1);
+
+    expect(await transformReactLynx(code, { ...options, cacheDir }))
+      .toEqual(output);
+    expect(entries()).toHaveLength(1);
+
+    await transformReactLynx(code, {
+      ...options,
+      cacheDir,
+      mode: 'development',
+    });
+    expect(entries()).toHaveLength(2);
+  });
+
+  it('should not cache failed transforms', async () => {
+    const output = await transformReactLynx('let a = ', {
+      ...options,
+      cacheDir,
+    });
+    expect(output.errors).not.toEqual([]);
+    expect(entries()).toHaveLength(0);
+  });
+});
@@ -0,0 +1,45 @@
+// Copyright 2024 The Lynx Authors. All rights reserved.
+// Licensed under the Apache License Version 2.0 that can be found in the
+// LICENSE file in the root directory of this source tree.
+import { describe, expect, it } from 'vitest';
+
+import { transformReactLynx } from '../main.js';
+
+const options = {
+  pluginName: '',
+  filename: 'index.jsx',
+  sourcemap: false,
+  cssScope: false,
+  directiveDCE: false,
+  defineDCE: false,
+  shake: false,
+  compat: false,
+  worklet: false,
+  refresh: false,
+  mode: 'test',
+};
+
+const code = '<view>\n  <text foo:bar="1" />\n</view>;\n';
+
+describe('codeFrame', () => {
+  it('should not render code frames by default', async () => {
+    const result = await transformReactLynx(code, options);
+    expect(result.errors[0].detail).toBeUndefined();
+  });
+
+  it('should render code frames into detail', async () => {
+    const result = await transformReactLynx(code, {
+      ...options,
+      codeFrame: true,
+    });
+    expect(result.errors[0].detail).toBe(`\
+error[RL4005]: Unknown namespaced attribute \`foo:bar\`, only worklet events, \`ref\` and \`gesture\` are supported
+ --> index.jsx:2:9
+  |
+1 | <view>
+2 |   <text foo:bar="1" />
+  |         ^^^^^^^
+3 | </view>;
+`);
+  });
+});
@@ -0,0 +1,96 @@
+// Copyright 2024 The Lynx Authors. All rights reserved.
+// Licensed under the Apache License Version 2.0 that can be found in the
+// LICENSE file in the root directory of this source tree.
+import { describe, expect, it } from 'vitest';
+
+import { transformBundleResult, transformReactLynx } from '../main.js';
+
+const options = {
+  pluginName: '',
+  filename: 'index.js',
+  sourcemap: true,
+  cssScope: false,
+  directiveDCE: false,
+  defineDCE: false,
+  shake: false,
+  compat: false,
+  worklet: false,
+  refresh: false,
+  mode: 'test',
+};
+
+const code = `\
+/*! legal */
+/* normal */
+console.log("你好");
+const a = /*#__PURE__*/ foo();
+export { a };
+`;
+
+describe('codegen', () => {
+  it('should keep comments and non-ASCII characters by default', async () => {
+    const result = await transformReactLynx(code, options);
+    expect(result.code).toContain('/*! legal */');
+    expect(result.code).toContain('/* normal */');
+    expect(result.code).toContain('你好');
+  });
+
+  it('should keep only legal comments and escape non-ASCII characters', async () => {
+    const result = await transformReactLynx(code, {
+      ...options,
+      codegen: { asciiOnly: true, comments: 'license', sourceRoot: '/src/' },
+    });
+    expect(result.code).toContain('/*! legal */');
+    expect(result.code).not.toContain('/* normal */');
+    expect(result.code).toContain('/*#__PURE__*/');
+    expect(result.code).not.toContain('你好');
+    expect(result.code).toContain('\\u4F60\\u597D');
+    expect(JSON.parse(result.map).sourceRoot).toBe('/src/');
+  });
+
+  it('should minify without comments', async () => {
+    const result = await transformReactLynx(code, {
+      ...options,
+      codegen: { comments: 'none', minify: true },
+    });
+    expect(result.code).not.toContain('legal');
+    expect(result.code).toContain('/*#__PURE__*/');
+    expect(result.code).not.toContain('\n');
+  });
+
+  it('should report syntax newer than target', async () => {
+    const result = await transformReactLynx(code, {
+      ...options,
+      codegen: { target: 'es5' },
+    });
+    expect(result.errors).toMatchObject([{
+      id: 'RL9004',
+      text:
+        '`let` and `const` of `es2015` cannot be printed as `codegen.target` `es5`, syntax is not lowered',
+    }]);
+
+    const { errors } = await transformReactLynx(code, {
+      ...options,
+      codegen: { target: 'es2015' },
+    });
+    expect(errors).toEqual([]);
+  });
+
+  it('should override minify of transformBundleResult', async () => {
+    const bundle = async (extra) =>
+      (await transformBundleResult(code, {
+        pluginName: '',
+        filename: 'main.js',
+        sourcemap: false,
+        extractStr: false,
+        ...extra,
+      })).code;
+    const lines = output => output.trimEnd().split('\n').length;
+
+    expect(lines(await bundle({ minify: true }))).toBe(1);
+    expect(lines(await bundle({ minify: true, codegen: { minify: false } })))
+      .toBeGreaterThan(1);
+    expect(await bundle({ codegen: { comments: 'none' } }))
+      .not.toContain('legal');
+  });
+});
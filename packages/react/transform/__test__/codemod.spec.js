@@ -0,0 +1,78 @@
+// Copyright 2024 The Lynx Authors. All rights reserved.
+// Licensed under the Apache License Version 2.0 that can be found in the
+// LICENSE file in the root directory of this source tree.
+import { describe, expect, it } from 'vitest';
+
+import { transformReactLynx } from '../main.js';
+
+const options = {
+  pluginName: '',
+  filename: 'index.jsx',
+  sourcemap: false,
+  cssScope: false,
+  directiveDCE: false,
+  defineDCE: false,
+  shake: false,
+  compat: {
+    target: 'LEPUS',
+    componentsPkg: ['@lynx-js/react-components'],
+    oldRuntimePkg: ['@lynx-js/react-runtime'],
+    newRuntimePkg: '@lynx-js/react',
+    additionalComponentAttributes: [],
+    addComponentElement: false,
+    simplifyCtorLikeReactLynx2: false,
+    disableDeprecatedWarning: false,
+  },
+  worklet: false,
+  refresh: false,
+  mode: 'test',
+};
+
+const code = `\
+import { View } from '@lynx-js/react-components';
+import { Component } from "@lynx-js/react-runtime";
+export class App extends Component {
+  render() {
+    return <View lynx-key="a" onClick={f}><View onTapCatch={g} /></View>;
+  }
+}
+`;
+
+describe('codemod', () => {
+  it('should not apply fixes by default', async () => {
+    const result = await transformReactLynx(code, options);
+    expect(result.codemod).toBeUndefined();
+  });
+
+  it('should apply the fix suggestions to the source', async () => {
+    const result = await transformReactLynx(code, {
+      ...options,
+      codemod: true,
+    });
+    expect(result.errors).toEqual([]);
+    expect(result.codemod).toBe(`
+import { Component } from "@lynx-js/react/legacy-react-runtime";
+export class App extends Component {
+  render() {
+    return <view key="a" bindtap={f}><view catchtap={g} /></view>;
+  }
+}
+`);
+
+    const lynxKey = result.warnings.find(warning =>
+      warning.text === 'DEPRECATED: lynx-key is changed to key'
+    );
+    expect(lynxKey.location.suggestion).toBe('key');
+
+    // The name of an event prop is replaced, not the whole attribute.
+    const onClick = result.warnings.find(warning =>
+      warning.text.includes('"onClick"')
+    );
+    expect(onClick.location.suggestion).toBeUndefined();
+    expect(onClick.notes[0].text).toBe('rename to "bindtap"');
+    expect(onClick.notes[0].location).toMatchObject({
+      length: 7,
+      suggestion: 'bindtap',
+    });
+  });
+});
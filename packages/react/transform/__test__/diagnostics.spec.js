@@ -0,0 +1,57 @@
+// Copyright 2024 The Lynx Authors. All rights reserved.
+// Licensed under the Apache License Version 2.0 that can be found in the
+// LICENSE file in the root directory of this source tree.
+import { describe, expect, it } from 'vitest';
+
+import { transformReactLynx } from '../main.js';
+
+const options = {
+  pluginName: '',
+  filename: 'index.jsx',
+  sourcemap: false,
+  cssScope: false,
+  directiveDCE: false,
+  defineDCE: false,
+  shake: false,
+  compat: {
+    target: 'LEPUS',
+    componentsPkg: ['@lynx-js/react-components'],
+    oldRuntimePkg: ['@lynx-js/react-runtime'],
+    newRuntimePkg: '@lynx-js/react',
+    additionalComponentAttributes: [],
+    addComponentElement: false,
+    simplifyCtorLikeReactLynx2: false,
+    disableDeprecatedWarning: false,
+  },
+  worklet: false,
+  refresh: false,
+  mode: 'test',
+};
+
+const code = `\
+import { View } from '@lynx-js/react-components';
+export const App = () => <View lynx-key="a" onClick={(e) => e.stopPropagation()} />;
+export const lazy = () => import();
+`;
+
+const ids = messages => messages.map(message => message.id);
+
+describe('diagnostics', () => {
+  it('should report every diagnostic with its code', async () => {
+    const { errors, warnings } = await transformReactLynx(code, options);
+    expect(ids(errors)).toEqual(['RL3001']);
+    expect(ids(warnings)).toContain('RL1005');
+    expect(ids(warnings)).toContain('RL1103');
+    expect(warnings.every(warning => warning.id)).toBe(true);
+  });
+
+  it('should override the severity by code', async () => {
+    const { errors, warnings } = await transformReactLynx(code, {
+      ...options,
+      diagnostics: { RL1005: 'off', RL1103: 'error', RL3001: 'warn' },
+    });
+    expect(ids(errors)).toEqual(['RL1103']);
+    expect(ids(warnings)).toContain('RL3001');
+    expect(ids(warnings)).not.toContain('RL1005');
+  });
+});
@@ -0,0 +1,192 @@
+// Copyright 2024 The Lynx Authors. All rights reserved.
+// Licensed under the Apache License Version 2.0 that can be found in the
+// LICENSE file in the root directory of this source tree.
+import { describe, expect, it } from 'vitest';
+
+import {
+  transformReactLynx,
+  transformReactLynxDualThread,
+  transformReactLynxDualThreadSync,
+} from '../main.js';
+
+function options(target, layer = 'main-thread') {
+  return {
+    pluginName: '',
+    filename: 'index.jsx',
+    sourcemap: false,
+    cssScope: false,
+    directiveDCE: { target },
+    defineDCE: false,
+    shake: false,
+    compat: false,
+    worklet: false,
+    refresh: false,
+    mode: 'test',
+    snapshot: {
+      preserveJsx: false,
+      runtimePkg: '@lynx-js/react',
+      filename: 'index.jsx',
+      target,
+    },
+    dynamicImport: {
+      layer,
+      runtimePkg: '@lynx-js/react/internal',
+    },
+  };
+}
+
+function workletOptions(target) {
+  return {
+    ...options(target),
+    worklet: {
+      filename: 'index.jsx',
+      target,
+      runtimePkg: '@lynx-js/react',
+    },
+  };
+}
+
+// The options differing between the threads of the webpack loader,
+// see `getMainThreadTransformOptions` of `@lynx-js/react-webpack-plugin`.
+function loaderOptions(target, layer) {
+  return {
+    ...workletOptions(target),
+    compat: {
+      target,
+      componentsPkg: ['@lynx-js/react-components'],
+      oldRuntimePkg: ['@lynx-js/react-runtime'],
+      newRuntimePkg: '@lynx-js/react',
+      additionalComponentAttributes: [],
+      addComponentElement: false,
+      simplifyCtorLikeReactLynx2: false,
+      disableDeprecatedWarning: false,
+    },
+    dynamicImport: {
+      layer,
+      runtimePkg: '@lynx-js/react/internal',
+    },
+  };
+}
+
+function customPipelineOptions(target, pipeline) {
+  return {
+    ...options(target),
+    cssScope: { mode: 'modules', filename: 'index.jsx' },
+    pipeline,
+  };
+}
+
+const code = `\
+import './index.css';
+import styles from './index.module.css';
+export const App = () => <view id="app" />;
+export const lazy = () => import('./lazy.js');
+`;
+
+describe('transformReactLynxDualThread', () => {
+  it.each([
+    ['the same dynamicImport', options('LEPUS'), options('JS')],
+    [
+      'different dynamicImport',
+      options('LEPUS', 'main-thread'),
+      options('JS', 'background'),
+    ],
+    ['worklet', workletOptions('LEPUS'), workletOptions('JS')],
+    [
+      'the webpack loader options',
+      loaderOptions('LEPUS', 'react__main-thread'),
+      loaderOptions('JS', 'react__background'),
+    ],
+    [
+      'custom pipelines',
+      customPipelineOptions('LEPUS', [
+        'cssScope',
+        'typescript',
+        'dynamicImport',
+        'snapshot',
+        'shake',
+        'react',
+      ]),
+      customPipelineOptions('JS', [
+        'typescript',
+        'cssScope',
+        'dynamicImport',
+        'snapshot',
+        'shake',
+        'react',
+      ]),
+    ],
+  ])('should match two separate transforms with %s', async (
+    _,
+    mainThread,
+    background,
+  ) => {
+    const result = await transformReactLynxDualThread(code, {
+      mainThread,
+      background,
+    });
+    for (const [output, threadOptions] of [
+      [result.mainThread, mainThread],
+      [result.background, background],
+    ]) {
+      const expected = await transformReactLynx(code, threadOptions);
+      expect(output.errors).toEqual([]);
+      expect(output.code).toBe(expected.code);
+      expect(output.metadata).toEqual(expected.metadata);
+    }
+    expect(result.mainThread.code).not.toBe(result.background.code);
+  });
+
+  it('should name the chunks of each thread by its layer', async () => {
+    const result = await transformReactLynxDualThread(
+      'export const lazy = () => import(\'./lazy.js\');',
+      {
+        mainThread: loaderOptions('LEPUS', 'react__main-thread'),
+        background: loaderOptions('JS', 'react__background'),
+      },
+    );
+    expect(result.mainThread.code).toContain('lazy.js-react__main-thread');
+    expect(result.background.code).toContain('lazy.js-react__background');
+    expect(result.background.code).not.toContain('main-thread"');
+  });
+
+  it('should report diagnostics of the shared passes to both threads', async () => {
+    const result = await transformReactLynxDualThread(
+      'export const a = import(\'./a.js\', 1);',
+      { mainThread: options('LEPUS'), background: options('JS') },
+    );
+    expect(result.mainThread.errors).toHaveLength(1);
+    expect(result.background.errors).toEqual(result.mainThread.errors);
+  });
+
+  it('should time out as a whole', async () => {
+    await expect(transformReactLynxDualThread(code, {
+      mainThread: options('LEPUS'),
+      background: options('JS'),
+      timeout: 0,
+    })).rejects.toMatchObject({
+      code: 'Cancelled',
+      message: 'TimeoutError: the transform is not finished within 0ms',
+    });
+  });
+
+  it('should reject unsupported options', () => {
+    const validate = (background) => () =>
+      transformReactLynxDualThreadSync(code, {
+        mainThread: options('LEPUS'),
+        background: { ...options('JS'), ...background },
+      });
+    expect(validate({ filename: 'other.jsx' })).toThrow(
+      '`filename`, `mode`, `syntaxConfig`, `isModule` and `recover` must be the same',
+    );
+    expect(validate({ codemod: true })).toThrow(
+      '`background.codemod` is not supported',
+    );
+    expect(validate({ cacheDir: 'node_modules/.cache' })).toThrow(
+      '`background.cacheDir` is not supported',
+    );
+    expect(validate({ timeout: 1000 })).toThrow(
+      '`background.timeout` is not supported, use `timeout` instead',
+    );
+  });
+});
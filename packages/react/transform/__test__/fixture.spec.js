@@ -144,6 +144,12 @@ describe('jsx', () => {
       });
       ",
         "errors": [],
+        "metadata": {
+          "cssImports": [],
+          "dynamicImports": [],
+          "snapshots": [],
+          "worklets": [],
+        },
         "warnings": [],
       }
     `);
@@ -210,6 +216,33 @@ describe('jsx', () => {
       });
       ",
         "errors": [],
+        "metadata": {
+          "cssImports": [],
+          "dynamicImports": [],
+          "snapshots": [
+            {
+              "id": "__snapshot_da39a_04d8c_1",
+              "location": {
+                "column": 12,
+                "file": "",
+                "length": 45,
+                "line": 1,
+                "lineText": "const jsx = <list><list-item defer item-key="1" /></list>;",
+              },
+            },
+            {
+              "id": "__snapshot_da39a_04d8c_2",
+              "location": {
+                "column": 18,
+                "file": "",
+                "length": 32,
+                "line": 1,
+                "lineText": "const jsx = <list><list-item defer item-key="1" /></list>;",
+              },
+            },
+          ],
+          "worklets": [],
+        },
         "warnings": [],
       }
     `);
@@ -235,11 +268,38 @@ describe('errors and warnings', () => {
             "text": "Expected '</', got '<eof>'",
           },
         ],
+        "metadata": {
+          "cssImports": [],
+          "dynamicImports": [],
+          "snapshots": [],
+          "worklets": [],
+        },
         "warnings": [],
       }
     `);
   });
 
+  it('should recover from syntax error', async () => {
+    const result = await transformReactLynx(`<view>;`, {
+      pluginName: '',
+      filename: '',
+      sourcemap: false,
+      cssScope: false,
+      jsx: false,
+      directiveDCE: false,
+      defineDCE: false,
+      shake: false,
+      compat: false,
+      worklet: false,
+      refresh: false,
+      recover: true,
+    });
+    expect(result.code).toBe(
+      `throw new SyntaxError(":1:8: Expected '</', got '<eof>'");\n`,
+    );
+    expect(result.errors).toHaveLength(1);
+  });
+
   it('should nodiff compat', async () => {
     const result = await transformReactLynx(
       `
@@ -273,8 +333,15 @@ Component, View
       Component, View;
       ",
         "errors": [],
+        "metadata": {
+          "cssImports": [],
+          "dynamicImports": [],
+          "snapshots": [],
+          "worklets": [],
+        },
         "warnings": [
           {
+            "id": "RL1001",
             "location": {
               "column": 0,
               "file": "",
@@ -282,10 +349,23 @@ Component, View
               "line": 2,
               "lineText": "import { View } from "@lynx-js/react-components";",
             },
+            "notes": [
+              {
+                "location": {
+                  "column": 0,
+                  "file": "",
+                  "length": 49,
+                  "line": 2,
+                  "lineText": "import { View } from "@lynx-js/react-components";",
+                },
+                "text": "remove the import",
+              },
+            ],
             "pluginName": "",
             "text": "DEPRECATED: old package "@lynx-js/react-components" is removed",
           },
           {
+            "id": "RL1002",
             "location": {
               "column": 0,
               "file": "",
@@ -293,6 +373,19 @@ Component, View
               "line": 4,
               "lineText": "import { Component } from "@lynx-js/react-runtime";",
             },
+            "notes": [
+              {
+                "location": {
+                  "column": 26,
+                  "file": "",
+                  "length": 24,
+                  "line": 4,
+                  "lineText": "import { Component } from "@lynx-js/react-runtime";",
+                  "suggestion": ""@lynx-js/react/legacy-react-runtime"",
+                },
+                "text": "import from "@lynx-js/react/legacy-react-runtime"",
+              },
+            ],
             "pluginName": "",
             "text": "DEPRECATED: old runtime package "@lynx-js/react-runtime" is changed to "@lynx-js/react"",
           },
@@ -0,0 +1,63 @@
+// Copyright 2024 The Lynx Authors. All rights reserved.
+// Licensed under the Apache License Version 2.0 that can be found in the
+// LICENSE file in the root directory of this source tree.
+import { describe, expect, it } from 'vitest';
+
+import { transformBundleResult, transformReactLynx } from '../main.js';
+
+const options = {
+  pluginName: '',
+  filename: 'index.js',
+  sourcemap: true,
+  cssScope: false,
+  directiveDCE: false,
+  defineDCE: false,
+  shake: false,
+  compat: false,
+  worklet: false,
+  refresh: false,
+  mode: 'test',
+};
+
+const code = 'console.log(1);\n';
+
+// Maps the first column of line 1 to line 10 of `original.ts`.
+const inputSourceMap = JSON.stringify({
+  version: 3,
+  sources: ['original.ts'],
+  names: [],
+  mappings: 'AASA',
+});
+
+describe('inputSourceMap', () => {
+  it('should be chained by transformReactLynx', async () => {
+    const result = await transformReactLynx(code, {
+      ...options,
+      inputSourceMap,
+    });
+    const map = JSON.parse(result.map);
+    expect(map.sources).toEqual(['original.ts']);
+    // The first column of line 1 still maps to line 10.
+    expect(map.mappings.startsWith('AASA')).toBe(true);
+  });
+
+  it('should be chained by transformBundleResult', async () => {
+    const result = await transformBundleResult(code, {
+      pluginName: '',
+      filename: 'main.js',
+      sourcemap: true,
+      extractStr: false,
+      inputSourceMap,
+    });
+    expect(JSON.parse(result.map).sources).toEqual(['original.ts']);
+  });
+
+  it('should warn about an invalid input source map', async () => {
+    const result = await transformReactLynx(code, {
+      ...options,
+      inputSourceMap: '{',
+    });
+    expect(result.code).toBe(code);
+    expect(result.warnings).toHaveLength(1);
+  });
+});
@@ -0,0 +1,69 @@
+// Copyright 2024 The Lynx Authors. All rights reserved.
+// Licensed under the Apache License Version 2.0 that can be found in the
+// LICENSE file in the root directory of this source tree.
+import { describe, expect, it } from 'vitest';
+
+import { transformReactLynx } from '../main.js';
+
+const options = {
+  pluginName: '',
+  filename: 'index.jsx',
+  sourcemap: false,
+  cssScope: { mode: 'all', filename: 'index.jsx' },
+  directiveDCE: false,
+  defineDCE: false,
+  shake: false,
+  compat: false,
+  worklet: {
+    filename: 'index.jsx',
+    target: 'LEPUS',
+    runtimePkg: '@lynx-js/react',
+  },
+  refresh: false,
+  mode: 'test',
+};
+
+const code = `\
+import './foo.css';
+function onTap() {
+  'main thread';
+  console.log('tap');
+}
+export const App = () => <view main-thread:bindtap={onTap} />;
+export const Lazy = () => import('./lazy.js');
+export const Remote = () => import('https://example.com/remote.js');
+`;
+
+describe('metadata', () => {
+  it('should report what the transform created', async () => {
+    const result = await transformReactLynx(code, options);
+    expect(result.errors).toEqual([]);
+    const { snapshots, worklets, cssImports, dynamicImports } =
+      result.metadata;
+
+    expect(snapshots).toHaveLength(1);
+    expect(result.code).toContain(snapshots[0].id);
+    expect(snapshots[0].location).toMatchObject({
+      file: 'index.jsx',
+      line: 6,
+      column: 25,
+    });
+
+    expect(worklets).toHaveLength(1);
+    expect(worklets[0].type).toBe('main-thread');
+    expect(result.code).toContain(worklets[0].hash);
+    expect(worklets[0].location.line).toBe(2);
+
+    expect(cssImports).toHaveLength(1);
+    expect(cssImports[0].src).toBe('./foo.css');
+    expect(result.code).toContain(`./foo.css?cssId=${cssImports[0].cssId}`);
+
+    expect(dynamicImports).toHaveLength(2);
+    expect(dynamicImports[0]).toMatchObject({
+      src: './lazy.js',
+      lazy: false,
+      location: { line: 7 },
+    });
+    expect(dynamicImports[1].lazy).toBe(true);
+  });
+});
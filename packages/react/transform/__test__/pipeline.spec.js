@@ -0,0 +1,50 @@
+// Copyright 2024 The Lynx Authors. All rights reserved.
+// Licensed under the Apache License Version 2.0 that can be found in the
+// LICENSE file in the root directory of this source tree.
+import { describe, expect, it } from 'vitest';
+
+import { transformReactLynx, transformReactLynxSync } from '../main.js';
+
+const options = {
+  pluginName: '',
+  filename: '',
+  sourcemap: false,
+  cssScope: false,
+  directiveDCE: false,
+  defineDCE: false,
+  shake: false,
+  compat: false,
+  worklet: false,
+  refresh: false,
+  mode: 'test',
+};
+
+const code = 'const a: number = 1;\nconst b = <view />;';
+
+describe('pipeline', () => {
+  it('should run only the given passes', async () => {
+    const result = await transformReactLynx(code, {
+      ...options,
+      pipeline: ['typescript'],
+    });
+    expect(result.errors).toEqual([]);
+    expect(result.code).toBe('const a = 1;\nconst b = <view/>;\n');
+  });
+
+  it('should run the built-in pipeline by default', async () => {
+    const result = await transformReactLynx(code, options);
+    expect(result.errors).toEqual([]);
+    expect(result.code).toContain('createSnapshot');
+  });
+
+  it('should reject an invalid pipeline', () => {
+    expect(() =>
+      transformReactLynxSync(code, {
+        ...options,
+        pipeline: ['snapshot', 'list'],
+      })
+    ).toThrow(
+      'Invalid pipeline: `list` (at index 1) must run before `snapshot` (at index 0)',
+    );
+  });
+});
@@ -0,0 +1,44 @@
+// Copyright 2024 The Lynx Authors. All rights reserved.
+// Licensed under the Apache License Version 2.0 that can be found in the
+// LICENSE file in the root directory of this source tree.
+import { describe, expect, it } from 'vitest';
+
+import { transformReactLynx } from '../main.js';
+
+const options = {
+  pluginName: '',
+  filename: '',
+  sourcemap: false,
+  cssScope: false,
+  directiveDCE: false,
+  defineDCE: false,
+  shake: false,
+  compat: false,
+  worklet: false,
+  refresh: false,
+  mode: 'test',
+};
+
+const code = 'const a = <view />;';
+
+describe('profile', () => {
+  it('should not profile by default', async () => {
+    const result = await transformReactLynx(code, options);
+    expect(result.profile).toBeUndefined();
+  });
+
+  it('should profile every pass', async () => {
+    const { profile } = await transformReactLynx(code, {
+      ...options,
+      profile: true,
+    });
+    const names = profile.map(pass => pass.name);
+    expect(names[0]).toBe('fixer');
+    expect(names.at(-1)).toBe('fixer');
+
+    const snapshot = profile.find(pass => pass.name === 'snapshot');
+    expect(snapshot.duration).toBeGreaterThanOrEqual(0);
+    expect(snapshot.nodesAfter).toBeGreaterThan(snapshot.nodesBefore);
+    expect(snapshot.diagnostics).toBe(0);
+  });
+});
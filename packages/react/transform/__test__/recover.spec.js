@@ -0,0 +1,54 @@
+// Copyright 2024 The Lynx Authors. All rights reserved.
+// Licensed under the Apache License Version 2.0 that can be found in the
+// LICENSE file in the root directory of this source tree.
+import { describe, expect, it } from 'vitest';
+
+import { transformReactLynx } from '../main.js';
+
+const options = {
+  pluginName: '',
+  filename: 'index.jsx',
+  sourcemap: false,
+  cssScope: false,
+  directiveDCE: false,
+  defineDCE: false,
+  shake: false,
+  compat: false,
+  worklet: false,
+  refresh: false,
+  mode: 'test',
+};
+
+describe('recover', () => {
+  // `with` is not allowed in modules, which is recoverable.
+  const code = 'export const App = () => <view />;\nwith (a) {}\n';
+
+  it('should not recover by default', async () => {
+    const result = await transformReactLynx(code, options);
+    expect(result.code).toBe('');
+    expect(result.errors).not.toEqual([]);
+  });
+
+  it('should transform recoverable code with the same errors', async () => {
+    const result = await transformReactLynx(code, options);
+    const recovered = await transformReactLynx(code, {
+      ...options,
+      recover: true,
+    });
+    expect(recovered.code).toContain('createSnapshot');
+    expect(recovered.errors).toEqual(result.errors);
+    expect(recovered.errors.every(error => error.location.line === 2))
+      .toBe(true);
+  });
+
+  it('should throw the first error from unrecoverable code', async () => {
+    const result = await transformReactLynx('<view>;', {
+      ...options,
+      recover: true,
+    });
+    expect(result.errors).toHaveLength(1);
+    expect(result.code).toBe(
+      'throw new SyntaxError("index.jsx:1:8: Expected \'</\', got \'<eof>\'");\n',
+    );
+  });
+});
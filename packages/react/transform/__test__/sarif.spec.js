@@ -0,0 +1,67 @@
+// Copyright 2024 The Lynx Authors. All rights reserved.
+// Licensed under the Apache License Version 2.0 that can be found in the
+// LICENSE file in the root directory of this source tree.
+import { describe, expect, it } from 'vitest';
+
+import { formatSarif, transformReactLynx } from '../main.js';
+
+const options = {
+  pluginName: '',
+  filename: 'src/App.jsx',
+  sourcemap: false,
+  cssScope: false,
+  directiveDCE: false,
+  defineDCE: false,
+  shake: false,
+  compat: false,
+  worklet: false,
+  refresh: false,
+  mode: 'test',
+};
+
+describe('formatSarif', () => {
+  it('should format the messages of a transform', async () => {
+    const { errors, warnings } = await transformReactLynx(
+      'export const App = () => <view foo:bar="1" style={1} />;\n',
+      options,
+    );
+    expect(errors.map(error => error.id)).toEqual(['RL4005']);
+    expect(warnings.map(warning => warning.id)).toEqual(['RL4003']);
+
+    const log = JSON.parse(formatSarif(errors, warnings));
+    expect(log.version).toBe('2.1.0');
+    expect(log.runs).toHaveLength(1);
+
+    const [run] = log.runs;
+    expect(run.tool.driver.name).toBe('ReactLynx');
+    const ruleIds = run.tool.driver.rules.map(rule => rule.id);
+    expect(ruleIds).toContain('RL4005');
+    expect(ruleIds).toContain('RL4003');
+
+    expect(run.results).toHaveLength(2);
+    expect(run.results[0]).toMatchObject({
+      level: 'error',
+      ruleId: 'RL4005',
+      ruleIndex: ruleIds.indexOf('RL4005'),
+      message: { text: errors[0].text },
+      locations: [{
+        physicalLocation: {
+          artifactLocation: { uri: 'src/App.jsx' },
+          region: { startLine: 1, startColumn: 32, endColumn: 39 },
+        },
+      }],
+    });
+    expect(run.results[1]).toMatchObject({
+      level: 'warning',
+      ruleId: 'RL4003',
+      ruleIndex: ruleIds.indexOf('RL4003'),
+    });
+  });
+
+  it('should format messages without id or location', () => {
+    const log = JSON.parse(formatSarif([{ text: 'syntax error' }], []));
+    expect(log.runs[0].results).toEqual([
+      { level: 'error', message: { text: 'syntax error' } },
+    ]);
+  });
+});
@@ -1,10 +1,94 @@
-use std::env;
+use std::{
+  env, fs,
+  path::{Path, PathBuf},
+};
+
+use sha1::{Digest, Sha1};
 
 extern crate napi_build;
 
+/// The directories and files the transform is built from, relative to this package.
+const SOURCES: &[&str] = &["src", "crates", "Cargo.toml", "../../../Cargo.lock"];
+
+/// Collect the files under `path` into `files`, skipping the `tests` directories of the plugins.
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) {
+  if path.is_dir() {
+    if path.file_name().is_some_and(|name| name == "tests") {
+      return;
+    }
+    let entries = fs::read_dir(path)
+      .unwrap_or_else(|err| panic!("failed to read directory {}: {err}", path.display()));
+    for entry in entries {
+      let entry =
+        entry.unwrap_or_else(|err| panic!("failed to read directory {}: {err}", path.display()));
+      collect_files(&entry.path(), files);
+    }
+  } else if path.is_file() {
+    files.push(path.to_path_buf());
+  }
+}
+
+/// The content of `file` that the transform is built from.
+///
+/// The unit tests are the trailing `#[cfg(test)] mod tests` of the Rust sources,
+/// and are left out, so that changing a test does not invalidate the transform cache.
+fn source_content(file: &Path) -> Vec<u8> {
+  let content =
+    fs::read(file).unwrap_or_else(|err| panic!("failed to read {}: {err}", file.display()));
+  if file.extension().is_some_and(|ext| ext == "rs") {
+    if let Ok(content) = std::str::from_utf8(&content) {
+      if let Some(index) = content.find("\n#[cfg(test)]\nmod tests {") {
+        return content.as_bytes()[..index].to_vec();
+      }
+    }
+  }
+  content
+}
+
+/// A hash of the paths and contents of `SOURCES`, see `source_content`.
+fn source_hash() -> String {
+  let mut files = vec![];
+  for source in SOURCES {
+    println!("cargo:rerun-if-changed={source}");
+    collect_files(Path::new(source), &mut files);
+  }
+  files.sort();
+
+  let mut hasher = Sha1::new();
+  for file in files {
+    let content = source_content(&file);
+    hasher.update(file.to_string_lossy().as_bytes());
+    hasher.update([0]);
+    hasher.update(&content);
+    hasher.update([0]);
+  }
+  hex::encode(hasher.finalize())
+}
+
 fn main() {
   napi_build::setup();
 
+  // The version of `@lynx-js/react` is used to invalidate the transform cache,
+  // and as the tool version of SARIF logs
+  println!("cargo:rerun-if-changed=../package.json");
+  let package_json = fs::read_to_string("../package.json")
+    .expect("failed to read the package.json of @lynx-js/react");
+  let package_json: serde_json::Value = serde_json::from_str(&package_json)
+    .expect("failed to parse the package.json of @lynx-js/react");
+  println!(
+    "cargo:rustc-env=LYNX_REACT_VERSION={}",
+    package_json["version"]
+      .as_str()
+      .expect("the package.json of @lynx-js/react has no version")
+  );
+
+  // The sources also invalidate the transform cache, since the transform may change
+  // without a new version of `@lynx-js/react`
+  println!(
+    "cargo:rustc-env=LYNX_REACT_TRANSFORM_SOURCE_HASH={}",
+    source_hash()
+  );
+
   if env::var("CARGO_CFG_TARGET_OS").unwrap() == "linux" {
     println!("cargo:rustc-link-arg=-Wl,--unresolved-symbols=ignore-all");
   }
@@ -8,7 +8,10 @@ use swc_core::common::comments::Comments;
 use swc_core::common::util::take::Take;
 use swc_core::common::Span;
 use swc_core::{
-  common::{errors::HANDLER, DUMMY_SP},
+  common::{
+    errors::{Applicability, DiagnosticId, HANDLER},
+    DUMMY_SP,
+  },
   ecma::{
     ast::*,
     utils::{prepend_stmt, private_ident},
@@ -17,7 +20,7 @@ use swc_core::{
   quote,
 };
 
-use swc_plugins_shared::target::TransformTarget;
+use swc_plugins_shared::{diagnostics, target::TransformTarget};
 
 mod is_component_class;
 mod simplify_ctor_like_react_lynx_2;
@@ -26,6 +29,14 @@ pub mod napi;
 
 type Stack<T> = Vec<T>;
 
+/// `value` as a string literal, quoted the same way as `src`.
+fn quote_like(src: &Str, value: &str) -> String {
+  match src.raw.as_ref().and_then(|raw| raw.chars().next()) {
+    Some('\'') => format!("'{value}'"),
+    _ => format!("\"{value}\""),
+  }
+}
+
 #[derive(Deserialize, Debug, Clone, PartialEq)]
 pub enum Either<A, B> {
   A(A),
@@ -517,9 +528,16 @@ where
       if !self.opts.disable_deprecated_warning {
         HANDLER.with(|handler| {
           handler
-            .struct_span_warn(
+            .struct_span_warn_with_code(
               n.span,
               format!("DEPRECATED: old package \"{}\" is removed", n.src.value).as_str(),
+              DiagnosticId::Lint(diagnostics::DEPRECATED_COMPONENTS_PKG.into()),
+            )
+            .span_suggestion_with_applicability(
+              n.span,
+              "remove the import",
+              String::new(),
+              Applicability::MachineApplicable,
             )
             .emit()
         });
@@ -529,16 +547,25 @@ where
     }
 
     if self.opts.old_runtime_pkg.contains(&n.src.value.to_string()) {
+      let legacy_runtime_pkg = format!("{}/legacy-react-runtime", self.opts.new_runtime_pkg);
+
       if !self.opts.disable_deprecated_warning {
         HANDLER.with(|handler| {
           handler
-            .struct_span_warn(
+            .struct_span_warn_with_code(
               n.span,
               format!(
                 "DEPRECATED: old runtime package \"{}\" is changed to \"{}\"",
                 n.src.value, self.opts.new_runtime_pkg
               )
               .as_str(),
+              DiagnosticId::Lint(diagnostics::DEPRECATED_RUNTIME_PKG.into()),
+            )
+            .span_suggestion_with_applicability(
+              n.src.span,
+              format!("import from \"{legacy_runtime_pkg}\"").as_str(),
+              quote_like(&n.src, &legacy_runtime_pkg),
+              Applicability::MachineApplicable,
             )
             .emit()
         });
@@ -547,7 +574,7 @@ where
       n.src = Box::new(Str {
         span: DUMMY_SP,
         raw: None,
-        value: format!("{}/legacy-react-runtime", self.opts.new_runtime_pkg.clone()).into(),
+        value: legacy_runtime_pkg.into(),
       });
 
       self.is_old_runtime_pkg = true;
@@ -670,7 +697,7 @@ where
       if !self.opts.disable_deprecated_warning {
         HANDLER.with(|handler| {
                     handler
-                        .struct_span_warn(n.span, format!("DEPRECATED: syntax `<component is=? />` is deprecated, use `lazy` and `loadLazyBundle` exported from \"{}\" instead.", self.opts.new_runtime_pkg).as_str())
+                        .struct_span_warn_with_code(n.span, format!("DEPRECATED: syntax `<component is=? />` is deprecated, use `lazy` and `loadLazyBundle` exported from \"{}\" instead.", self.opts.new_runtime_pkg).as_str(), DiagnosticId::Lint(diagnostics::DEPRECATED_COMPONENT_IS.into()))
                         .emit()
                 });
       }
@@ -748,9 +775,10 @@ where
             ) {
               HANDLER.with(|handler| {
                 handler
-                  .struct_span_warn(
+                  .struct_span_warn_with_code(
                     spread.dot3_token,
                     "addComponentElement: component with JSXSpread is ignored to avoid badcase, you can switch addComponentElement.compilerOnly to false to enable JSXSpread support",
+                    DiagnosticId::Lint(diagnostics::COMPONENT_SPREAD_IGNORED.into()),
                   )
                   .emit()
               });
@@ -794,9 +822,10 @@ where
           if ident_str == "item-key" {
             HANDLER.with(|handler| {
               handler
-                .struct_span_warn(
+                .struct_span_warn_with_code(
                   ident.span,
                   "BROKEN: \"item-key\" in component props takes no effect, this may indicate that your code is not fully migrated",
+                  DiagnosticId::Lint(diagnostics::BROKEN_ITEM_KEY.into()),
                 )
                 .emit()
             });
@@ -952,14 +981,21 @@ where
       None
     }
 
-    let warning_transform_event_name = |old_name, new_name| {
+    let warning_transform_event_name = |old_name, new_name: &str, name_span| {
       if !self.opts.disable_deprecated_warning {
         HANDLER.with(|handler| {
           handler
-            .struct_span_warn(
+            .struct_span_warn_with_code(
               n.span,
               format!("DEPRECATED: old event props \"{old_name}\" is changed to \"{new_name}\"",)
                 .as_str(),
+              DiagnosticId::Lint(diagnostics::DEPRECATED_EVENT_PROPS.into()),
+            )
+            .span_suggestion_with_applicability(
+              name_span,
+              format!("rename to \"{new_name}\"").as_str(),
+              new_name.to_string(),
+              Applicability::MachineApplicable,
             )
             .emit()
         });
@@ -970,13 +1006,13 @@ where
       match &n.name {
         JSXAttrName::Ident(id) => {
           if let Some(new_name) = transform_event_name(id.sym.to_string().as_str()) {
-            warning_transform_event_name(&id.sym, &new_name);
+            warning_transform_event_name(&id.sym, &new_name, id.span);
             n.name = JSXAttrName::Ident(IdentName::new(new_name.into(), id.span));
           }
         }
         JSXAttrName::JSXNamespacedName(JSXNamespacedName { ns, name, span }) => {
           if let Some(new_name) = transform_event_name(name.sym.to_string().as_str()) {
-            warning_transform_event_name(&name.sym, &new_name);
+            warning_transform_event_name(&name.sym, &new_name, name.span);
             n.name = JSXAttrName::JSXNamespacedName(JSXNamespacedName {
               ns: ns.clone(),
               name: IdentName::new(new_name.into(), name.span),
@@ -993,7 +1029,17 @@ where
           if !self.opts.disable_deprecated_warning {
             HANDLER.with(|handler| {
               handler
-                .struct_span_warn(id.span, "DEPRECATED: lynx-key is changed to key")
+                .struct_span_warn_with_code(
+                  id.span,
+                  "DEPRECATED: lynx-key is changed to key",
+                  DiagnosticId::Lint(diagnostics::DEPRECATED_LYNX_KEY.into()),
+                )
+                .span_suggestion_with_applicability(
+                  id.span,
+                  "rename to \"key\"",
+                  "key".into(),
+                  Applicability::MachineApplicable,
+                )
                 .emit()
             });
           }
@@ -1021,13 +1067,20 @@ where
             if !self.opts.disable_deprecated_warning {
               HANDLER.with(|handler| {
                 handler
-                  .struct_span_warn(
+                  .struct_span_warn_with_code(
                     id.span,
                     format!(
                       "DEPRECATED: old JSXElementName \"{}\" is changed to \"{}\"",
                       id.sym, new_id_str
                     )
                     .as_str(),
+                    DiagnosticId::Lint(diagnostics::DEPRECATED_ELEMENT_NAME.into()),
+                  )
+                  .span_suggestion_with_applicability(
+                    id.span,
+                    format!("rename to \"{new_id_str}\"").as_str(),
+                    new_id_str.clone(),
+                    Applicability::MachineApplicable,
                   )
                   .emit()
               });
@@ -1051,9 +1104,10 @@ where
           if id.sym == "stopPropagation" {
             HANDLER.with(|handler| {
               handler
-              .struct_span_warn(
+              .struct_span_warn_with_code(
                 n.span,
                 "BROKEN: e.stopPropagation() takes no effect and MUST be migrated in ReactLynx 3.0",
+                DiagnosticId::Lint(diagnostics::BROKEN_STOP_PROPAGATION.into()),
               )
               .emit()
             });
@@ -1065,9 +1119,10 @@ where
               "getNodeRef" | "getNodeRefFromRoot" | "createSelectorQuery" => {
                 HANDLER.with(|handler| {
                                     handler
-                                        .struct_span_warn(
+                                        .struct_span_warn_with_code(
                                             n.span,
                                             format!("BROKEN: {} on component instance is broken and MUST be migrated in ReactLynx 3.0, please use ref or lynx.createSelectorQuery instead.", id.sym).as_str(),
+                                            DiagnosticId::Lint(diagnostics::BROKEN_INSTANCE_QUERY.into()),
                                         )
                                         .emit()
                                 });
@@ -1075,9 +1130,10 @@ where
               "getElementById" => {
                 HANDLER.with(|handler| {
                                 handler
-                                    .struct_span_warn(
+                                    .struct_span_warn_with_code(
                                         n.span,
                                         format!("BROKEN: {} on component instance is broken and MUST be migrated in ReactLynx 3.0, please use ref or lynx.getElementById instead.", id.sym).as_str(),
+                                        DiagnosticId::Lint(diagnostics::BROKEN_INSTANCE_QUERY.into()),
                                     )
                                     .emit()
                             });
@@ -1184,9 +1240,10 @@ where
           if ident.sym == "config" {
             HANDLER.with(|handler| {
               handler
-                .struct_span_warn(
+                .struct_span_warn_with_code(
                   *span,
                   "BROKEN: supporting for class property `config` is removed and MUST be migrated in ReactLynx 3.0, you should put your configs inside `pageConfig` in lynx.config.js",
+                  DiagnosticId::Lint(diagnostics::BROKEN_CONFIG_PROPERTY.into()),
                 )
                 .emit()
             });
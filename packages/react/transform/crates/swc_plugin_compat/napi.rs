@@ -2,6 +2,7 @@ use std::vec;
 
 use napi::Either as NapiEither;
 use napi_derive::napi;
+use serde::Serialize;
 use swc_core::common::comments::Comments;
 use swc_core::ecma::{ast::*, visit::VisitMut};
 
@@ -13,7 +14,7 @@ use crate::{
 };
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct DarkModeConfig {
   /// @public
   /// Theme expression to be used for dark mode
@@ -69,7 +70,7 @@ fn convert_dark_mode_option_back(
 /// {@inheritdoc CompatVisitorConfig.addComponentElement}
 /// @public
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct AddComponentElementConfig {
   /// @public
   /// Whether to only add component element during compilation
@@ -136,7 +137,7 @@ impl From<Either<bool, CoreAddComponentElementConfig>>
 /// {@inheritdoc PluginReactLynxOptions.compat}
 /// @public
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct CompatVisitorConfig {
   /// @internal
   #[napi(ts_type = "'LEPUS' | 'JS' | 'MIXED'")]
@@ -279,6 +280,7 @@ pub struct CompatVisitorConfig {
   ///   ],
   /// })
   /// ```
+  #[serde(serialize_with = "swc_plugins_shared::napi_serde::either")]
   pub add_component_element: NapiEither<bool, AddComponentElementConfig>,
   /// @public
   /// Whether to simplify constructor calls like ReactLynx 2
@@ -348,6 +350,12 @@ pub struct CompatVisitorConfig {
   /// @remarks
   /// Default value: `false`
   ///
+  /// Prefer turning off the codes `RL1001` to `RL1006` with the `diagnostics` option of the transform,
+  /// which also works for other warnings.
+  ///
+  /// This option takes precedence over `diagnostics`: the deprecated warnings are not emitted at all,
+  /// so they cannot be reported as errors by `diagnostics` either.
+  ///
   /// @example
   ///
   /// Disable all the `DEPRECATED:` warnings.
@@ -390,6 +398,7 @@ pub struct CompatVisitorConfig {
   ///   ],
   /// })
   /// ```
+  #[serde(serialize_with = "swc_plugins_shared::napi_serde::option_either")]
   pub dark_mode: Option<NapiEither<bool, DarkModeConfig>>,
 }
 
@@ -2,7 +2,7 @@ use regex::Regex;
 use swc_core::{
   common::{
     comments::{Comment, CommentKind, Comments},
-    DUMMY_SP,
+    Span, DUMMY_SP,
   },
   ecma::{
     ast::*,
@@ -50,6 +50,16 @@ impl Default for CSSScopeVisitorConfig {
   }
 }
 
+/// A CSS import rewritten by `CSSScopeVisitor` with `?cssId=`.
+#[derive(Clone, Debug)]
+pub struct ScopedCSSImport {
+  /// The original import source, without `?cssId=`.
+  pub src: String,
+  pub css_id: usize,
+  /// Span of the import declaration.
+  pub span: Span,
+}
+
 pub struct CSSScopeVisitor<C>
 where
   C: Comments,
@@ -61,6 +71,8 @@ where
   css_id: usize,
 
   has_jsx: bool,
+
+  pub scoped_imports: Vec<ScopedCSSImport>,
 }
 
 impl<C> CSSScopeVisitor<C>
@@ -77,6 +89,7 @@ where
       comments,
       cfg,
       has_jsx: false,
+      scoped_imports: vec![],
     }
   }
 }
@@ -135,6 +148,11 @@ where
         // Is CSS files
         //
         // Add cssId to the import
+        self.scoped_imports.push(ScopedCSSImport {
+          src: import_decl.src.value.to_string(),
+          css_id: self.css_id,
+          span: import_decl.span,
+        });
         import_decl.src = Box::new(Str {
           span: import_decl.src.span,
           raw: None,
@@ -1,13 +1,15 @@
 use crate::{
   CSSScope as CoreCSSScope, CSSScopeVisitor as CoreVisitor, CSSScopeVisitorConfig as CoreConfig,
+  ScopedCSSImport,
 };
 use napi_derive::napi;
+use serde::Serialize;
 use swc_core::{
   common::comments::Comments,
   ecma::{ast::*, visit::VisitMut},
 };
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize)]
 pub enum CSSScope {
   All,
   None,
@@ -75,7 +77,7 @@ impl From<CoreCSSScope> for CSSScope {
 }
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct CSSScopeVisitorConfig {
   #[napi(ts_type = "'all' | 'none' | 'modules'")]
   /// @public
@@ -128,6 +130,11 @@ where
       inner: CoreVisitor::new(cfg.into(), comments),
     }
   }
+
+  /// Take the CSS imports rewritten so far.
+  pub fn take_scoped_imports(&mut self) -> Vec<ScopedCSSImport> {
+    std::mem::take(&mut self.inner.scoped_imports)
+  }
 }
 
 impl<C> VisitMut for CSSScopeVisitor<C>
@@ -2,6 +2,7 @@ use std::{collections::HashMap, fmt::Debug};
 
 use crate::DefineDCEVisitorConfig as CoreConfig;
 use napi_derive::napi;
+use serde::Serialize;
 
 impl From<DefineDCEVisitorConfig> for CoreConfig {
   fn from(val: DefineDCEVisitorConfig) -> Self {
@@ -18,7 +19,7 @@ impl From<CoreConfig> for DefineDCEVisitorConfig {
 /// {@inheritdoc PluginReactLynxOptions.defineDCE}
 /// @public
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct DefineDCEVisitorConfig {
   /// @public
   /// Replaces variables in your code with other values or expressions at compile time.
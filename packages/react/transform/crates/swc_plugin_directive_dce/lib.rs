@@ -1,13 +1,16 @@
 use std::fmt::Debug;
 use swc_core::{
-  common::{errors::HANDLER, Span},
+  common::{
+    errors::{DiagnosticId, HANDLER},
+    Span,
+  },
   ecma::{
     ast::*,
     visit::{VisitMut, VisitMutWith},
   },
 };
 
-use swc_plugins_shared::target::TransformTarget;
+use swc_plugins_shared::{diagnostics, target::TransformTarget};
 
 pub mod napi;
 
@@ -93,7 +96,11 @@ impl VisitMut for DirectiveDCEVisitor {
             if let Some(span) = span {
               HANDLER.with(|handler| {
                 handler
-                  .struct_span_warn(span, "directive inside constructor is not allowed")
+                  .struct_span_warn_with_code(
+                    span,
+                    "directive inside constructor is not allowed",
+                    DiagnosticId::Lint(diagnostics::DIRECTIVE_IN_CONSTRUCTOR.into()),
+                  )
                   .emit();
               });
             }
@@ -131,7 +138,11 @@ impl VisitMut for DirectiveDCEVisitor {
             if let Some(span) = span {
               HANDLER.with(|handler| {
                 handler
-                  .struct_span_warn(span, "directive inside getter/setter is ignored")
+                  .struct_span_warn_with_code(
+                    span,
+                    "directive inside getter/setter is ignored",
+                    DiagnosticId::Lint(diagnostics::DIRECTIVE_IN_ACCESSOR.into()),
+                  )
                   .emit();
               });
             }
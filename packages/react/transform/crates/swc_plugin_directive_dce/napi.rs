@@ -1,4 +1,5 @@
 use napi_derive::napi;
+use serde::Serialize;
 use std::fmt::Debug;
 use swc_core::ecma::{ast::*, visit::VisitMut};
 
@@ -6,7 +7,7 @@ use crate::{DirectiveDCEVisitor as CoreVisitor, DirectiveDCEVisitorConfig as Cor
 use swc_plugins_shared::target_napi::TransformTarget;
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct DirectiveDCEVisitorConfig {
   /// @internal
   #[napi(ts_type = "'LEPUS' | 'JS' | 'MIXED'")]
@@ -4,9 +4,9 @@ use serde_json::Value;
 use swc_core::{
   common::{
     comments::{Comment, CommentKind, Comments},
-    errors::HANDLER,
+    errors::{DiagnosticId, HANDLER},
     util::take::Take,
-    Spanned, DUMMY_SP,
+    Span, Spanned, DUMMY_SP,
   },
   ecma::{
     ast::*,
@@ -15,7 +15,7 @@ use swc_core::{
   },
 };
 
-use swc_plugins_shared::utils::jsonify;
+use swc_plugins_shared::{diagnostics, utils::jsonify};
 
 pub mod napi;
 
@@ -36,6 +36,18 @@ impl Default for DynamicImportVisitorConfig {
   }
 }
 
+/// An `import()` call rewritten by `DynamicImportVisitor`.
+#[derive(Clone, Debug)]
+pub struct RewrittenDynamicImport {
+  /// The module id, `None` if it is not a string literal.
+  pub src: Option<String>,
+  /// Whether the call is replaced with `__dynamicImport`,
+  /// otherwise a `webpackChunkName` comment is added.
+  pub lazy: bool,
+  /// Span of the `import()` call.
+  pub span: Span,
+}
+
 pub struct DynamicImportVisitor<C>
 where
   C: Comments,
@@ -43,6 +55,7 @@ where
   opts: DynamicImportVisitorConfig,
   named_imports: HashSet<Ident>,
   comments: Option<C>,
+  pub rewritten_imports: Vec<RewrittenDynamicImport>,
 }
 
 impl<C> Default for DynamicImportVisitor<C>
@@ -63,6 +76,7 @@ where
       opts,
       comments,
       named_imports: HashSet::new(),
+      rewritten_imports: vec![],
     }
   }
 }
@@ -121,11 +135,12 @@ where
     if call_expr.args.is_empty() {
       HANDLER.with(|handler| {
         handler
-          .struct_span_err(
+          .struct_span_err_with_code(
             call_expr.span,
             "`import()` with no argument is not allowed"
               .to_string()
               .as_str(),
+            DiagnosticId::Error(diagnostics::IMPORT_WITHOUT_ARGUMENT.into()),
           )
           .emit()
       });
@@ -152,11 +167,12 @@ where
     if is_import_call_lit && !is_import_call_str_lit {
       HANDLER.with(|handler| {
         handler
-          .struct_span_err(
+          .struct_span_err_with_code(
             call_expr.span,
             "`import(...)` call with non-string literal module id is not allowed"
               .to_string()
               .as_str(),
+            DiagnosticId::Error(diagnostics::IMPORT_NON_STRING_LITERAL.into()),
           )
           .emit()
       });
@@ -175,11 +191,12 @@ where
       if has_option && !is_import_call_with_type {
         HANDLER.with(|handler| {
           handler
-            .struct_span_err(
+            .struct_span_err_with_code(
               call_expr.span,
               "`import(\"...\", ...)` with invalid options is not allowed"
                 .to_string()
                 .as_str(),
+              DiagnosticId::Error(diagnostics::IMPORT_INVALID_OPTIONS.into()),
             )
             .emit()
         });
@@ -187,6 +204,11 @@ where
         return;
       }
 
+      self.rewritten_imports.push(RewrittenDynamicImport {
+        src: Some(str_lit.to_string()),
+        lazy: false,
+        span: call_expr.span,
+      });
       self.comments.add_leading(
         call_expr.args[0].span_lo(),
         Comment {
@@ -196,6 +218,11 @@ where
         },
       );
     } else {
+      self.rewritten_imports.push(RewrittenDynamicImport {
+        src: is_import_call_str_lit.then(|| str_lit.to_string()),
+        lazy: true,
+        span: call_expr.span,
+      });
       let ident: Ident = "__dynamicImport".into();
       *call_expr = CallExpr {
         ctxt: call_expr.ctxt,
@@ -1,15 +1,19 @@
 use std::fmt::Debug;
 
 use napi_derive::napi;
+use serde::Serialize;
 use swc_core::{
   common::comments::Comments,
   ecma::{ast::*, visit::VisitMut},
 };
 
-use crate::{DynamicImportVisitor as CoreVisitor, DynamicImportVisitorConfig as CoreConfig};
+use crate::{
+  DynamicImportVisitor as CoreVisitor, DynamicImportVisitorConfig as CoreConfig,
+  RewrittenDynamicImport,
+};
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct DynamicImportVisitorConfig {
   /// @internal
   pub runtime_pkg: String,
@@ -69,6 +73,11 @@ where
       inner: CoreVisitor::new(cfg.into(), comments),
     }
   }
+
+  /// Take the `import()` calls rewritten so far.
+  pub fn take_rewritten_imports(&mut self) -> Vec<RewrittenDynamicImport> {
+    std::mem::take(&mut self.inner.rewritten_imports)
+  }
 }
 
 impl<C> VisitMut for DynamicImportVisitor<C>
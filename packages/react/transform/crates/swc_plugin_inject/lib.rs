@@ -1,7 +1,12 @@
 use std::{collections::HashMap, fmt::Debug};
 
 use swc_core::{
-  common::{errors::HANDLER, sync::Lrc, util::take::Take, FileName, Mark, SourceMap, DUMMY_SP},
+  common::{
+    errors::{DiagnosticId, HANDLER},
+    sync::Lrc,
+    util::take::Take,
+    FileName, Mark, SourceMap, DUMMY_SP,
+  },
   ecma::{
     ast::*,
     parser::{lexer::Lexer, PResult, Parser, StringInput},
@@ -11,6 +16,8 @@ use swc_core::{
   },
 };
 
+use swc_plugins_shared::diagnostics;
+
 pub mod napi;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -73,9 +80,10 @@ impl VisitMut for InjectVisitor {
                   Err(e) => {
                     HANDLER.with(|handler| {
                       handler
-                        .struct_span_err(
+                        .struct_span_err_with_code(
                           i.span,
                           format!("parse define failed: {}", e.kind().msg()).as_str(),
+                          DiagnosticId::Error(diagnostics::INJECT_PARSE_FAILED.into()),
                         )
                         .emit();
                     });
@@ -1,6 +1,7 @@
 use std::{collections::HashMap, fmt::Debug};
 
 use napi_derive::napi;
+use serde::Serialize;
 use swc_core::{
   common::Mark,
   ecma::{ast::*, visit::VisitMut},
@@ -10,7 +11,7 @@ use crate::{
   InjectAs as CoreInjectAs, InjectVisitor as CoreVisitor, InjectVisitorConfig as CoreConfig,
 };
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize)]
 pub enum InjectAs {
   Expr(String),
   ImportDefault(String),
@@ -98,7 +99,7 @@ impl From<CoreInjectAs> for InjectAs {
 }
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct InjectVisitorConfig {
   #[napi(
     ts_type = "Record<string, ['expr', string] | ['importDefault', string] | ['importStarAs', string] | ['importNamed', string, string]>"
@@ -1,4 +1,5 @@
 use napi_derive::napi;
+use serde::Serialize;
 use swc_core::{ecma::ast::*, ecma::visit::VisitMut};
 
 use crate::{ShakeVisitor as CoreVisitor, ShakeVisitorConfig as CoreConfig};
@@ -6,7 +7,7 @@ use crate::{ShakeVisitor as CoreVisitor, ShakeVisitorConfig as CoreConfig};
 /// {@inheritdoc PluginReactLynxOptions.shake}
 /// @public
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ShakeVisitorConfig {
   /// Package names to identify runtime imports that need to be processed
   ///
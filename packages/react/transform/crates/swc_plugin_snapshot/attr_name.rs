@@ -59,17 +59,19 @@ impl From<Ident> for AttrName {
 }
 
 impl AttrName {
-  pub fn from_ns(ns: Ident, name: Ident) -> Self {
+  /// The attribute name of `ns:name`, or `None` if it is not a worklet event,
+  /// a worklet ref or a gesture.
+  pub fn from_ns(ns: Ident, name: Ident) -> Option<Self> {
     let ns_str = ns.sym.as_ref().to_string();
     let name_str = name.sym.as_ref().to_string();
     if name_str == "ref" {
-      AttrName::WorkletRef(ns_str)
+      Some(AttrName::WorkletRef(ns_str))
     } else if let Some((event_type, event_name)) = get_event_type_and_name(name_str.as_str()) {
-      AttrName::WorkletEvent(ns_str, event_type, event_name)
+      Some(AttrName::WorkletEvent(ns_str, event_type, event_name))
     } else if name_str == "gesture" {
-      AttrName::Gesture(ns_str)
+      Some(AttrName::Gesture(ns_str))
     } else {
-      todo!()
+      None
     }
   }
 }
@@ -7,7 +7,7 @@ use once_cell::sync::Lazy;
 use swc_core::{
   common::{
     comments::{CommentKind, Comments},
-    errors::HANDLER,
+    errors::{DiagnosticId, HANDLER},
     util::take::Take,
     Mark, Span, Spanned, SyntaxContext, DUMMY_SP,
   },
@@ -26,6 +26,7 @@ pub mod napi;
 
 use swc_plugins_shared::{
   css::get_string_inline_style_from_literal,
+  diagnostics,
   jsx_helpers::{
     jsx_attr_name, jsx_attr_to_prop, jsx_attr_value, jsx_children_to_expr,
     jsx_is_children_full_dynamic, jsx_is_custom, jsx_is_list, jsx_is_list_item, jsx_name,
@@ -232,6 +233,68 @@ impl DynamicPart {
   }
 }
 
+fn emit_unsupported_attr_value(value: &JSXAttrValue) {
+  HANDLER.with(|handler| {
+    handler
+      .struct_span_err_with_code(
+        value.span(),
+        "JSX elements are not supported as attribute values, wrap it in `{}` instead",
+        DiagnosticId::Error(diagnostics::UNSUPPORTED_ATTR_VALUE.into()),
+      )
+      .emit()
+  });
+}
+
+/// Report JSX spread children as errors, and turn them into expression containers
+/// so that the rest of the transform can go on.
+struct SpreadChildRewriter;
+
+impl VisitMut for SpreadChildRewriter {
+  fn visit_mut_jsx_element_child(&mut self, n: &mut JSXElementChild) {
+    n.visit_mut_children_with(self);
+
+    if let JSXElementChild::JSXSpreadChild(JSXSpreadChild { span, expr }) = n {
+      HANDLER.with(|handler| {
+        handler
+          .struct_span_err_with_code(
+            *span,
+            "JSX spread children are not supported",
+            DiagnosticId::Error(diagnostics::UNSUPPORTED_SPREAD_CHILD.into()),
+          )
+          .emit()
+      });
+      *n = JSXElementChild::JSXExprContainer(JSXExprContainer {
+        span: *span,
+        expr: JSXExpr::Expr(expr.take()),
+      });
+    }
+  }
+}
+
+/// Erase what differs between structurally identical snapshots, i.e. spans, syntax contexts
+/// of the private identifiers like `el`, and raw literals, so they compare and hash equal.
+struct StructureNormalizer;
+
+impl VisitMut for StructureNormalizer {
+  fn visit_mut_span(&mut self, n: &mut Span) {
+    *n = DUMMY_SP;
+  }
+
+  fn visit_mut_syntax_context(&mut self, n: &mut SyntaxContext) {
+    *n = SyntaxContext::empty();
+  }
+
+  fn visit_mut_str(&mut self, n: &mut Str) {
+    n.visit_mut_children_with(self);
+    n.raw = None;
+  }
+
+  fn visit_mut_number(&mut self, n: &mut Number) {
+    n.visit_mut_children_with(self);
+    n.raw = None;
+  }
+}
+
 pub struct DynamicPartExtractor<'a, V>
 where
   V: VisitMut,
@@ -518,7 +581,8 @@ where
           .attrs
           .iter_mut()
           .for_each(|attr_or_spread| match attr_or_spread {
-            JSXAttrOrSpread::SpreadElement(_) => todo!(),
+            // spread attributes are handled by `has_spread_element` above
+            JSXAttrOrSpread::SpreadElement(_) => {}
             JSXAttrOrSpread::JSXAttr(JSXAttr { name, value, .. }) => {
               if let Some(JSXAttrValue::Lit(Lit::Str(s))) = value {
                 let transformed_value = transform_jsx_attr_str(&s.value);
@@ -583,8 +647,9 @@ where
                           expr: JSXExpr::JSXEmptyExpr(_),
                           ..
                         })) => {}
-                        Some(JSXAttrValue::JSXElement(_)) => unreachable!("Unexpected JSXElement in JSX attribute value - not supported"),
-                        Some(JSXAttrValue::JSXFragment(_)) => unreachable!("Unexpected JSXFragment in JSX attribute value - not supported"),
+                        Some(value @ (JSXAttrValue::JSXElement(_) | JSXAttrValue::JSXFragment(_))) => {
+                          emit_unsupported_attr_value(value);
+                        }
                       };
                     }
                     AttrName::Dataset(name) => {
@@ -621,8 +686,9 @@ where
                           expr: JSXExpr::JSXEmptyExpr(_),
                           ..
                         })) => {}
-                        Some(JSXAttrValue::JSXElement(_)) => unreachable!("Unexpected JSXElement in JSX attribute value - not supported"),
-                        Some(JSXAttrValue::JSXFragment(_)) => unreachable!("Unexpected JSXFragment in JSX attribute value - not supported"),
+                        Some(value @ (JSXAttrValue::JSXElement(_) | JSXAttrValue::JSXFragment(_))) => {
+                          emit_unsupported_attr_value(value);
+                        }
                       };
                     }
                     AttrName::Event(..) | AttrName::Ref => {
@@ -687,8 +753,9 @@ where
                           expr: JSXExpr::JSXEmptyExpr(_),
                           ..
                         })) => {}
-                        Some(JSXAttrValue::JSXElement(_)) => unreachable!("Unexpected JSXElement in JSX attribute value - not supported"),
-                        Some(JSXAttrValue::JSXFragment(_)) => unreachable!("Unexpected JSXFragment in JSX attribute value - not supported"),
+                        Some(value @ (JSXAttrValue::JSXElement(_) | JSXAttrValue::JSXFragment(_))) => {
+                          emit_unsupported_attr_value(value);
+                        }
                       };
                     }
                     AttrName::Class => {
@@ -726,8 +793,9 @@ where
                           expr: JSXExpr::JSXEmptyExpr(_),
                           ..
                         })) => {}
-                        Some(JSXAttrValue::JSXElement(_)) => unreachable!("Unexpected JSXElement in JSX attribute value - not supported"),
-                        Some(JSXAttrValue::JSXFragment(_)) => unreachable!("Unexpected JSXFragment in JSX attribute value - not supported"),
+                        Some(value @ (JSXAttrValue::JSXElement(_) | JSXAttrValue::JSXFragment(_))) => {
+                          emit_unsupported_attr_value(value);
+                        }
                       };
                     }
                     AttrName::ID => {
@@ -755,11 +823,14 @@ where
                           expr: JSXExpr::JSXEmptyExpr(_),
                           ..
                         })) => {}
-                        Some(JSXAttrValue::JSXElement(_)) => unreachable!("Unexpected JSXElement in JSX attribute value - not supported"),
-                        Some(JSXAttrValue::JSXFragment(_)) => unreachable!("Unexpected JSXFragment in JSX attribute value - not supported"),
+                        Some(value @ (JSXAttrValue::JSXElement(_) | JSXAttrValue::JSXFragment(_))) => {
+                          emit_unsupported_attr_value(value);
+                        }
                       };
                     }
-                    AttrName::ListItemPlatformInfo => unreachable!("Unexpected ListItemPlatformInfo attribute in static JSX processing"),
+                    // `AttrName::from` never returns it, the platform info of `<list-item>` is picked above,
+                    // and it is set as a plain attribute on any other element.
+                    AttrName::ListItemPlatformInfo => debug_assert!(false, "Unexpected ListItemPlatformInfo attribute in static JSX processing"),
                     AttrName::WorkletEvent(..) | AttrName::WorkletRef(..) => {
                       unreachable!("A worklet event should have an attribute namespace.")
                     }
@@ -769,23 +840,26 @@ where
                   }
                 }
                 JSXAttrName::JSXNamespacedName(JSXNamespacedName { ns, name, .. }) => {
-                  let attr_name: AttrName = AttrName::from_ns(ns.clone().into(), name.clone().into());
-                  match attr_name {
-                    AttrName::WorkletEvent(..) | AttrName::WorkletRef(..) => {
+                  match AttrName::from_ns(ns.clone().into(), name.clone().into()) {
+                    Some(attr_name) => {
                       self.dynamic_parts.push(DynamicPart::Attr(
                         *jsx_attr_value((*value).clone()),
                         self.element_index,
-                        attr_name.clone(),
+                        attr_name,
                       ));
                     }
-                    AttrName::Gesture(..) => {
-                      self.dynamic_parts.push(DynamicPart::Attr(
-                        *jsx_attr_value((*value).clone()),
-                        self.element_index,
-                        attr_name.clone(),
-                      ));
-                    }
-                    _ => todo!(),
+                    None => HANDLER.with(|handler| {
+                      handler
+                        .struct_span_err_with_code(
+                          ns.span.to(name.span),
+                          &format!(
+                            "Unknown namespaced attribute `{}:{}`, only worklet events, `ref` and `gesture` are supported",
+                            ns.sym, name.sym
+                          ),
+                          DiagnosticId::Error(diagnostics::UNKNOWN_NAMESPACED_ATTR.into()),
+                        )
+                        .emit()
+                    }),
                   }
                 }
               };
@@ -984,6 +1058,9 @@ pub struct JSXTransformerConfig {
   pub target: TransformTarget,
   /// @internal
   pub is_dynamic_component: Option<bool>,
+  /// Share one snapshot definition between structurally identical elements of a module.
+  /// Defaults to `false`.
+  pub dedupe_snapshots: Option<bool>,
 }
 
 impl Default for JSXTransformerConfig {
@@ -995,10 +1072,19 @@ impl Default for JSXTransformerConfig {
       filename: Default::default(),
       target: TransformTarget::LEPUS,
       is_dynamic_component: Some(false),
+      dedupe_snapshots: None,
     }
   }
 }
 
+/// A snapshot created by `JSXTransformer`.
+#[derive(Clone, Debug)]
+pub struct CreatedSnapshot {
+  pub id: String,
+  /// Span of the JSX element the snapshot is created from.
+  pub span: Span,
+}
+
 pub struct JSXTransformer<C>
 where
   C: Comments + Clone,
@@ -1014,7 +1100,11 @@ where
   snapshot_counter: u32,
   current_snapshot_defs: Vec<ModuleItem>,
   current_snapshot_id: Option<Ident>,
+  /// The snapshots defined so far, keyed by their normalized creator, updaters and slots,
+  /// so that structurally identical elements share one definition, see `dedupe_snapshots`.
+  snapshot_structures: HashMap<Expr, Ident>,
   comments: Option<C>,
+  pub created_snapshots: Vec<CreatedSnapshot>,
 }
 
 impl<C> JSXTransformer<C>
@@ -1046,10 +1136,20 @@ where
       snapshot_counter: 0,
       current_snapshot_defs: vec![],
       current_snapshot_id: None,
+      snapshot_structures: HashMap::new(),
       comments,
+      created_snapshots: vec![],
     }
   }
 
+  fn next_snapshot_uid(&mut self) -> String {
+    self.snapshot_counter += 1;
+    format!(
+      "__snapshot_{}_{}_{}",
+      self.filename_hash, self.content_hash, self.snapshot_counter
+    )
+  }
+
   fn parse_directives(&mut self, span: Span) {
     self.comments.with_leading(span.lo, |comments| {
       for cmt in comments {
@@ -1075,12 +1175,18 @@ where
             let val = words.next();
             if let Some("@jsxCSSId") = pragma {
               if let Some(css_id) = val {
-                self.css_id_value = Some(Expr::Lit(Lit::Num(
-                  css_id
-                    .parse::<f64>()
-                    .expect("should have numeric cssId")
-                    .into(),
-                )));
+                match css_id.parse::<f64>() {
+                  Ok(css_id) => self.css_id_value = Some(Expr::Lit(Lit::Num(css_id.into()))),
+                  Err(_) => HANDLER.with(|handler| {
+                    handler
+                      .struct_span_err_with_code(
+                        cmt.span,
+                        &format!("`@jsxCSSId` should be a number, got `{css_id}`"),
+                        DiagnosticId::Error(diagnostics::INVALID_CSS_ID.into()),
+                      )
+                      .emit()
+                  }),
+                }
               }
             }
           }
@@ -1133,7 +1239,11 @@ where
           if s.value.as_ref() == "component" {
             HANDLER.with(|handler| {
               handler
-                .struct_span_err(node.opening.name.span(), "<component /> is not supported")
+                .struct_span_err_with_code(
+                  node.opening.name.span(),
+                  "<component /> is not supported",
+                  DiagnosticId::Error(diagnostics::COMPONENT_ELEMENT_UNSUPPORTED.into()),
+                )
                 .emit()
             });
           }
@@ -1144,18 +1254,17 @@ where
       }
     }
 
-    self.snapshot_counter += 1;
+    let dedupe_snapshots = self.cfg.dedupe_snapshots == Some(true);
+    // With `dedupe_snapshots`, the id is allocated only if no identical snapshot is found.
+    let snapshot_uid = (!dedupe_snapshots).then(|| self.next_snapshot_uid());
+    // Pushed before the snapshots nested in `node`, and dropped if `node` reuses a snapshot.
+    let created_snapshot_index = self.created_snapshots.len();
+    self.created_snapshots.push(CreatedSnapshot {
+      id: snapshot_uid.clone().unwrap_or_default(),
+      span: node.span,
+    });
 
-    let snapshot_uid = format!(
-      "__snapshot_{}_{}_{}",
-      self.filename_hash, self.content_hash, self.snapshot_counter
-    );
-    let snapshot_id = Ident::new(
-      // format!("__snapshot_{}", snapshot_uid).into(),
-      snapshot_uid.clone().into(),
-      DUMMY_SP,
-      SyntaxContext::default().apply_mark(Mark::fresh(Mark::root())),
-    );
+    node.visit_mut_with(&mut SpreadChildRewriter);
 
     let mut wrap_dynamic_part = WrapperMarker {
       current_is_children_full_dynamic: false,
@@ -1178,6 +1287,8 @@ where
     let mut snapshot_attrs: Vec<JSXAttrOrSpread> = vec![];
     let mut snapshot_children: Vec<JSXElementChild> = vec![];
     let mut snapshot_dynamic_part_def: Vec<Option<ExprOrSpread>> = vec![];
+    // The updaters of the main thread, which identify the snapshot on both threads.
+    let mut snapshot_updaters: Vec<Option<ExprOrSpread>> = vec![];
     let mut snapshot_refs_and_spread_index: Vec<Option<ExprOrSpread>> = vec![];
     let mut snapshot_slot_def: Vec<Option<ExprOrSpread>> = vec![];
 
@@ -1243,6 +1354,17 @@ where
                   Expr::Lit(Lit::Num(snapshot_dynamic_part_def.len().into())).into(),
                 ));
               }
+              if dedupe_snapshots {
+                snapshot_updaters.push(Some(
+                  dynamic_part
+                    .to_updater(
+                      runtime_id.clone(),
+                      TransformTarget::LEPUS,
+                      snapshot_dynamic_part_def.len() as i32,
+                    )
+                    .into(),
+                ));
+              }
               snapshot_dynamic_part_def.push(Some(ExprOrSpread {
                 spread: None,
                 expr: Box::new(dynamic_part.to_updater(
@@ -1401,55 +1523,108 @@ where
       }
     };
 
-    let snapshot_creator = if target == TransformTarget::JS {
-      Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))
-    } else {
-      Expr::Fn(FnExpr {
-        ident: None,
-        function: Box::new(dynamic_part_extractor.snapshot_creator.unwrap()),
-      })
+    let snapshot_creator = Expr::Fn(FnExpr {
+      ident: None,
+      function: Box::new(dynamic_part_extractor.snapshot_creator.unwrap()),
+    });
+    let css_id = match &self.css_id_value {
+      Some(css_id_expr) => css_id_expr.clone(),
+      // We use `undefined` here since runtime will skip `__SetCSSId` when `cssId === undefined && entryName === undefined`
+      None => Expr::Ident("undefined".into()),
     };
 
-    let snapshot_create_call = quote!(
-        r#"$runtime_id.createSnapshot(
-             $snapshot_uid,
-             $snapshot_creator,
-             $snapshot_dynamic_parts_def,
-             $slot,
-             $css_id,
-             globDynamicComponentEntry,
-             $snapshot_refs_and_spread_index
-        )"# as Expr,
-        runtime_id: Expr = self.runtime_id.clone(),
-        // FIXME(colinaaa): Use snapshot_uid with entry_name
-        snapshot_uid: Expr = Expr::Lit(Lit::Str(snapshot_uid.into())),
-        snapshot_creator: Expr = snapshot_creator,
-        snapshot_dynamic_parts_def: Expr = match (target, snapshot_dynamic_part_def.len()) {
-          (TransformTarget::JS, _) | (_, 0) => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
-          _ => Expr::Array(ArrayLit { span: DUMMY_SP, elems: snapshot_dynamic_part_def }),
-        },
-        slot: Expr = slot_expr,
-        css_id: Expr = match &self.css_id_value {
-          Some(css_id_expr) => css_id_expr.clone(),
-          // We use `undefined` here since runtime will skip `__SetCSSId` when `cssId === undefined && entryName === undefined`
-          None => Expr::Ident("undefined".into()),
-        },
-        snapshot_refs_and_spread_index: Expr = match snapshot_refs_and_spread_index.len() {
-          0 => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
-          _ => Expr::Array(ArrayLit { span: DUMMY_SP, elems: snapshot_refs_and_spread_index }),
-        },
-        // has_multi_children: Expr = Expr::Lit(Lit::Num(Number { span: DUMMY_SP, value: wrap_dynamic_part.dynamic_part_count as f64, raw: None })),
-    );
+    // Built from the main thread creator and updaters on the background thread as well,
+    // so that both threads share the same snapshot ids.
+    let snapshot_structure = dedupe_snapshots.then(|| {
+      let mut snapshot_structure = Expr::Array(ArrayLit {
+        span: DUMMY_SP,
+        elems: vec![
+          Some(snapshot_creator.clone().into()),
+          Some(
+            Expr::Array(ArrayLit {
+              span: DUMMY_SP,
+              elems: snapshot_updaters,
+            })
+            .into(),
+          ),
+          Some(slot_expr.clone().into()),
+          Some(css_id.clone().into()),
+          Some(
+            Expr::Array(ArrayLit {
+              span: DUMMY_SP,
+              elems: snapshot_refs_and_spread_index.clone(),
+            })
+            .into(),
+          ),
+        ],
+      });
+      snapshot_structure.visit_mut_with(&mut StructureNormalizer);
+      snapshot_structure
+    });
+    let existing_snapshot_id = snapshot_structure
+      .as_ref()
+      .and_then(|snapshot_structure| self.snapshot_structures.get(snapshot_structure))
+      .cloned();
+
+    let snapshot_id = if let Some(existing_snapshot_id) = existing_snapshot_id {
+      self.created_snapshots.remove(created_snapshot_index);
+      existing_snapshot_id
+    } else {
+      let snapshot_uid = snapshot_uid.unwrap_or_else(|| self.next_snapshot_uid());
+      self.created_snapshots[created_snapshot_index].id = snapshot_uid.clone();
+      let snapshot_id = Ident::new(
+        // format!("__snapshot_{}", snapshot_uid).into(),
+        snapshot_uid.clone().into(),
+        DUMMY_SP,
+        SyntaxContext::default().apply_mark(Mark::fresh(Mark::root())),
+      );
+      if let Some(snapshot_structure) = snapshot_structure {
+        self
+          .snapshot_structures
+          .insert(snapshot_structure, snapshot_id.clone());
+      }
+      let snapshot_create_call = quote!(
+          r#"$runtime_id.createSnapshot(
+               $snapshot_uid,
+               $snapshot_creator,
+               $snapshot_dynamic_parts_def,
+               $slot,
+               $css_id,
+               globDynamicComponentEntry,
+               $snapshot_refs_and_spread_index
+          )"# as Expr,
+          runtime_id: Expr = self.runtime_id.clone(),
+          // FIXME(colinaaa): Use snapshot_uid with entry_name
+          snapshot_uid: Expr = Expr::Lit(Lit::Str(snapshot_uid.into())),
+          snapshot_creator: Expr = match target {
+            TransformTarget::JS => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+            _ => snapshot_creator,
+          },
+          snapshot_dynamic_parts_def: Expr = match (target, snapshot_dynamic_part_def.len()) {
+            (TransformTarget::JS, _) | (_, 0) => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+            _ => Expr::Array(ArrayLit { span: DUMMY_SP, elems: snapshot_dynamic_part_def }),
+          },
+          slot: Expr = slot_expr,
+          css_id: Expr = css_id,
+          snapshot_refs_and_spread_index: Expr = match snapshot_refs_and_spread_index.len() {
+            0 => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+            _ => Expr::Array(ArrayLit { span: DUMMY_SP, elems: snapshot_refs_and_spread_index }),
+          },
+          // has_multi_children: Expr = Expr::Lit(Lit::Num(Number { span: DUMMY_SP, value: wrap_dynamic_part.dynamic_part_count as f64, raw: None })),
+      );
 
-    let snapshot_def = ModuleItem::Stmt(quote!(
-        r#"const $snapshot_id = $snapshot_create_call"#
-            as Stmt,
-        snapshot_id = snapshot_id.clone(),
-        snapshot_create_call: Expr = snapshot_create_call,
-    ));
+      let snapshot_def = ModuleItem::Stmt(quote!(
+          r#"const $snapshot_id = $snapshot_create_call"#
+              as Stmt,
+          snapshot_id = snapshot_id.clone(),
+          snapshot_create_call: Expr = snapshot_create_call,
+      ));
+
+      self.current_snapshot_defs.push(snapshot_def);
+      snapshot_id
+    };
 
     self.current_snapshot_id = Some(snapshot_id.clone());
-    self.current_snapshot_defs.push(snapshot_def);
 
     *node = JSXElement {
       span: node.span(),
@@ -2818,4 +2993,166 @@ aaaaa
     </view>
     "#
   );
+
+  const DEDUPE_INPUT: &str = r#"
+    function Card({ card }) {
+      return <view className="card"><text>{card.title}</text><image src={card.cover} /></view>;
+    }
+    function SelectedCard({ card }) {
+      return <view className="card selected"><text>{card.title}</text><image src={card.cover} /></view>;
+    }
+    function Cards({ cards, recommended }) {
+      return <view>
+        {cards.map(card => <view className="card"><text>{card.title}</text><image src={card.cover} /></view>)}
+        {recommended.map(card => <view className="card"><text>{card.title}</text><image src={card.cover} /></view>)}
+      </view>;
+    }
+    "#;
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      jsx: true,
+      ..Default::default()
+    }),
+    |t| visit_mut_pass(JSXTransformer::new(
+      super::JSXTransformerConfig {
+        preserve_jsx: true,
+        dedupe_snapshots: Some(true),
+        ..Default::default()
+      },
+      Some(t.comments.clone()),
+      TransformMode::Test,
+    )),
+    should_dedupe_identical_snapshots,
+    DEDUPE_INPUT
+  );
+
+  #[test]
+  fn test_dedupe_snapshots_on_both_threads() {
+    use swc_core::{
+      common::{sync::Lrc, FileName, SourceMap, GLOBALS},
+      ecma::{ast::Pass, parser::parse_file_as_program},
+    };
+    use swc_plugins_shared::target::TransformTarget;
+
+    let snapshot_ids = |target| {
+      GLOBALS.set(&Default::default(), || {
+        let cm: Lrc<SourceMap> = Default::default();
+        let fm = cm.new_source_file(FileName::Anon.into(), DEDUPE_INPUT.to_string());
+        let mut program = parse_file_as_program(
+          &fm,
+          Syntax::Es(EsSyntax {
+            jsx: true,
+            ..Default::default()
+          }),
+          Default::default(),
+          None,
+          &mut vec![],
+        )
+        .unwrap();
+        let mut transformer = JSXTransformer::<SingleThreadedComments>::new(
+          super::JSXTransformerConfig {
+            target,
+            dedupe_snapshots: Some(true),
+            ..Default::default()
+          },
+          None,
+          TransformMode::Test,
+        );
+        visit_mut_pass(&mut transformer).process(&mut program);
+        transformer
+          .created_snapshots
+          .into_iter()
+          .map(|snapshot| snapshot.id)
+          .collect::<Vec<_>>()
+      })
+    };
+
+    let lepus = snapshot_ids(TransformTarget::LEPUS);
+    assert_eq!(lepus.len(), 3);
+    assert_eq!(lepus, snapshot_ids(TransformTarget::JS));
+  }
+
+  #[test]
+  fn test_jsx_errors() {
+    use std::sync::{Arc, Mutex};
+    use swc_core::{
+      common::{
+        errors::{DiagnosticBuilder, DiagnosticId, Emitter, Handler, Level, HANDLER},
+        sync::Lrc,
+        FileName, SourceMap, GLOBALS,
+      },
+      ecma::{
+        ast::{Pass, Program},
+        parser::parse_file_as_module,
+      },
+    };
+    use swc_plugins_shared::diagnostics::{
+      INVALID_CSS_ID, UNKNOWN_NAMESPACED_ATTR, UNSUPPORTED_ATTR_VALUE, UNSUPPORTED_SPREAD_CHILD,
+    };
+
+    #[derive(Clone, Default)]
+    struct Diagnostics(Arc<Mutex<Vec<(Level, String)>>>);
+
+    impl Emitter for Diagnostics {
+      fn emit(&mut self, db: &mut DiagnosticBuilder<'_>) {
+        let code = match &db.code {
+          Some(DiagnosticId::Error(code) | DiagnosticId::Lint(code)) => code.clone(),
+          None => String::new(),
+        };
+        self.0.lock().unwrap().push((db.level, code));
+      }
+    }
+
+    let diagnostics = |code: &str| {
+      GLOBALS.set(&Default::default(), || {
+        let cm: Lrc<SourceMap> = Default::default();
+        let fm = cm.new_source_file(FileName::Anon.into(), code.to_string());
+        let comments = SingleThreadedComments::default();
+        let module = parse_file_as_module(
+          &fm,
+          Syntax::Es(EsSyntax {
+            jsx: true,
+            ..Default::default()
+          }),
+          Default::default(),
+          Some(&comments),
+          &mut vec![],
+        )
+        .unwrap();
+        let mut program = Program::Module(module);
+        let emitter = Diagnostics::default();
+        let handler = Handler::with_emitter(true, false, Box::new(emitter.clone()));
+        HANDLER.set(&handler, || {
+          let mut transformer =
+            JSXTransformer::new(Default::default(), Some(comments), TransformMode::Test);
+          visit_mut_pass(&mut transformer).process(&mut program);
+        });
+        let diagnostics = emitter.0.lock().unwrap().clone();
+        diagnostics
+      })
+    };
+
+    for (code, id) in [
+      ("<view>{...a}</view>;", UNSUPPORTED_SPREAD_CHILD),
+      (
+        "<view><text>{...a}</text></view>;",
+        UNSUPPORTED_SPREAD_CHILD,
+      ),
+      ("<view foo:bar=\"1\" />;", UNKNOWN_NAMESPACED_ATTR),
+      ("<view attr=<view /> />;", UNSUPPORTED_ATTR_VALUE),
+      ("<view style=<></> />;", UNSUPPORTED_ATTR_VALUE),
+      ("/* @jsxCSSId abc */\n<view />;", INVALID_CSS_ID),
+    ] {
+      assert_eq!(
+        diagnostics(code),
+        vec![(Level::Error, id.to_string())],
+        "{code}"
+      );
+    }
+
+    // Set as a plain attribute outside `<list-item>`.
+    assert_eq!(diagnostics("<view item-key=\"a\" />;"), vec![]);
+  }
 }
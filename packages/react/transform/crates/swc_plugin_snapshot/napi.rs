@@ -1,4 +1,5 @@
 use napi_derive::napi;
+use serde::Serialize;
 use swc_core::{
   common::comments::Comments,
   ecma::{ast::*, visit::VisitMut},
@@ -6,12 +7,13 @@ use swc_core::{
 use swc_plugins_shared::{target_napi::TransformTarget, transform_mode_napi::TransformMode};
 
 use crate::{
-  JSXTransformer as CoreJSXTransformer, JSXTransformerConfig as CoreJSXTransformerConfig,
+  CreatedSnapshot, JSXTransformer as CoreJSXTransformer,
+  JSXTransformerConfig as CoreJSXTransformerConfig,
 };
 
 /// @internal
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct JSXTransformerConfig {
   /// @internal
   pub preserve_jsx: bool,
@@ -26,6 +28,9 @@ pub struct JSXTransformerConfig {
   pub target: TransformTarget,
   /// @internal
   pub is_dynamic_component: Option<bool>,
+  /// Share one snapshot definition between structurally identical elements of a module.
+  /// Defaults to `false`.
+  pub dedupe_snapshots: Option<bool>,
 }
 
 impl Default for JSXTransformerConfig {
@@ -37,6 +42,7 @@ impl Default for JSXTransformerConfig {
       filename: Default::default(),
       target: TransformTarget::LEPUS,
       is_dynamic_component: Some(false),
+      dedupe_snapshots: None,
     }
   }
 }
@@ -50,6 +56,7 @@ impl From<JSXTransformerConfig> for CoreJSXTransformerConfig {
       filename: val.filename,
       target: val.target.into(),
       is_dynamic_component: val.is_dynamic_component,
+      dedupe_snapshots: val.dedupe_snapshots,
     }
   }
 }
@@ -63,6 +70,7 @@ impl From<CoreJSXTransformerConfig> for JSXTransformerConfig {
       filename: val.filename,
       target: val.target.into(),
       is_dynamic_component: val.is_dynamic_component,
+      dedupe_snapshots: val.dedupe_snapshots,
     }
   }
 }
@@ -88,6 +96,11 @@ where
       inner: CoreJSXTransformer::new(cfg.into(), comments, mode.into()),
     }
   }
+
+  /// Take the snapshots created so far.
+  pub fn take_created_snapshots(&mut self) -> Vec<CreatedSnapshot> {
+    std::mem::take(&mut self.inner.created_snapshots)
+  }
 }
 
 impl<C> VisitMut for JSXTransformer<C>
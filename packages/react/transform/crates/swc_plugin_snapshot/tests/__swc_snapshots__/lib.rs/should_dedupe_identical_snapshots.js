@@ -0,0 +1,78 @@
+import * as ReactLynx from "@lynx-js/react";
+const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_1", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    __SetClasses(el, "card");
+    const el1 = __CreateText(pageId);
+    __AppendElement(el, el1);
+    const el2 = __CreateImage(pageId);
+    __AppendElement(el, el2);
+    return [
+        el,
+        el1,
+        el2
+    ];
+}, [
+    function(ctx) {
+        if (ctx.__elements) {
+            __SetAttribute(ctx.__elements[2], "src", ctx.__values[0]);
+        }
+    }
+], [
+    [
+        ReactLynx.__DynamicPartChildren,
+        1
+    ]
+], undefined, globDynamicComponentEntry, null);
+function Card({ card }) {
+    return <__snapshot_da39a_test_1 values={[
+        card.cover
+    ]}>{card.title}</__snapshot_da39a_test_1>;
+}
+const __snapshot_da39a_test_2 = ReactLynx.createSnapshot("__snapshot_da39a_test_2", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    __SetClasses(el, "card selected");
+    const el1 = __CreateText(pageId);
+    __AppendElement(el, el1);
+    const el2 = __CreateImage(pageId);
+    __AppendElement(el, el2);
+    return [
+        el,
+        el1,
+        el2
+    ];
+}, [
+    function(ctx) {
+        if (ctx.__elements) {
+            __SetAttribute(ctx.__elements[2], "src", ctx.__values[0]);
+        }
+    }
+], [
+    [
+        ReactLynx.__DynamicPartChildren,
+        1
+    ]
+], undefined, globDynamicComponentEntry, null);
+function SelectedCard({ card }) {
+    return <__snapshot_da39a_test_2 values={[
+        card.cover
+    ]}>{card.title}</__snapshot_da39a_test_2>;
+}
+const __snapshot_da39a_test_3 = ReactLynx.createSnapshot("__snapshot_da39a_test_3", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    return [
+        el
+    ];
+}, null, ReactLynx.__DynamicPartChildren_0, undefined, globDynamicComponentEntry, null);
+function Cards({ cards, recommended }) {
+    return <__snapshot_da39a_test_3>{[
+        cards.map((card)=><__snapshot_da39a_test_1 values={[
+                card.cover
+            ]}>{card.title}</__snapshot_da39a_test_1>),
+        recommended.map((card)=><__snapshot_da39a_test_1 values={[
+                card.cover
+            ]}>{card.title}</__snapshot_da39a_test_1>)
+    ]}</__snapshot_da39a_test_3>;
+}
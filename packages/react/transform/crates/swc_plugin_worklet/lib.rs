@@ -11,7 +11,7 @@ use hash::WorkletHash;
 use std::collections::HashSet;
 use std::vec;
 use swc_core::common::util::take::Take;
-use swc_core::common::{Spanned, DUMMY_SP};
+use swc_core::common::{Span, Spanned, DUMMY_SP};
 use swc_core::ecma::ast::*;
 use swc_core::ecma::utils::prepend_stmts;
 use swc_core::ecma::visit::VisitMutWith;
@@ -49,6 +49,16 @@ impl Default for WorkletVisitorConfig {
   }
 }
 
+/// A worklet registered by `WorkletVisitor`.
+#[derive(Clone, Debug)]
+pub struct RegisteredWorklet {
+  pub hash: String,
+  /// `main-thread` or `ui`.
+  pub worklet_type: String,
+  /// Span of the worklet function.
+  pub span: Span,
+}
+
 pub struct WorkletVisitor {
   mode: TransformMode,
   content_hash: String,
@@ -56,6 +66,7 @@ pub struct WorkletVisitor {
   stmts_to_insert_at_top_level: Vec<Stmt>,
   named_imports: HashSet<String>,
   hasher: WorkletHash,
+  pub registered_worklets: Vec<RegisteredWorklet>,
 }
 
 impl Default for WorkletVisitor {
@@ -86,7 +97,7 @@ impl VisitMut for WorkletVisitor {
     });
     n.visit_mut_with(&mut collector);
 
-    let hash = self.hasher.gen(&self.cfg.filename, &self.content_hash);
+    let hash = self.gen_hash(worklet_type.as_ref().unwrap(), n.span());
     let (worklet_object_expr, register_worklet_stmt) = StmtGen::transform_worklet(
       self.mode,
       worklet_type.unwrap(),
@@ -145,7 +156,7 @@ impl VisitMut for WorkletVisitor {
     });
     n.visit_mut_with(&mut collector);
 
-    let hash = self.hasher.gen(&self.cfg.filename, &self.content_hash);
+    let hash = self.gen_hash(worklet_type.as_ref().unwrap(), n.span());
     let (worklet_object_expr, register_worklet_stmt) = StmtGen::transform_worklet(
       self.mode,
       worklet_type.unwrap(),
@@ -191,7 +202,7 @@ impl VisitMut for WorkletVisitor {
         });
         n.visit_mut_with(&mut collector);
 
-        let hash = self.hasher.gen(&self.cfg.filename, &self.content_hash);
+        let hash = self.gen_hash(worklet_type.as_ref().unwrap(), n.span());
         let (worklet_object_expr, register_worklet_stmt) = StmtGen::transform_worklet(
           self.mode,
           worklet_type.unwrap(),
@@ -245,7 +256,7 @@ impl VisitMut for WorkletVisitor {
         });
         n.visit_mut_with(&mut collector);
 
-        let hash = self.hasher.gen(&self.cfg.filename, &self.content_hash);
+        let hash = self.gen_hash(worklet_type.as_ref().unwrap(), n.span());
         let (worklet_object_expr, register_worklet_stmt) = StmtGen::transform_worklet(
           self.mode,
           worklet_type.unwrap(),
@@ -316,7 +327,7 @@ impl VisitMut for WorkletVisitor {
       .unwrap()
       .visit_mut_with(&mut collector);
 
-    let hash = self.hasher.gen(&self.cfg.filename, &self.content_hash);
+    let hash = self.gen_hash(worklet_type.as_ref().unwrap(), n.span());
     let (worklet_object_expr, register_worklet_stmt) = StmtGen::transform_worklet(
       self.mode,
       worklet_type.unwrap(),
@@ -448,9 +459,20 @@ impl WorkletVisitor {
       stmts_to_insert_at_top_level: vec![],
       hasher: WorkletHash::new(),
       named_imports: HashSet::default(),
+      registered_worklets: vec![],
     }
   }
 
+  fn gen_hash(&mut self, worklet_type: &WorkletType, span: Span) -> String {
+    let hash = self.hasher.gen(&self.cfg.filename, &self.content_hash);
+    self.registered_worklets.push(RegisteredWorklet {
+      hash: hash.clone(),
+      worklet_type: worklet_type.type_str().to_string(),
+      span,
+    });
+    hash
+  }
+
   fn check_is_worklet_block(&self, n: &mut BlockStmt) -> Option<WorkletType> {
     let BlockStmt { stmts, .. } = n;
     if !stmts.is_empty() {
@@ -1,12 +1,13 @@
 use napi_derive::napi;
+use serde::Serialize;
 
 use swc_core::ecma::ast::*;
 use swc_core::ecma::visit::{noop_visit_mut_type, VisitMut};
 
-use crate::{WorkletVisitor as CoreVisitor, WorkletVisitorConfig as CoreConfig};
+use crate::{RegisteredWorklet, WorkletVisitor as CoreVisitor, WorkletVisitorConfig as CoreConfig};
 use swc_plugins_shared::{target_napi::TransformTarget, transform_mode_napi::TransformMode};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 #[napi(object)]
 pub struct WorkletVisitorConfig {
   /// @public
@@ -106,4 +107,9 @@ impl WorkletVisitor {
       inner: CoreVisitor::new(mode.into(), cfg.into()),
     }
   }
+
+  /// Take the worklets registered so far.
+  pub fn take_registered_worklets(&mut self) -> Vec<RegisteredWorklet> {
+    std::mem::take(&mut self.inner.registered_worklets)
+  }
 }
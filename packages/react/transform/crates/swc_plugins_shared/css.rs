@@ -7,9 +7,7 @@ use swc_core::{
   ecma::{ast::Expr, utils::is_literal},
 };
 
-use crate::utils::jsonify;
-
-static EXTRACT_CSS_DIAGNOSTIC_ID: &str = "react-lynx-extract-css";
+use crate::{diagnostics, utils::jsonify};
 
 pub fn get_string_inline_style_from_literal(expr: &Expr, span: &Span) -> Option<String> {
   let expr = expr.clone();
@@ -50,7 +48,7 @@ pub fn get_string_inline_style_from_literal(expr: &Expr, span: &Span) -> Option<
             .struct_span_warn_with_code(
               *span,
               "Unexpected literal for style",
-              DiagnosticId::Lint(EXTRACT_CSS_DIAGNOSTIC_ID.into()),
+              DiagnosticId::Lint(diagnostics::UNEXPECTED_STYLE_LITERAL.into()),
             )
             .emit();
         });
@@ -0,0 +1,143 @@
+//! Stable codes of the diagnostics emitted by the plugins.
+//!
+//! Codes are grouped by plugin and never reused:
+//!
+//! | Range    | Plugin           |
+//! | -------- | ---------------- |
+//! | `RL0xxx` | shared helpers   |
+//! | `RL1xxx` | `compat`         |
+//! | `RL2xxx` | `directiveDCE`   |
+//! | `RL3xxx` | `dynamicImport`  |
+//! | `RL4xxx` | `snapshot`       |
+//! | `RL5xxx` | `inject`         |
+//! | `RL9xxx` | the transform    |
+//!
+//! Warnings are emitted with `DiagnosticId::Lint` and errors with `DiagnosticId::Error`.
+//!
+//! Every code is listed in [`CODES`] with its default level and description,
+//! e.g. for the rule metadata of reports.
+
+/// The level a code is emitted with, unless it is changed by `TransformNodiffOptions.diagnostics`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DefaultLevel {
+  Warning,
+  Error,
+}
+
+/// A diagnostic code, see [`CODES`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Code {
+  /// The code, e.g. `RL1001`.
+  pub id: &'static str,
+  /// The name of the constant of the code, e.g. `DEPRECATED_COMPONENTS_PKG`.
+  pub name: &'static str,
+  pub level: DefaultLevel,
+  /// The doc comment of the constant of the code.
+  pub description: &'static str,
+}
+
+macro_rules! codes {
+  ($(#[doc = $doc:literal] $level:ident $name:ident = $id:literal;)*) => {
+    $(
+      #[doc = $doc]
+      pub const $name: &str = $id;
+    )*
+
+    /// Every diagnostic code emitted by the plugins.
+    pub const CODES: &[Code] = &[$(Code {
+      id: $id,
+      name: stringify!($name),
+      level: codes!(@level $level),
+      description: $doc.trim_ascii(),
+    },)*];
+  };
+  (@level warning) => { DefaultLevel::Warning };
+  (@level error) => { DefaultLevel::Error };
+}
+
+codes! {
+  /// A literal that cannot be converted to JSON, e.g. a spread in `style={{ ...a }}`.
+  error UNSUPPORTED_JSON_VALUE = "RL0001";
+
+  /// `compat`: the old components package is removed.
+  warning DEPRECATED_COMPONENTS_PKG = "RL1001";
+  /// `compat`: the old runtime package is changed to the new one.
+  warning DEPRECATED_RUNTIME_PKG = "RL1002";
+  /// `compat`: `<component is=? />` is deprecated.
+  warning DEPRECATED_COMPONENT_IS = "RL1003";
+  /// `compat`: event props like `onClick` are changed to `bindtap`.
+  warning DEPRECATED_EVENT_PROPS = "RL1004";
+  /// `compat`: `lynx-key` is changed to `key`.
+  warning DEPRECATED_LYNX_KEY = "RL1005";
+  /// `compat`: element names like `View` are changed to `view`.
+  warning DEPRECATED_ELEMENT_NAME = "RL1006";
+  /// `compat`: a component with JSXSpread is ignored by `addComponentElement`.
+  warning COMPONENT_SPREAD_IGNORED = "RL1101";
+  /// `compat`: `item-key` in component props takes no effect.
+  warning BROKEN_ITEM_KEY = "RL1102";
+  /// `compat`: `e.stopPropagation()` takes no effect.
+  warning BROKEN_STOP_PROPAGATION = "RL1103";
+  /// `compat`: `this.getNodeRef()` and friends on component instances are broken.
+  warning BROKEN_INSTANCE_QUERY = "RL1104";
+  /// `compat`: the class property `config` is removed.
+  warning BROKEN_CONFIG_PROPERTY = "RL1105";
+
+  /// `directiveDCE`: a directive inside a constructor.
+  warning DIRECTIVE_IN_CONSTRUCTOR = "RL2001";
+  /// `directiveDCE`: a directive inside a getter or setter.
+  warning DIRECTIVE_IN_ACCESSOR = "RL2002";
+
+  /// `dynamicImport`: `import()` with no argument.
+  error IMPORT_WITHOUT_ARGUMENT = "RL3001";
+  /// `dynamicImport`: `import()` with a non-string literal module id.
+  error IMPORT_NON_STRING_LITERAL = "RL3002";
+  /// `dynamicImport`: `import("...", ...)` with invalid options.
+  error IMPORT_INVALID_OPTIONS = "RL3003";
+
+  /// `snapshot`: `<component />` is not supported.
+  error COMPONENT_ELEMENT_UNSUPPORTED = "RL4001";
+  /// `snapshot`: JSX namespaces are disabled.
+  error JSX_NAMESPACE_DISABLED = "RL4002";
+  /// `snapshot`: a literal `style` that cannot be extracted.
+  warning UNEXPECTED_STYLE_LITERAL = "RL4003";
+  /// `snapshot`: a JSX element as an attribute value, e.g. `<view attr=<text /> />`.
+  error UNSUPPORTED_ATTR_VALUE = "RL4004";
+  /// `snapshot`: a namespaced attribute that is not a worklet event, a worklet ref or a gesture.
+  error UNKNOWN_NAMESPACED_ATTR = "RL4005";
+  /// `snapshot`: a `@jsxCSSId` pragma that is not a number.
+  error INVALID_CSS_ID = "RL4006";
+  /// `snapshot`: JSX spread children, e.g. `<view>{...children}</view>`.
+  error UNSUPPORTED_SPREAD_CHILD = "RL4007";
+
+  /// `inject`: an injected expression cannot be parsed.
+  error INJECT_PARSE_FAILED = "RL5001";
+
+  /// The `inputSourceMap` option cannot be read.
+  warning INVALID_INPUT_SOURCE_MAP = "RL9001";
+  /// The program or comments of `transformReactLynxAst` cannot be read.
+  error INVALID_AST_INPUT = "RL9002";
+  /// The `extractStr.manifest` option of `transformBundleResult` cannot be read.
+  error INVALID_EXTRACT_STR_MANIFEST = "RL9003";
+  /// The code has syntax newer than `codegen.target`, which only changes how the code is printed.
+  error UNSUPPORTED_CODEGEN_TARGET = "RL9004";
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_codes() {
+    assert_eq!(CODES.len(), 29);
+    assert!(CODES.windows(2).all(|codes| codes[0].id < codes[1].id));
+    assert_eq!(
+      CODES[1],
+      Code {
+        id: "RL1001",
+        name: "DEPRECATED_COMPONENTS_PKG",
+        level: DefaultLevel::Warning,
+        description: "`compat`: the old components package is removed.",
+      }
+    );
+  }
+}
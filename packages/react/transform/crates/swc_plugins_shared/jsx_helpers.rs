@@ -3,13 +3,19 @@ use std::borrow::Cow;
 
 use once_cell::sync::Lazy;
 use swc_core::{
-  common::{errors::HANDLER, iter::IdentifyLast, Spanned, DUMMY_SP},
+  common::{
+    errors::{DiagnosticId, HANDLER},
+    iter::IdentifyLast,
+    Spanned, DUMMY_SP,
+  },
   ecma::{
     ast::{JSXExpr, *},
     atoms::{atom, Atom},
   },
 };
 
+use crate::diagnostics;
+
 pub fn jsx_name(name: JSXElementName) -> Box<Expr> {
   let span = name.span();
   match name {
@@ -33,7 +39,11 @@ pub fn jsx_name(name: JSXElementName) -> Box<Expr> {
     }) => {
       HANDLER.with(|handler| {
         handler
-          .struct_span_err(span, "JSX Namespace is disabled")
+          .struct_span_err_with_code(
+            span,
+            "JSX Namespace is disabled",
+            DiagnosticId::Error(diagnostics::JSX_NAMESPACE_DISABLED.into()),
+          )
           .emit()
       });
       let value = format!("{}:{}", ns.sym, name.sym);
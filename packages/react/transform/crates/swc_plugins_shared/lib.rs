@@ -1,5 +1,7 @@
 pub mod css;
+pub mod diagnostics;
 pub mod jsx_helpers;
+pub mod napi_serde;
 pub mod target;
 pub mod target_napi;
 pub mod transform_mode;
@@ -0,0 +1,35 @@
+//! `Serialize` for `napi::Either`, which does not implement it,
+//! e.g. `#[serde(serialize_with = "swc_plugins_shared::napi_serde::either")]`.
+
+use napi::Either;
+use serde::{Serialize, Serializer};
+
+/// Both variants are tagged as `{ "A": a }` and `{ "B": b }`, so that they never collide.
+#[derive(Serialize)]
+enum Tagged<'a, A, B> {
+  A(&'a A),
+  B(&'a B),
+}
+
+impl<'a, A, B> From<&'a Either<A, B>> for Tagged<'a, A, B> {
+  fn from(value: &'a Either<A, B>) -> Self {
+    match value {
+      Either::A(a) => Tagged::A(a),
+      Either::B(b) => Tagged::B(b),
+    }
+  }
+}
+
+pub fn either<A: Serialize, B: Serialize, S: Serializer>(
+  value: &Either<A, B>,
+  serializer: S,
+) -> Result<S::Ok, S::Error> {
+  Tagged::from(value).serialize(serializer)
+}
+
+pub fn option_either<A: Serialize, B: Serialize, S: Serializer>(
+  value: &Option<Either<A, B>>,
+  serializer: S,
+) -> Result<S::Ok, S::Error> {
+  value.as_ref().map(Tagged::from).serialize(serializer)
+}
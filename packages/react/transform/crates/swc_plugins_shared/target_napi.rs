@@ -1,6 +1,8 @@
+use serde::Serialize;
+
 use crate::target::TransformTarget as CoreTarget;
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
 pub enum TransformTarget {
   LEPUS,
   JS,
@@ -1,6 +1,8 @@
+use serde::Serialize;
+
 use crate::transform_mode::TransformMode as CoreMode;
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
 pub enum TransformMode {
   /// Transform for production.
   Production,
@@ -1,31 +1,58 @@
 use serde_json::{json, Value};
 use sha1::{Digest, Sha1};
-use swc_core::ecma::ast::*;
+use swc_core::{
+  common::{
+    errors::{DiagnosticId, HANDLER},
+    Span, Spanned,
+  },
+  ecma::ast::*,
+};
+
+use crate::diagnostics;
+
+/// Report `what` at `span` as an error, and convert it to `null`.
+fn unsupported_json_value(span: Span, what: &str) -> Value {
+  HANDLER.with(|handler| {
+    handler
+      .struct_span_err_with_code(
+        span,
+        &format!("{what} cannot be converted to JSON, only JSON-like literals are supported"),
+        DiagnosticId::Error(diagnostics::UNSUPPORTED_JSON_VALUE.into()),
+      )
+      .emit()
+  });
+  Value::Null
+}
 
 // https://github.com/swc-project/swc/blob/v1.5.8/crates/swc_ecma_transforms_optimization/src/json_parse.rs#L95
+/// Convert a literal to JSON, see `swc_core::ecma::utils::is_literal`.
+///
+/// What cannot be converted is reported as an error and converted to `null`.
 pub fn jsonify(e: Expr) -> Value {
   match e {
     Expr::Object(obj) => Value::Object(
       obj
         .props
         .into_iter()
-        .map(|v| match v {
-          PropOrSpread::Prop(p) if p.is_key_value() => p.key_value().unwrap(),
-          _ => unreachable!(
-            "Unexpected property name type in JSON conversion - expected Ident, Str, or Num"
-          ),
+        .filter_map(|v| match v {
+          PropOrSpread::Prop(p) if p.is_key_value() => Some(p.key_value().unwrap()),
+          v => {
+            unsupported_json_value(v.span(), "A property that is not a key-value pair");
+            None
+          }
         })
-        .map(|p: KeyValueProp| {
-          let value = jsonify(*p.value);
+        .filter_map(|p: KeyValueProp| {
           let key = match p.key {
             PropName::Str(s) => s.value.to_string(),
             PropName::Ident(id) => id.sym.to_string(),
             PropName::Num(n) => format!("{}", n.value),
-            _ => unreachable!(
-              "Unexpected property name type in JSON conversion - expected Ident, Str, or Num"
-            ),
+            key => {
+              unsupported_json_value(key.span(), "A computed or bigint property name");
+              return None;
+            }
           };
-          (key, value)
+          let value = jsonify(*p.value);
+          Some((key, value))
         })
         .collect(),
     ),
@@ -33,7 +60,15 @@ pub fn jsonify(e: Expr) -> Value {
       arr
         .elems
         .into_iter()
-        .map(|v| jsonify(*v.unwrap().expr))
+        .map(|v| match v {
+          // holes are `null` in JSON, like `JSON.stringify([, 1])`
+          None => Value::Null,
+          Some(ExprOrSpread {
+            spread: Some(spread),
+            ..
+          }) => unsupported_json_value(spread, "A spread element"),
+          Some(ExprOrSpread { expr, .. }) => jsonify(*expr),
+        })
         .collect(),
     ),
     Expr::Lit(Lit::Str(Str { value, .. })) => Value::String(value.to_string()),
@@ -53,10 +88,7 @@ pub fn jsonify(e: Expr) -> Value {
       }) => value.to_string(),
       _ => String::new(),
     }),
-    _ => unreachable!(
-      "Unexpected expression type in JSON conversion - cannot convert {:?} to JSON",
-      e
-    ),
+    e => unsupported_json_value(e.span(), "An expression"),
   }
 }
 
@@ -67,3 +99,27 @@ pub fn calc_hash(s: &str) -> String {
 
   hex::encode(sum)[0..5].to_string()
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use swc_core::{common::errors::Handler, quote_expr};
+
+  #[test]
+  fn test_jsonify() {
+    let handler = Handler::with_emitter_writer(Box::new(std::io::sink()), None);
+    HANDLER.set(&handler, || {
+      assert_eq!(
+        jsonify(*quote_expr!("{ a: [, 1], 'b-c': `d`, 1: null }")),
+        json!({ "a": [null, 1], "b-c": "d", "1": null })
+      );
+      assert_eq!(handler.err_count(), 0);
+
+      assert_eq!(
+        jsonify(*quote_expr!("{ a: [...b], ...c, [d]: 1, e: f }")),
+        json!({ "a": [null], "e": null })
+      );
+      assert_eq!(handler.err_count(), 4);
+    });
+  }
+}
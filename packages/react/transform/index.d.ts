@@ -3,26 +3,273 @@
 
 /* auto-generated by NAPI-RS */
 
+export interface TransformAstInput {
+  /** The swc `Program` in JSON format. */
+  program: string
+  /**
+   * The comments of `program` in JSON format,
+   * i.e. `{ leading: Record<number, Comment[]>, trailing: Record<number, Comment[]> }`.
+   */
+  comments?: string
+  /**
+   * The code `program` is parsed from, used for diagnostics, metadata and source maps.
+   *
+   * @remarks
+   * Spans of `program` and `comments` should be relative to it, with its first byte at position `1`,
+   * which is the case when it is the first file parsed with a fresh swc `SourceMap`.
+   *
+   * It is required unless every span of `program` is dummy and there are no `comments`,
+   * e.g. when `program` is built by hand rather than parsed.
+   */
+  source?: string
+  /** Whether to print the transformed program into `code`. Defaults to `false`. */
+  print?: boolean
+}
+export interface TransformNodiffAstOutput {
+  /** The transformed `Program` in JSON format, absent if `program` cannot be read. */
+  program?: string
+  /** The comments of the transformed `program`, in the same format as `TransformAstInput.comments`. */
+  comments?: string
+  /** The printed code, only available when `print` is enabled. */
+  code?: string
+  map?: string
+  errors: Array<PartialMessage>
+  warnings: Array<PartialMessage>
+  /** Per-pass profiling results, only available when `profile` is enabled. */
+  profile?: Array<PassProfile>
+  /** Snapshots, worklets, CSS imports and dynamic imports produced by the transform. */
+  metadata: TransformMetadata
+}
+export interface TransformBatchFile {
+  code: string
+  /** Overrides `options.filename`. */
+  filename: string
+  /** Options of this file, including its own `cacheDir` and `timeout`. */
+  options?: TransformNodiffOptions
+}
 export interface TransformNodiffBundleOptions {
   filename: string
   pluginName: string
   sourceFileName?: string
   sourcemap: boolean | string
+  /**
+   * The source map of the input code in JSON format.
+   * It is composed into the output source map.
+   */
+  inputSourceMap?: string
+  /**
+   * The swc syntax config of the input code in JSON format, see `TransformNodiffOptions.syntaxConfig`.
+   * TypeScript and JSX are parsed, and printed as they are.
+   *
+   * Default value: ECMAScript.
+   */
+  syntaxConfig?: string
+  /**
+   * Whether the input code is a module, see `TransformNodiffOptions.isModule`.
+   * The `_EXTRACT_STR` table of a script is declared in its IIFE rather than at the top level.
+   *
+   * Default value: `true`.
+   */
+  isModule?: boolean | 'unknown' | 'commonjs'
   extractStr: boolean | ExtractStrConfig
+  /** Deprecated, use `codegen.minify` instead. */
   minify?: boolean
+  /** Options of the code generator, `codegen.minify` takes precedence over `minify`. */
+  codegen?: CodegenConfig
+  /**
+   * Compress and mangle the code, `true` for the default options of `MinifyConfig`.
+   * Whitespaces are removed as well, unless `codegen.minify` is `false`.
+   */
+  minifier?: boolean | MinifyConfig
+  /**
+   * Remove the registrations of the worklets whose hashes are not in the list,
+   * see `TransformNodiffBundleOutput.worklets`.
+   */
+  workletAllowList?: Array<string>
+  /**
+   * Remove the no-op PAPI calls in the creators of the snapshots, e.g. `__SetClasses(el, "")`,
+   * see `TransformNodiffBundleOutput.snapshotDCE`. With `SnapshotDCEConfig.liveSnapshots`,
+   * the snapshots that are referenced in neither this bundle nor the background bundle are removed as well.
+   */
+  snapshotDCE?: boolean | SnapshotDCEConfig
+  /**
+   * Cancel the transform if it is not finished within `timeout` milliseconds,
+   * see `TransformNodiffOptions.timeout`.
+   */
+  timeout?: number
 }
 export interface TransformNodiffBundleOutput {
   code: string
   map?: string
   selectStrVec?: Array<string>
+  /**
+   * The estimated number of bytes saved by `extractStr`, i.e. the length of the replaced string literals
+   * minus the length of the `_EXTRACT_STR[i]`s and of the table. Only available when `extractStr` is enabled.
+   */
+  extractStrSavedBytes?: number
+  /**
+   * The string table in JSON after this bundle, see `ExtractStrConfig.manifest`.
+   * Only available for the main thread bundle when `extractStr` is enabled.
+   */
+  extractStrManifest?: string
+  /**
+   * The strings of at least `strLength` that are left inline since they are not in `extractedStrArr`.
+   * Only available for the background bundle when `extractStr` is enabled.
+   */
+  extractStrUnresolved?: Array<string>
   useWorklet?: boolean
+  /** The worklets registered by `registerWorklet` and `registerWorkletInternal` in the bundle. */
+  worklets?: WorkletManifest
+  /** The snapshots and PAPI calls removed, only available when `snapshotDCE` is enabled. */
+  snapshotDCE?: SnapshotDCEOutput
   errors: Array<PartialMessage>
   warnings: Array<PartialMessage>
 }
+/** The options of the bundle-level snapshot DCE, see `TransformNodiffBundleOptions.snapshotDCE`. */
+export interface SnapshotDCEConfig {
+  /**
+   * The ids of the snapshots used by the background bundle, e.g. `__snapshot_da39a_test_1`.
+   * The background thread creates the snapshots by their ids, so a snapshot is only removed
+   * if it is referenced in neither the main thread bundle nor this list.
+   */
+  liveSnapshots: Array<string>
+}
+/** The result of the bundle-level snapshot DCE, see `TransformNodiffBundleOutput.snapshotDCE`. */
+export interface SnapshotDCEOutput {
+  /** The ids of the snapshots removed since they are referenced in neither bundle. */
+  removedSnapshots: Array<string>
+  /**
+   * The number of the no-op PAPI calls removed from the creators of the snapshots,
+   * e.g. `__SetClasses(el, "")`.
+   */
+  foldedPapiCalls: number
+}
+/** A worklet registered in the bundle, see `WorkletManifest`. */
+export interface BundleWorklet {
+  /** The hash the worklet is registered with. */
+  hash: string
+  type: 'main-thread' | 'ui'
+  /** Whether the hash is used in the bundle besides its registration, e.g. by `_wkltId` of a worklet object. */
+  referenced: boolean
+}
+/** The worklets registered in the bundle, see `TransformNodiffBundleOutput.worklets`. */
+export interface WorkletManifest {
+  /** The worklets that are kept, in the order they are registered. */
+  worklets: Array<BundleWorklet>
+  /** The number of the worklets that are kept. */
+  count: number
+  /** The hashes of the worklets removed since they are not in `TransformNodiffBundleOptions.workletAllowList`. */
+  removed: Array<string>
+}
+/**
+ * @public
+ * Options of the minifier of `transformBundleResult`.
+ */
+export interface MinifyConfig {
+  /**
+   * @public
+   * Whether to compress the code, e.g. remove dead code and inline variables.
+   *
+   * @remarks
+   * Default value: `true`.
+   */
+  compress?: boolean
+  /**
+   * @public
+   * Whether to mangle the names of local variables.
+   *
+   * @remarks
+   * Top-level names are never mangled, since they are shared by the chunks.
+   *
+   * Default value: `true`.
+   */
+  mangle?: boolean
+  /**
+   * @public
+   * Whether to keep the names of functions.
+   *
+   * @remarks
+   * Default value: `false`.
+   */
+  keepFnames?: boolean
+  /**
+   * @public
+   * Names that are never mangled.
+   *
+   * @remarks
+   * `_EXTRACT_STR`, `registerWorklet` and the Lynx PAPI globals like `__CreateView` are always kept.
+   */
+  reserved?: Array<string>
+}
+/**
+ * @public
+ * Options of the code generator.
+ */
+export interface CodegenConfig {
+  /**
+   * @public
+   * The ECMAScript version of the output code.
+   *
+   * @remarks
+   * This only affects how the code is printed (e.g.: escapes in strings),
+   * syntax is not lowered to the target version.
+   * Code with syntax newer than the target, e.g. arrow functions with `'es5'`, is reported as an error.
+   * The syntax of ES modules is not checked, since it is handled by the bundler.
+   *
+   * Default value: `'esnext'`.
+   */
+  target?: 'es3' | 'es5' | 'es2015' | 'es2016' | 'es2017' | 'es2018' | 'es2019' | 'es2020' | 'es2021' | 'es2022' | 'es2023' | 'es2024' | 'esnext'
+  /**
+   * @public
+   * Whether to escape all the non-ASCII characters.
+   *
+   * @remarks
+   * Default value: `false`.
+   */
+  asciiOnly?: boolean
+  /**
+   * @public
+   * Whether to remove whitespaces in the output code.
+   *
+   * @remarks
+   * Default value: `false`.
+   */
+  minify?: boolean
+  /**
+   * @public
+   * Which comments to keep in the output code.
+   *
+   * @remarks
+   * Annotations like `@__PURE__` and magic comments like `webpackChunkName` are always kept.
+   *
+   * Default value: `'all'`.
+   */
+  comments?: 'all' | 'license' | 'none'
+  /**
+   * @public
+   * The `sourceRoot` field of the output source map.
+   */
+  sourceRoot?: string
+}
 /**
  * This is esbuild's PartialMessage definition.
  * https://github.com/evanw/esbuild/blob/043ab306c490f692c68e8d254bbf00b6468be87d/lib/shared/types.ts#L421
  */
+export interface TransformDualThreadOptions {
+  /** Options of the main thread (`LEPUS`) output. */
+  mainThread: TransformNodiffOptions
+  /** Options of the background (`JS`) output. */
+  background: TransformNodiffOptions
+  /**
+   * Cancel the transform of both threads if it is not finished within this many milliseconds.
+   * `timeout` of `mainThread` and `background` is not supported.
+   */
+  timeout?: number
+}
+export interface TransformDualThreadOutput {
+  mainThread: TransformNodiffOutput
+  background: TransformNodiffOutput
+}
 export interface PartialMessage {
   id?: string
   pluginName?: string
@@ -44,6 +291,45 @@ export interface PartialLocation {
   lineText?: string
   suggestion?: string
 }
+/** Artifacts produced by the transform, see `TransformNodiffOutput.metadata`. */
+export interface TransformMetadata {
+  /** Snapshots created from JSX elements. */
+  snapshots: Array<SnapshotMetadata>
+  /** Worklets registered from `'main thread'` and `'use worklet'` functions. */
+  worklets: Array<WorkletMetadata>
+  /** CSS imports rewritten with `?cssId=`. */
+  cssImports: Array<CssImportMetadata>
+  /** `import()` calls rewritten for lazy bundles. */
+  dynamicImports: Array<DynamicImportMetadata>
+}
+export interface SnapshotMetadata {
+  /** The snapshot id, e.g. `__snapshot_da39a_test_1`. */
+  id: string
+  /** Location of the JSX element. */
+  location?: PartialLocation
+}
+export interface WorkletMetadata {
+  /** The hash the worklet is registered with. */
+  hash: string
+  type: 'main-thread' | 'ui'
+  /** Location of the worklet function. */
+  location?: PartialLocation
+}
+export interface CssImportMetadata {
+  /** The original import source, without `?cssId=`. */
+  src: string
+  cssId: number
+  /** Location of the import declaration. */
+  location?: PartialLocation
+}
+export interface DynamicImportMetadata {
+  /** The module id, absent if it is not a string literal. */
+  src?: string
+  /** Whether the call is replaced with the runtime `__dynamicImport`. */
+  lazy: boolean
+  /** Location of the `import()` call. */
+  location?: PartialLocation
+}
 export interface DarkModeConfig {
   /**
    * @public
@@ -309,6 +595,12 @@ export interface CompatVisitorConfig {
    * @remarks
    * Default value: `false`
    *
+   * Prefer turning off the codes `RL1001` to `RL1006` with the `diagnostics` option of the transform,
+   * which also works for other warnings.
+   *
+   * This option takes precedence over `diagnostics`: the deprecated warnings are not emitted at all,
+   * so they cannot be reported as errors by `diagnostics` either.
+   *
    * @example
    *
    * Disable all the `DEPRECATED:` warnings.
@@ -457,6 +749,42 @@ export interface ExtractStrConfig {
   strLength: number
   /** @internal */
   extractedStrArr?: Array<string>
+  /**
+   * @public
+   * How string literals are selected for extraction.
+   *
+   * @remarks
+   * - `'length'`: every string literal of at least `strLength`, in the order they are first seen.
+   * - `'size'`: only the string literals of at least `strLength` that occur often enough for
+   *   `_EXTRACT_STR[i]` and the table entry to be smaller than the literals they replace,
+   *   the most frequent first so that they get the shortest indexes.
+   *
+   * Default value: `'length'`.
+   */
+  strategy?: 'length' | 'size'
+  /**
+   * @public
+   * A string table shared by several bundles, in the format of `extractStrManifest` of the output.
+   *
+   * @remarks
+   * The strings of the table keep their indexes in `_EXTRACT_STR`, and the main thread bundle appends
+   * the new strings it extracts to the end of the table, in a deterministic order.
+   * Transform the bundles one after another with the `extractStrManifest` of the previous one,
+   * and pass the `strings` of the final table as `extractedStrArr` of the background bundles.
+   */
+  manifest?: string
+  /**
+   * @public
+   * Whether to extract the strings of template literals, string property keys and JSX texts as well.
+   *
+   * @remarks
+   * `` `${a}long string` `` becomes `` `${a}${_EXTRACT_STR[i]}` ``, `{ "long key": a }` becomes
+   * `{ [_EXTRACT_STR[i]]: a }` and `<text>long text</text>` becomes `<text>{_EXTRACT_STR[i]}</text>`.
+   * Tagged templates and `__proto__` keys are never extracted.
+   *
+   * Default value: `false`.
+   */
+  allPositions?: boolean
 }
 export interface InjectVisitorConfig {
   inject: Record<string, ['expr', string] | ['importDefault', string] | ['importStarAs', string] | ['importNamed', string, string]>
@@ -561,6 +889,11 @@ export interface JsxTransformerConfig {
   target: 'LEPUS' | 'JS' | 'MIXED'
   /** @internal */
   isDynamicComponent?: boolean
+  /**
+   * Share one snapshot definition between structurally identical elements of a module.
+   * Defaults to `false`.
+   */
+  dedupeSnapshots?: boolean
 }
 export interface WorkletVisitorConfig {
   /**
@@ -590,6 +923,17 @@ export interface TransformNodiffOptions {
   sourcemap: boolean | string
   sourceMapColumns?: boolean
   inlineSourcesContent?: boolean
+  /**
+   * @public
+   * The source map of the input code in JSON format, e.g. from a previous loader.
+   * It is composed into the output source map.
+   */
+  inputSourceMap?: string
+  /**
+   * @public
+   * Options of the code generator, e.g. `target`, `asciiOnly` and `comments`.
+   */
+  codegen?: CodegenConfig
   /**
    * @public
    * This is swc syntax config in JSON format
@@ -607,14 +951,120 @@ export interface TransformNodiffOptions {
   dynamicImport?: boolean | DynamicImportVisitorConfig
   /** @internal */
   inject?: boolean | InjectVisitorConfig
+  /**
+   * @public
+   * The ordered list of passes to run. Defaults to the built-in pipeline.
+   *
+   * @remarks
+   * A pass may appear more than once. Whether a pass is enabled and how it is
+   * configured is still controlled by its own option, e.g. `compat`.
+   */
+  pipeline?: Array<'typescript' | 'dynamicImport' | 'refresh' | 'compat' | 'worklet' | 'cssScope' | 'list' | 'snapshot' | 'directiveDCE' | 'defineDCE' | 'simplify' | 'shake' | 'react' | 'resolver' | 'compatPost' | 'inject'>
+  /**
+   * @public
+   * Whether to collect per-pass timing and AST size into `TransformNodiffOutput.profile`.
+   */
+  profile?: boolean
+  /**
+   * @public
+   * The directory to cache transform results in. The cache is disabled by default.
+   *
+   * @remarks
+   * Entries are keyed by the source code, the options and the version of the transform.
+   * The cache is bypassed when `profile` is enabled, and is not supported in the WebAssembly build.
+   */
+  cacheDir?: string
+  /**
+   * @public
+   * Cancel the transform if it is not finished within `timeout` milliseconds.
+   *
+   * @remarks
+   * The timeout is checked between passes, so a single pass is never interrupted.
+   * A cancelled transform rejects (or throws) with an error whose `code` is `'Cancelled'`.
+   */
+  timeout?: number
+  /**
+   * @public
+   * Apply the machine-applicable fixes of the deprecation warnings of `compat` to the input code,
+   * and return the rewritten code in `TransformNodiffOutput.codemod`.
+   *
+   * @remarks
+   * The fixes come with the warnings, so `compat.disableDeprecatedWarning` must not be set.
+   * Some deprecations, e.g. `<component is={...} />`, have no fix and still need to be migrated by hand.
+   * Not supported by `transformReactLynxAst`, which has no input code to apply the fixes to.
+   */
+  codemod?: boolean
+  /**
+   * @public
+   * The severity of diagnostics by their codes, e.g. `{ RL1005: 'off', RL3001: 'warn', RL1103: 'error' }`.
+   *
+   * @remarks
+   * Every diagnostic emitted by the plugins has a stable code, which is the `id` of the message.
+   * `'off'` drops the diagnostic, `'warn'` and `'error'` report it as a warning or an error.
+   * The deprecated warnings `RL1001` to `RL1006` are not emitted at all with `compat.disableDeprecatedWarning`.
+   */
+  diagnostics?: Record<string, 'off' | 'warn' | 'error'>
+  /**
+   * @public
+   * Keep transforming when the code has syntax errors, e.g. in the dev server.
+   *
+   * @remarks
+   * The passes run on the program recovered by the parser, and every syntax error is still reported in `errors`.
+   * If the code cannot be parsed at all, `code` is a module that throws the syntax errors when evaluated,
+   * instead of an empty string.
+   * Not supported by `transformReactLynxAst`, whose program is already parsed.
+   */
+  recover?: boolean
+  /**
+   * @public
+   * Render a code frame of each error and warning in its `detail`, e.g. for printing them in a terminal.
+   *
+   * @remarks
+   * The code frame shows the annotated lines with two lines of context, `^` under the span of the diagnostic,
+   * `-` under its labeled spans, and its notes and suggestions.
+   */
+  codeFrame?: boolean
+}
+/** Profiling result of a single pass, see `TransformNodiffOptions.profile`. */
+export interface PassProfile {
+  /** The name of the pass, e.g. `snapshot`. */
+  name: string
+  /** Wall time spent in the pass, in milliseconds. */
+  duration: number
+  /** Number of AST nodes before the pass. */
+  nodesBefore: number
+  /** Number of AST nodes after the pass. */
+  nodesAfter: number
+  /** Number of errors and warnings emitted by the pass. */
+  diagnostics: number
 }
 export interface TransformNodiffOutput {
   code: string
   map?: string
   errors: Array<PartialMessage>
   warnings: Array<PartialMessage>
+  /** Per-pass profiling results, only available when `profile` is enabled. */
+  profile?: Array<PassProfile>
+  /** Snapshots, worklets, CSS imports and dynamic imports produced by the transform. */
+  metadata: TransformMetadata
+  /** The input code with the fixes applied, only available when `codemod` is enabled. */
+  codemod?: string
 }
 export function transformReactLynxSync(code: string, options?: TransformNodiffOptions | undefined | null): TransformNodiffOutput
-export function transformReactLynx(code: string, options?: TransformNodiffOptions | undefined | null): Promise<TransformNodiffOutput>
+export function transformReactLynx(code: string, options?: TransformNodiffOptions | undefined | null, signal?: AbortSignal | undefined | null): Promise<TransformNodiffOutput>
+export function transformReactLynxAstSync(input: TransformAstInput, options?: TransformNodiffOptions | undefined | null): TransformNodiffAstOutput
+export function transformReactLynxAst(input: TransformAstInput, options?: TransformNodiffOptions | undefined | null, signal?: AbortSignal | undefined | null): Promise<TransformNodiffAstOutput>
+export function transformReactLynxDualThreadSync(code: string, options: TransformDualThreadOptions): TransformDualThreadOutput
+export function transformReactLynxDualThread(code: string, options: TransformDualThreadOptions, signal?: AbortSignal | undefined | null): Promise<TransformDualThreadOutput>
+export function transformReactLynxBatch(files: Array<TransformBatchFile>): Promise<Array<TransformNodiffOutput>>
 export function transformBundleResultSync(code: string, options?: TransformNodiffBundleOptions | undefined | null): TransformNodiffBundleOutput
-export function transformBundleResult(code: string, options?: TransformNodiffBundleOptions | undefined | null): Promise<TransformNodiffBundleOutput>
+export function transformBundleResult(code: string, options?: TransformNodiffBundleOptions | undefined | null, signal?: AbortSignal | undefined | null): Promise<TransformNodiffBundleOutput>
+/**
+ * Serialize the errors and warnings of transforms into a SARIF 2.1.0 log,
+ * e.g. for uploading them to code scanning dashboards.
+ *
+ * The log has a single run, with every diagnostic code of the plugins as its rules.
+ * The `file` of the locations is used as the artifact uri as is,
+ * so it should be made relative to the root of the repository beforehand.
+ */
+export function formatSarif(errors: Array<PartialMessage>, warnings: Array<PartialMessage>): string
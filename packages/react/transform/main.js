@@ -11,5 +11,11 @@ export const {
   transformReactLynxSync,
   transformBundleResultSync,
   transformReactLynx,
+  transformReactLynxBatch,
+  transformReactLynxAstSync,
+  transformReactLynxAst,
+  transformReactLynxDualThreadSync,
+  transformReactLynxDualThread,
   transformBundleResult,
+  formatSarif,
 } = process.env['USE_NAPI'] ? require('./index.cjs') : exports;
@@ -0,0 +1,254 @@
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use serde::{Deserialize, Serialize};
+use swc_core::{
+  base::Compiler,
+  common::{
+    comments::{Comment, SingleThreadedComments},
+    errors::{DiagnosticId, Emitter, Handler},
+    sync::Lrc,
+    BytePos, FileName, FilePathMapping, Mark, SourceMap, Span, GLOBALS,
+  },
+  ecma::{
+    ast::Program,
+    visit::{Visit, VisitWith},
+  },
+};
+use swc_plugins_shared::{diagnostics, transform_mode_napi::TransformMode, utils::calc_hash};
+
+use crate::{
+  cancellation::{Cancellation, Cancelled},
+  esbuild::{EsbuildEmitter, PartialMessage},
+  metadata::TransformMetadata,
+  print_program,
+  profile::PassProfile,
+  run_passes, MultiEmitter, PassContext, PassStage, TransformNodiffOptions,
+};
+
+#[napi(object)]
+#[derive(Clone, Debug, Default)]
+pub struct TransformAstInput {
+  /// The swc `Program` in JSON format.
+  pub program: String,
+  /// The comments of `program` in JSON format,
+  /// i.e. `{ leading: Record<number, Comment[]>, trailing: Record<number, Comment[]> }`.
+  pub comments: Option<String>,
+  /// The code `program` is parsed from, used for diagnostics, metadata and source maps.
+  ///
+  /// @remarks
+  /// Spans of `program` and `comments` should be relative to it, with its first byte at position `1`,
+  /// which is the case when it is the first file parsed with a fresh swc `SourceMap`.
+  ///
+  /// It is required unless every span of `program` is dummy and there are no `comments`,
+  /// e.g. when `program` is built by hand rather than parsed.
+  pub source: Option<String>,
+  /// Whether to print the transformed program into `code`. Defaults to `false`.
+  pub print: Option<bool>,
+}
+
+#[napi(object)]
+pub struct TransformNodiffAstOutput {
+  /// The transformed `Program` in JSON format, absent if `program` cannot be read.
+  pub program: Option<String>,
+  /// The comments of the transformed `program`, in the same format as `TransformAstInput.comments`.
+  pub comments: Option<String>,
+  /// The printed code, only available when `print` is enabled.
+  pub code: Option<String>,
+  pub map: Option<String>,
+
+  pub errors: Vec<PartialMessage>,
+  pub warnings: Vec<PartialMessage>,
+
+  /// Per-pass profiling results, only available when `profile` is enabled.
+  pub profile: Option<Vec<PassProfile>>,
+  /// Snapshots, worklets, CSS imports and dynamic imports produced by the transform.
+  pub metadata: TransformMetadata,
+}
+
+/// The JSON format of `SingleThreadedComments`.
+///
+/// `BTreeMap` is used so that the output is stable.
+#[derive(Default, Serialize, Deserialize)]
+struct CommentsJson {
+  #[serde(default)]
+  leading: BTreeMap<BytePos, Vec<Comment>>,
+  #[serde(default)]
+  trailing: BTreeMap<BytePos, Vec<Comment>>,
+}
+
+fn read_comments(comments: Option<&str>, handler: &Handler) -> Option<SingleThreadedComments> {
+  let CommentsJson { leading, trailing } = match comments {
+    Some(comments) => match serde_json::from_str(comments) {
+      Ok(comments) => comments,
+      Err(err) => {
+        handler
+          .struct_err_with_code(
+            &format!("failed to read comments: {err}"),
+            DiagnosticId::Error(diagnostics::INVALID_AST_INPUT.into()),
+          )
+          .emit();
+        return None;
+      }
+    },
+    None => Default::default(),
+  };
+
+  Some(SingleThreadedComments::from_leading_and_trailing(
+    Rc::new(RefCell::new(leading.into_iter().collect())),
+    Rc::new(RefCell::new(trailing.into_iter().collect())),
+  ))
+}
+
+/// Finds a span that is not dummy, which points into the code the program is parsed from.
+#[derive(Default)]
+struct HasSpan(bool);
+
+impl Visit for HasSpan {
+  fn visit_span(&mut self, span: &Span) {
+    self.0 |= !span.is_dummy();
+  }
+}
+
+fn write_comments(comments: &SingleThreadedComments) -> Option<String> {
+  let (leading, trailing) = comments.borrow_all();
+  serde_json::to_string(&CommentsJson {
+    leading: leading.clone().into_iter().collect(),
+    trailing: trailing.clone().into_iter().collect(),
+  })
+  .ok()
+}
+
+/// The program is already parsed, so there are neither syntax errors to recover from
+/// nor input code to apply the fixes of `codemod` to.
+///
+/// `codemod` and `recover` are rejected rather than ignored.
+pub fn validate_ast_options(options: &TransformNodiffOptions) -> Result<(), String> {
+  if options.codemod == Some(true) {
+    return Err("`codemod` is not supported by `transformReactLynxAst`".into());
+  }
+  if options.recover == Some(true) {
+    return Err("`recover` is not supported by `transformReactLynxAst`".into());
+  }
+  Ok(())
+}
+
+/// Same as `transform_react_lynx_inner`, but takes and returns an swc `Program`,
+/// which saves a parse and a codegen when it is used along with other swc passes.
+///
+/// `options.syntaxConfig`, `options.isModule` and `options.cacheDir` are ignored,
+/// `options.codemod` and `options.recover` are rejected by `validate_ast_options`.
+pub fn transform_react_lynx_ast_inner(
+  input: TransformAstInput,
+  options: TransformNodiffOptions,
+  cancellation: &Cancellation,
+) -> Result<TransformNodiffAstOutput, Cancelled> {
+  let cancellation = cancellation.with_timeout(options.timeout);
+  let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+  let content_hash = match options.mode {
+    Some(TransformMode::Test) => "test".into(),
+    _ => calc_hash(input.source.as_deref().unwrap_or(&input.program)),
+  };
+  let has_source = input.source.is_some();
+  cm.new_source_file(
+    FileName::Real(options.filename.clone().into()).into(),
+    input.source.unwrap_or_default(),
+  );
+
+  let c = Compiler::new(cm.clone());
+
+  let (esbuild_emitter, errors, warnings) =
+    EsbuildEmitter::new(options.plugin_name.clone(), Some(c.cm.clone()));
+  let esbuild_emitter = esbuild_emitter.with_code_frame(options.code_frame == Some(true));
+  let emitters: Vec<Box<dyn Emitter>> = vec![Box::new(esbuild_emitter)];
+  let emitter = Box::new(MultiEmitter::new(emitters).with_severities(options.diagnostics.clone()));
+  let handler = Handler::with_emitter(true, false, emitter);
+
+  let result = GLOBALS.set(&Default::default(), || {
+    let program = match serde_json::from_str::<Program>(&input.program) {
+      Ok(program) => program,
+      Err(err) => {
+        handler
+          .struct_err_with_code(
+            &format!("failed to read program: {err}"),
+            DiagnosticId::Error(diagnostics::INVALID_AST_INPUT.into()),
+          )
+          .emit();
+        return Ok(None);
+      }
+    };
+    let Some(comments) = read_comments(input.comments.as_deref(), &handler) else {
+      return Ok(None);
+    };
+    if !has_source {
+      let mut has_span = HasSpan::default();
+      program.visit_with(&mut has_span);
+      let (leading, trailing) = comments.borrow_all();
+      if has_span.0 || !leading.is_empty() || !trailing.is_empty() {
+        handler
+          .struct_err_with_code(
+            "`source` is required since `program` or `comments` has spans pointing into it",
+            DiagnosticId::Error(diagnostics::INVALID_AST_INPUT.into()),
+          )
+          .emit();
+        return Ok(None);
+      }
+    }
+
+    let diagnostics_count =
+      || (errors.read().unwrap().len() + warnings.read().unwrap().len()) as u32;
+    let ctx = PassContext {
+      cm: &cm,
+      comments: &comments,
+      handler: &handler,
+      options: &options,
+      content_hash: &content_hash,
+      diagnostics_count: &diagnostics_count,
+      unresolved_mark: Mark::new(),
+      top_level_mark: Mark::new(),
+      cancellation: &cancellation,
+    };
+    let (program, profile, metadata) = run_passes(
+      &ctx,
+      program,
+      &options.resolved_pipeline(),
+      PassStage::Whole,
+    )?;
+
+    // The code generator takes the comments it prints, so they are serialized first.
+    let comments_json = write_comments(&comments);
+    let printed = match input.print {
+      Some(true) => print_program(&c, &program, &comments, &handler, &options),
+      _ => None,
+    };
+
+    Ok(Some(TransformNodiffAstOutput {
+      program: serde_json::to_string(&program).ok(),
+      comments: comments_json,
+      code: printed.as_ref().map(|printed| printed.code.clone()),
+      map: printed.and_then(|printed| printed.map),
+      errors: vec![],
+      warnings: vec![],
+      profile,
+      metadata,
+    }))
+  })?;
+
+  let result = result.unwrap_or(TransformNodiffAstOutput {
+    program: None,
+    comments: None,
+    code: None,
+    map: None,
+    errors: vec![],
+    warnings: vec![],
+    profile: None,
+    metadata: Default::default(),
+  });
+
+  let errors = errors.read().unwrap().clone();
+  let warnings = warnings.read().unwrap().clone();
+  Ok(TransformNodiffAstOutput {
+    errors,
+    warnings,
+    ..result
+  })
+}
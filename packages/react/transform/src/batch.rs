@@ -0,0 +1,77 @@
+use rayon::prelude::*;
+use swc_core::common::{sync::Lrc, FilePathMapping, SourceMap};
+
+use crate::{
+  cancellation::Cancelled,
+  esbuild::{PartialLocation, PartialMessage},
+  transform_react_lynx_with_source_map, validate_options, TransformNodiffOptions,
+  TransformNodiffOutput,
+};
+
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct TransformBatchFile {
+  pub code: String,
+  /// Overrides `options.filename`.
+  pub filename: String,
+  /// Options of this file, including its own `cacheDir` and `timeout`.
+  pub options: Option<TransformNodiffOptions>,
+}
+
+/// Transform all the `files` in parallel, sharing one `SourceMap`.
+///
+/// The outputs are in the same order as `files`, each with its own `errors` and `warnings`.
+/// Each file is looked up in and stored to its own `options.cacheDir`, as in `transformReactLynx`.
+/// A file with invalid options, or that does not finish within its `timeout`,
+/// gets an empty `code` and an error, without affecting the other files.
+pub fn transform_react_lynx_batch_inner(
+  files: Vec<TransformBatchFile>,
+) -> Vec<TransformNodiffOutput> {
+  let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+
+  files
+    .into_par_iter()
+    .map(|file| {
+      let options = TransformNodiffOptions {
+        filename: file.filename,
+        ..file.options.unwrap_or_default()
+      };
+      let (filename, plugin_name) = (options.filename.clone(), options.plugin_name.clone());
+      if let Err(err) = validate_options(&options) {
+        return failed_output(err.reason.clone(), filename, plugin_name);
+      }
+      transform_react_lynx_with_source_map(cm.clone(), file.code, options, &Default::default())
+        .unwrap_or_else(|cancelled: Cancelled| {
+          failed_output(cancelled.to_string(), filename, plugin_name)
+        })
+    })
+    .collect()
+}
+
+/// The output of a file that is not transformed, with `text` as its only error.
+fn failed_output(text: String, filename: String, plugin_name: String) -> TransformNodiffOutput {
+  TransformNodiffOutput {
+    code: "".into(),
+    map: None,
+    errors: vec![PartialMessage {
+      id: None,
+      plugin_name: Some(plugin_name),
+      text: Some(text),
+      location: Some(PartialLocation {
+        file: Some(filename),
+        namespace: None,
+        line: None,
+        column: None,
+        length: None,
+        line_text: None,
+        suggestion: None,
+      }),
+      notes: None,
+      detail: None,
+    }],
+    warnings: vec![],
+    profile: None,
+    metadata: Default::default(),
+    codemod: None,
+  }
+}
@@ -1,6 +1,8 @@
 #![deny(clippy::all)]
 
+use crate::cancellation::{Cancellation, Cancelled};
 use crate::esbuild::{EsbuildEmitter, PartialMessage};
+use crate::{read_input_source_map, IsModuleConfig, SyntaxConfig};
 use napi::Either;
 use std::vec;
 use swc_core::common::pass::Optional;
@@ -11,23 +13,28 @@ use swc_core::{
   },
   common::{
     comments::SingleThreadedComments,
-    errors::{DiagnosticBuilder, Emitter, Handler, HANDLER},
+    errors::{DiagnosticBuilder, DiagnosticId, Emitter, Handler, HANDLER},
     sync::Lrc,
+    util::take::Take,
     FileName, FilePathMapping, Mark, SourceMap, GLOBALS,
   },
   ecma::{
     ast::*,
-    codegen,
+    minifier::{optimize, option::ExtraOptions},
     parser::{EsSyntax, Syntax},
-    transforms::base::{helpers, hygiene::hygiene_with_config, resolver},
+    transforms::base::{fixer::fixer, helpers, hygiene::hygiene_with_config, resolver},
     visit::visit_mut_pass,
   },
 };
+use swc_plugins_shared::diagnostics;
 
 // currently `use xxx as yyy` is not supported by napi-rs
 // So we have to use different name
-use crate::swc_plugin_extract_str::{ExtractStrConfig, ExtractStrVisitor};
-use crate::swc_plugin_worklet_post_process::WorkletPostProcessorVisitor;
+use crate::codegen_config::CodegenConfig;
+use crate::minify_config::{ExtractStrTables, MinifyConfig};
+use crate::swc_plugin_extract_str::{ExtractStrConfig, ExtractStrManifest, ExtractStrVisitor};
+use crate::swc_plugin_snapshot_dce::{SnapshotDCEConfig, SnapshotDCEOutput, SnapshotDCEVisitor};
+use crate::swc_plugin_worklet_post_process::{WorkletManifest, WorkletPostProcessorVisitor};
 
 #[napi(object)]
 #[derive(Clone, Debug)]
@@ -36,8 +43,40 @@ pub struct TransformNodiffBundleOptions {
   pub plugin_name: String,
   pub source_file_name: Option<String>,
   pub sourcemap: Either<bool, String>,
+  /// The source map of the input code in JSON format.
+  /// It is composed into the output source map.
+  pub input_source_map: Option<String>,
+  /// The swc syntax config of the input code in JSON format, see `TransformNodiffOptions.syntaxConfig`.
+  /// TypeScript and JSX are parsed, and printed as they are.
+  ///
+  /// Default value: ECMAScript.
+  #[napi(ts_type = "string")]
+  pub syntax_config: Option<SyntaxConfig>,
+  /// Whether the input code is a module, see `TransformNodiffOptions.isModule`.
+  /// The `_EXTRACT_STR` table of a script is declared in its IIFE rather than at the top level.
+  ///
+  /// Default value: `true`.
+  #[napi(ts_type = "boolean | 'unknown' | 'commonjs'")]
+  pub is_module: Option<IsModuleConfig>,
   pub extract_str: Either<bool, ExtractStrConfig>,
+  /// Deprecated, use `codegen.minify` instead.
   pub minify: Option<bool>,
+  /// Options of the code generator, `codegen.minify` takes precedence over `minify`.
+  pub codegen: Option<CodegenConfig>,
+  /// Compress and mangle the code, `true` for the default options of `MinifyConfig`.
+  /// Whitespaces are removed as well, unless `codegen.minify` is `false`.
+  pub minifier: Option<Either<bool, MinifyConfig>>,
+  /// Remove the registrations of the worklets whose hashes are not in the list,
+  /// see `TransformNodiffBundleOutput.worklets`.
+  pub worklet_allow_list: Option<Vec<String>>,
+  /// Remove the no-op PAPI calls in the creators of the snapshots, e.g. `__SetClasses(el, "")`,
+  /// see `TransformNodiffBundleOutput.snapshotDCE`. With `SnapshotDCEConfig.liveSnapshots`,
+  /// the snapshots that are referenced in neither this bundle nor the background bundle are removed as well.
+  #[napi(js_name = "snapshotDCE")]
+  pub snapshot_dce: Option<Either<bool, SnapshotDCEConfig>>,
+  /// Cancel the transform if it is not finished within `timeout` milliseconds,
+  /// see `TransformNodiffOptions.timeout`.
+  pub timeout: Option<u32>,
 }
 
 impl Default for TransformNodiffBundleOptions {
@@ -47,8 +86,16 @@ impl Default for TransformNodiffBundleOptions {
       filename: Default::default(),
       source_file_name: Default::default(),
       sourcemap: Either::A(false),
+      input_source_map: None,
+      syntax_config: None,
+      is_module: None,
       extract_str: Either::A(false),
       minify: Some(false),
+      codegen: None,
+      minifier: None,
+      worklet_allow_list: None,
+      snapshot_dce: None,
+      timeout: None,
     }
   }
 }
@@ -58,7 +105,21 @@ pub struct TransformNodiffBundleOutput {
   pub code: String,
   pub map: Option<String>,
   pub select_str_vec: Option<Vec<String>>,
+  /// The estimated number of bytes saved by `extractStr`, i.e. the length of the replaced string literals
+  /// minus the length of the `_EXTRACT_STR[i]`s and of the table. Only available when `extractStr` is enabled.
+  pub extract_str_saved_bytes: Option<i64>,
+  /// The string table in JSON after this bundle, see `ExtractStrConfig.manifest`.
+  /// Only available for the main thread bundle when `extractStr` is enabled.
+  pub extract_str_manifest: Option<String>,
+  /// The strings of at least `strLength` that are left inline since they are not in `extractedStrArr`.
+  /// Only available for the background bundle when `extractStr` is enabled.
+  pub extract_str_unresolved: Option<Vec<String>>,
   pub use_worklet: Option<bool>,
+  /// The worklets registered by `registerWorklet` and `registerWorkletInternal` in the bundle.
+  pub worklets: Option<WorkletManifest>,
+  /// The snapshots and PAPI calls removed, only available when `snapshotDCE` is enabled.
+  #[napi(js_name = "snapshotDCE")]
+  pub snapshot_dce: Option<SnapshotDCEOutput>,
 
   // #[napi(ts_type = "Array<import('esbuild').PartialMessage>")]
   pub errors: Vec<PartialMessage>,
@@ -85,10 +146,32 @@ impl Emitter for MultiEmitter {
   }
 }
 
+/// Parse the `extractStr.manifest` option, emitting an error if it is invalid.
+fn read_extract_str_manifest(manifest: &str, handler: &Handler) -> Option<ExtractStrManifest> {
+  let message = match serde_json::from_str::<ExtractStrManifest>(manifest) {
+    Ok(manifest) if manifest.version == ExtractStrManifest::VERSION => return Some(manifest),
+    Ok(manifest) => format!(
+      "unsupported version {} of extractStr.manifest, expected {}",
+      manifest.version,
+      ExtractStrManifest::VERSION
+    ),
+    Err(err) => format!("failed to read extractStr.manifest: {err}"),
+  };
+  handler
+    .struct_err_with_code(
+      &message,
+      DiagnosticId::Error(diagnostics::INVALID_EXTRACT_STR_MANIFEST.into()),
+    )
+    .emit();
+  None
+}
+
 pub fn transform_bundle_result_inner(
   code: String,
   options: TransformNodiffBundleOptions,
-) -> TransformNodiffBundleOutput {
+  cancellation: &Cancellation,
+) -> Result<TransformNodiffBundleOutput, Cancelled> {
+  let cancellation = cancellation.with_timeout(options.timeout);
   let comments = SingleThreadedComments::default();
   let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
   let fm = cm.new_source_file(FileName::Real(options.filename.clone().into()).into(), code);
@@ -106,8 +189,10 @@ pub fn transform_bundle_result_inner(
       fm,
       &handler,
       EsVersion::latest(),
-      Syntax::Es(EsSyntax::default()),
-      IsModule::Bool(true),
+      options
+        .syntax_config
+        .map_or(Syntax::Es(EsSyntax::default()), Into::into),
+      options.is_module.map_or(IsModule::Bool(true), Into::into),
       Some(&comments),
     );
     let should_extract_str = match options.extract_str {
@@ -117,7 +202,7 @@ pub fn transform_bundle_result_inner(
     let program = match program {
       Ok(program) => program,
       Err(_) => {
-        return TransformNodiffBundleOutput {
+        return Ok(TransformNodiffBundleOutput {
           code: "".into(),
           map: None,
           errors: errors.read().unwrap().clone(),
@@ -126,36 +211,120 @@ pub fn transform_bundle_result_inner(
             true => Some(vec!["".to_string()]),
             false => None,
           },
+          extract_str_saved_bytes: None,
+          extract_str_manifest: None,
+          extract_str_unresolved: None,
           use_worklet: None,
-        };
+          worklets: None,
+          snapshot_dce: None,
+        });
       }
     };
 
     let mut extract_str_vis: ExtractStrVisitor = match options.extract_str {
       Either::A(_) => ExtractStrVisitor::default(),
-      Either::B(config) => ExtractStrVisitor::new(config),
+      Either::B(config) => {
+        let manifest = config
+          .manifest
+          .as_deref()
+          .and_then(|manifest| read_extract_str_manifest(manifest, &handler));
+        let visitor = ExtractStrVisitor::new(config);
+        match manifest {
+          Some(manifest) => visitor.with_manifest(manifest),
+          None => visitor,
+        }
+      }
     };
-    let extract_str_plugin =
+    let mut extract_str_plugin =
       Optional::new(visit_mut_pass(&mut extract_str_vis), should_extract_str);
-    let mut worklet_post_process_vis = WorkletPostProcessorVisitor::default();
-    let worklet_post_process_plugin = visit_mut_pass(&mut worklet_post_process_vis);
-
-    let pass = (
-      resolver(Mark::new(), Mark::new(), true),
-      extract_str_plugin,
-      worklet_post_process_plugin,
-      hygiene_with_config(Default::default()),
-    );
+    let mut worklet_post_process_vis = match options.worklet_allow_list.clone() {
+      Some(allow_list) => WorkletPostProcessorVisitor::default().with_allow_list(allow_list),
+      None => WorkletPostProcessorVisitor::default(),
+    };
+    let mut worklet_post_process_plugin = visit_mut_pass(&mut worklet_post_process_vis);
 
-    let program = helpers::HELPERS.set(&helpers::Helpers::new(true), || {
-      HANDLER.set(&handler, || program.apply(pass))
+    let minify_config = match &options.minifier {
+      None | Some(Either::A(false)) => None,
+      Some(Either::A(true)) => Some(MinifyConfig::default()),
+      Some(Either::B(config)) => Some(config.clone()),
+    };
+    let unresolved_mark = Mark::new();
+    let top_level_mark = Mark::new();
+    let should_snapshot_dce = !matches!(options.snapshot_dce, None | Some(Either::A(false)));
+    let mut snapshot_dce_vis = match &options.snapshot_dce {
+      Some(Either::B(config)) => {
+        SnapshotDCEVisitor::new(unresolved_mark).with_live_snapshots(config.live_snapshots.clone())
+      }
+      _ => SnapshotDCEVisitor::new(unresolved_mark),
+    };
+    let mut snapshot_dce_plugin =
+      Optional::new(visit_mut_pass(&mut snapshot_dce_vis), should_snapshot_dce);
+    // Runs after `extractStr` and the worklet post processor,
+    // so that `_EXTRACT_STR` and `registerWorklet` can be kept.
+    let mut minify_plugin = fn_pass(|program: &mut Program| {
+      let Some(config) = &minify_config else {
+        return;
+      };
+      let minify_options = config.to_minify_options(program);
+      let mut extract_str_tables = ExtractStrTables::new(unresolved_mark);
+      extract_str_tables.hide(program);
+      *program = optimize(
+        program.take(),
+        c.cm.clone(),
+        Some(&comments),
+        None,
+        &minify_options,
+        &ExtraOptions {
+          unresolved_mark,
+          top_level_mark,
+          mangle_name_cache: None,
+        },
+      );
+      extract_str_tables.restore(program);
     });
 
+    let passes: [&mut dyn Pass; 7] = [
+      &mut resolver(unresolved_mark, top_level_mark, true),
+      // Before `extractStr`, so that the strings of the removed snapshots are not extracted.
+      &mut snapshot_dce_plugin,
+      // Before `extractStr`, so that the hashes of the worklets are still string literals.
+      &mut worklet_post_process_plugin,
+      &mut extract_str_plugin,
+      &mut minify_plugin,
+      &mut hygiene_with_config(Default::default()),
+      &mut Optional::new(fixer(Some(&comments)), minify_config.is_some()),
+    ];
+
+    let program = helpers::HELPERS.set(&helpers::Helpers::new(true), || {
+      HANDLER.set(&handler, || {
+        let mut program = program;
+        for pass in passes {
+          cancellation.check()?;
+          program.mutate(pass);
+        }
+        cancellation.check()?;
+        Ok(program)
+      })
+    })?;
+
+    let codegen_config = CodegenConfig {
+      minify: options
+        .codegen
+        .as_ref()
+        .and_then(|codegen| codegen.minify)
+        .or(Some(
+          options.minify == Some(true) || minify_config.is_some(),
+        )),
+      ..options.codegen.clone().unwrap_or_default()
+    };
+    codegen_config.retain_comments(&comments);
+    codegen_config.check_target(&program, &handler);
+
     let result = c.print(
       &program,
       PrintArgs {
         output: None,
-        source_root: "".into(), // TODO: add root
+        source_root: Some(codegen_config.source_root.as_deref().unwrap_or("")),
         source_file_name: options.source_file_name.as_deref(),
         source_map_url: None,
         source_map_ignore_list: None,
@@ -166,30 +335,35 @@ pub fn transform_bundle_result_inner(
           Either::B(s) => SourceMapsConfig::Str(s),
         },
         source_map_names: &Default::default(),
-        orig: None,
+        orig: read_input_source_map(options.input_source_map.as_deref(), &handler),
         comments: Some(&comments),
         emit_source_map_columns: true,
         preamble: "",
-        codegen_config: codegen::Config::default()
-          .with_target(EsVersion::latest())
-          .with_minify(options.minify.unwrap_or(false))
-          .with_ascii_only(false),
+        codegen_config: codegen_config.to_codegen_config(),
       },
     );
     match result {
-      Ok(result) => TransformNodiffBundleOutput {
+      Ok(result) => Ok(TransformNodiffBundleOutput {
         code: result.code,
         map: result.map,
         errors: vec![],
         warnings: vec![],
+        extract_str_saved_bytes: should_extract_str.then_some(extract_str_vis.saved_bytes),
+        extract_str_manifest: extract_str_vis
+          .manifest()
+          .filter(|_| should_extract_str)
+          .and_then(|manifest| serde_json::to_string(&manifest).ok()),
+        extract_str_unresolved: extract_str_vis.unresolved().filter(|_| should_extract_str),
         select_str_vec: match should_extract_str {
           true => Some(extract_str_vis.select_str_vec),
           false => None,
         },
         use_worklet: Some(worklet_post_process_vis.result.has_worklet),
-      },
+        worklets: Some(worklet_post_process_vis.result.manifest()),
+        snapshot_dce: should_snapshot_dce.then_some(snapshot_dce_vis.result),
+      }),
       Err(_) => {
-        return TransformNodiffBundleOutput {
+        return Ok(TransformNodiffBundleOutput {
           code: "".into(),
           map: None,
           errors: errors.read().unwrap().clone(),
@@ -198,11 +372,16 @@ pub fn transform_bundle_result_inner(
             true => Some(vec!["".to_string()]),
             false => None,
           },
+          extract_str_saved_bytes: None,
+          extract_str_manifest: None,
+          extract_str_unresolved: None,
           use_worklet: None,
-        };
+          worklets: None,
+          snapshot_dce: None,
+        });
       }
     }
-  });
+  })?;
 
   let r = TransformNodiffBundleOutput {
     code: result.code,
@@ -210,10 +389,15 @@ pub fn transform_bundle_result_inner(
     errors: errors.read().unwrap().clone(),
     warnings: warnings.read().unwrap().clone(),
     select_str_vec: result.select_str_vec,
+    extract_str_saved_bytes: result.extract_str_saved_bytes,
+    extract_str_manifest: result.extract_str_manifest,
+    extract_str_unresolved: result.extract_str_unresolved,
     use_worklet: result.use_worklet,
+    worklets: result.worklets,
+    snapshot_dce: result.snapshot_dce,
   };
 
-  r
+  Ok(r)
 }
 
 // #[cfg(test)]
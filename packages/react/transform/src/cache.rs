@@ -0,0 +1,190 @@
+use std::{
+  collections::BTreeMap,
+  fs,
+  path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha1::{Digest, Sha1};
+
+use crate::{
+  esbuild::PartialMessage, metadata::TransformMetadata, TransformNodiffOptions,
+  TransformNodiffOutput,
+};
+
+/// The version of `@lynx-js/react` this transform is shipped with, see `build.rs`.
+const VERSION: &str = env!("LYNX_REACT_VERSION");
+
+/// The hash of the sources the transform is built from, see `build.rs`.
+///
+/// It changes whenever the transform does, so that entries are not reused across
+/// builds between releases of `@lynx-js/react`.
+const SOURCE_HASH: &str = env!("LYNX_REACT_TRANSFORM_SOURCE_HASH");
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+  code: String,
+  map: Option<String>,
+  warnings: Vec<PartialMessage>,
+  metadata: TransformMetadata,
+  #[serde(default)]
+  codemod: Option<String>,
+}
+
+/// An on-disk cache of `transformReactLynx` outputs, see `TransformNodiffOptions.cacheDir`.
+///
+/// Entries are keyed by the source code, the options, the version of `@lynx-js/react`
+/// and the hash of the sources of the transform. Only outputs without errors are cached. All IO errors are
+/// ignored and treated as cache misses.
+pub struct TransformCache {
+  path: PathBuf,
+}
+
+impl TransformCache {
+  pub fn new(cache_dir: &str, code: &str, options: &TransformNodiffOptions) -> Self {
+    let mut hasher = Sha1::new();
+    hasher.update(code.as_bytes());
+    hasher.update([0]);
+    hasher.update(options_key(options).as_bytes());
+    hasher.update([0]);
+    hasher.update(VERSION.as_bytes());
+    hasher.update([0]);
+    hasher.update(SOURCE_HASH.as_bytes());
+    let key = hex::encode(hasher.finalize());
+
+    Self {
+      path: Path::new(cache_dir)
+        .join(&key[0..2])
+        .join(format!("{}.json", &key[2..])),
+    }
+  }
+
+  pub fn get(&self) -> Option<TransformNodiffOutput> {
+    let content = fs::read(&self.path).ok()?;
+    let entry: CacheEntry = serde_json::from_slice(&content).ok()?;
+
+    Some(TransformNodiffOutput {
+      code: entry.code,
+      map: entry.map,
+      errors: vec![],
+      warnings: entry.warnings,
+      profile: None,
+      metadata: entry.metadata,
+      codemod: entry.codemod,
+    })
+  }
+
+  pub fn set(&self, output: &TransformNodiffOutput) {
+    if !output.errors.is_empty() {
+      return;
+    }
+
+    let entry = CacheEntry {
+      code: output.code.clone(),
+      map: output.map.clone(),
+      warnings: output.warnings.clone(),
+      metadata: output.metadata.clone(),
+      codemod: output.codemod.clone(),
+    };
+    let Ok(content) = serde_json::to_vec(&entry) else {
+      return;
+    };
+    let Some(dir) = self.path.parent() else {
+      return;
+    };
+    if fs::create_dir_all(dir).is_err() {
+      return;
+    }
+
+    // Write to a temporary file first, so that concurrent readers never see a partial entry.
+    let tmp = self.path.with_extension(format!(
+      "{}.{:?}.tmp",
+      std::process::id(),
+      std::thread::current().id()
+    ));
+    if fs::write(&tmp, content).is_ok() && fs::rename(&tmp, &self.path).is_err() {
+      let _ = fs::remove_file(&tmp);
+    }
+  }
+}
+
+/// The canonical JSON of `options`, with the options not affecting the output left out.
+fn options_key(options: &TransformNodiffOptions) -> String {
+  serde_json::to_value(options)
+    .map(sort_keys)
+    .unwrap_or_default()
+    .to_string()
+}
+
+/// Sort the keys of the objects in `value`, since `HashMap`s are serialized in random order.
+fn sort_keys(value: Value) -> Value {
+  match value {
+    Value::Object(object) => Value::Object(
+      object
+        .into_iter()
+        .collect::<BTreeMap<_, _>>()
+        .into_iter()
+        .map(|(key, value)| (key, sort_keys(value)))
+        .collect(),
+    ),
+    Value::Array(array) => Value::Array(array.into_iter().map(sort_keys).collect()),
+    value => value,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use napi::Either;
+  use serde_json::json;
+  use swc_plugin_define_dce::napi::DefineDCEVisitorConfig;
+
+  #[test]
+  fn test_options_key_is_stable() {
+    let options = |define: Vec<(&str, &str)>| TransformNodiffOptions {
+      define_dce: Either::B(DefineDCEVisitorConfig {
+        define: define
+          .into_iter()
+          .map(|(k, v)| (k.to_string(), v.to_string()))
+          .collect(),
+      }),
+      ..Default::default()
+    };
+
+    assert_eq!(
+      options_key(&options(vec![("a", "1"), ("b", "2"), ("c", "3")])),
+      options_key(&options(vec![("c", "3"), ("b", "2"), ("a", "1")])),
+    );
+    assert_ne!(
+      options_key(&options(vec![("a", "1")])),
+      options_key(&options(vec![("a", "2")])),
+    );
+    assert_eq!(
+      options_key(&TransformNodiffOptions {
+        cache_dir: Some("foo".into()),
+        timeout: Some(1000),
+        ..Default::default()
+      }),
+      options_key(&TransformNodiffOptions::default()),
+    );
+    assert_ne!(
+      options_key(&TransformNodiffOptions {
+        sourcemap: Either::A(true),
+        ..Default::default()
+      }),
+      options_key(&TransformNodiffOptions {
+        sourcemap: Either::B("true".into()),
+        ..Default::default()
+      }),
+    );
+
+    let key: Value = serde_json::from_str(&options_key(&options(vec![("a", "1")]))).unwrap();
+    assert_eq!(
+      key["define_dce"],
+      json!({ "B": { "define": { "a": "1" } } })
+    );
+    assert_eq!(key["mode"], "Production");
+    assert!(key.get("cache_dir").is_none());
+  }
+}
@@ -0,0 +1,161 @@
+use std::sync::{
+  atomic::{AtomicBool, Ordering},
+  Arc,
+};
+
+use napi::{Env, JsBoolean, JsFunction, JsObject, Ref};
+
+use crate::profile;
+
+/// Why a transform is cancelled, see `Cancellation`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Cancelled {
+  /// The `AbortSignal` is aborted.
+  Aborted,
+  /// The transform is not finished within the timeout, in milliseconds.
+  TimedOut(u32),
+}
+
+impl std::fmt::Display for Cancelled {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Cancelled::Aborted => write!(f, "AbortError: the transform is aborted"),
+      Cancelled::TimedOut(timeout) => write!(
+        f,
+        "TimeoutError: the transform is not finished within {timeout}ms"
+      ),
+    }
+  }
+}
+
+impl From<Cancelled> for napi::Error {
+  fn from(val: Cancelled) -> Self {
+    // `Status::Cancelled` becomes the `code` of the rejected error,
+    // which tells cancellations apart from other errors.
+    napi::Error::new(napi::Status::Cancelled, val.to_string())
+  }
+}
+
+/// Cancels a transform between passes, when an `AbortSignal` is aborted or the timeout expires.
+#[derive(Clone, Debug, Default)]
+pub struct Cancellation {
+  aborted: Arc<AtomicBool>,
+  /// The timeout and the deadline, see `profile::now`.
+  deadline: Option<(u32, f64)>,
+}
+
+impl Cancellation {
+  /// Start the timer of `timeout` milliseconds, which replaces the previous one.
+  pub fn with_timeout(&self, timeout: Option<u32>) -> Self {
+    Self {
+      aborted: self.aborted.clone(),
+      deadline: timeout.map(|timeout| (timeout, profile::now() + timeout as f64)),
+    }
+  }
+
+  pub fn abort(&self) {
+    self.aborted.store(true, Ordering::Relaxed);
+  }
+
+  pub fn check(&self) -> Result<(), Cancelled> {
+    if self.aborted.load(Ordering::Relaxed) {
+      return Err(Cancelled::Aborted);
+    }
+    match self.deadline {
+      Some((timeout, deadline)) if profile::now() >= deadline => Err(Cancelled::TimedOut(timeout)),
+      _ => Ok(()),
+    }
+  }
+
+  /// Abort when `signal` is aborted.
+  ///
+  /// Unlike `napi::bindgen_prelude::AbortSignal`, which only rejects the promise,
+  /// this lets the task running on the thread pool bail out.
+  ///
+  /// The returned listener should be removed once the task settles,
+  /// it is `None` if `signal` is already aborted.
+  pub fn listen(&self, env: Env, signal: JsObject) -> napi::Result<Option<AbortListener>> {
+    if signal
+      .get_named_property::<JsBoolean>("aborted")?
+      .get_value()?
+    {
+      self.abort();
+      return Ok(None);
+    }
+
+    let aborted = self.aborted.clone();
+    let listener = env
+      .create_function_from_closure("onabort", move |ctx| {
+        aborted.store(true, Ordering::Relaxed);
+        ctx.env.get_undefined()
+      })?
+      .into_unknown();
+
+    let mut options = env.create_object()?;
+    options.set_named_property("once", env.get_boolean(true)?)?;
+    let add_event_listener = signal.get_named_property::<JsFunction>("addEventListener")?;
+    add_event_listener.call(
+      Some(&signal),
+      &[
+        &env.create_string("abort")?.into_unknown(),
+        &listener,
+        &options.into_unknown(),
+      ],
+    )?;
+
+    Ok(Some(AbortListener {
+      signal: env.create_reference(signal)?,
+      listener: env.create_reference(listener)?,
+    }))
+  }
+}
+
+/// The `abort` listener added by `Cancellation::listen`.
+pub struct AbortListener {
+  signal: Ref<()>,
+  listener: Ref<()>,
+}
+
+impl AbortListener {
+  /// Remove the listener from the signal, so that neither of them is kept alive by the other.
+  pub fn remove(mut self, env: Env) -> napi::Result<()> {
+    let signal = env.get_reference_value::<JsObject>(&self.signal);
+    let listener = env.get_reference_value::<JsFunction>(&self.listener);
+    // Both references are released before any error is returned, so that neither of them leaks.
+    let unref_signal = self.signal.unref(env);
+    let unref_listener = self.listener.unref(env);
+    unref_signal?;
+    unref_listener?;
+    let (signal, listener) = (signal?, listener?);
+
+    let remove_event_listener = signal.get_named_property::<JsFunction>("removeEventListener")?;
+    remove_event_listener.call(
+      Some(&signal),
+      &[
+        env.create_string("abort")?.into_unknown(),
+        listener.into_unknown(),
+      ],
+    )?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_cancellation() {
+    let cancellation = Cancellation::default();
+    assert_eq!(cancellation.check(), Ok(()));
+    assert_eq!(cancellation.with_timeout(Some(60_000)).check(), Ok(()));
+    assert_eq!(
+      cancellation.with_timeout(Some(0)).check(),
+      Err(Cancelled::TimedOut(0))
+    );
+
+    let with_timeout = cancellation.with_timeout(Some(60_000));
+    cancellation.abort();
+    assert_eq!(with_timeout.check(), Err(Cancelled::Aborted));
+  }
+}
@@ -0,0 +1,222 @@
+use std::{collections::BTreeSet, fmt::Write};
+
+use swc_core::common::{
+  errors::{DiagnosticBuilder, DiagnosticId},
+  sync::Lrc,
+  SourceMapperDyn,
+};
+
+/// The number of lines shown before and after the annotated lines.
+const CONTEXT_LINES: usize = 2;
+
+/// A span to underline, with 0-based line indexes and char columns.
+struct Annotation {
+  start: (usize, usize),
+  end: (usize, usize),
+  is_primary: bool,
+  label: Option<String>,
+}
+
+/// Render `msg` like rustc does, with the annotated lines of the file of its primary span,
+/// `^` under the primary span, `-` under the labeled spans, and its notes and suggestions.
+pub fn render(sm: &SourceMapperDyn, msg: &DiagnosticBuilder<'_>) -> String {
+  let mut out = String::new();
+  let level = msg.level.to_str();
+  let message = msg.message();
+  match &msg.code {
+    Some(DiagnosticId::Error(id) | DiagnosticId::Lint(id)) => {
+      let _ = writeln!(out, "{level}[{id}]: {message}");
+    }
+    None => {
+      let _ = writeln!(out, "{level}: {message}");
+    }
+  }
+
+  let primary_span = msg.span.primary_span().filter(|span| !span.is_dummy());
+  let primary = primary_span.map(|span| sm.lookup_char_pos(span.lo));
+
+  let mut annotations = vec![];
+  if let Some(primary) = &primary {
+    for label in msg.span.span_labels() {
+      if label.span.is_dummy() {
+        continue;
+      }
+      let lo = sm.lookup_char_pos(label.span.lo);
+      if !Lrc::ptr_eq(&lo.file, &primary.file) {
+        continue;
+      }
+      let hi = sm.lookup_char_pos(label.span.hi);
+      let mut end = (hi.line - 1, hi.col.0);
+      // A span ending with a line break ends on the line before.
+      if end.1 == 0 && end.0 > lo.line - 1 {
+        end.0 -= 1;
+        end.1 = line_text(&primary.file, end.0).chars().count();
+      }
+      annotations.push(Annotation {
+        start: (lo.line - 1, lo.col.0),
+        end,
+        is_primary: label.is_primary,
+        label: label.label,
+      });
+    }
+  }
+
+  let last_line = primary
+    .as_ref()
+    .map_or(0, |primary| primary.file.count_lines().saturating_sub(1));
+  let lines = annotations
+    .iter()
+    .flat_map(|annotation| {
+      annotation.start.0.saturating_sub(CONTEXT_LINES)
+        ..=(annotation.end.0 + CONTEXT_LINES).min(last_line)
+    })
+    .collect::<BTreeSet<_>>();
+  let width = lines.last().map_or(1, |line| (line + 1).to_string().len());
+  let pad = " ".repeat(width);
+
+  if let Some(primary) = &primary {
+    let _ = writeln!(
+      out,
+      "{pad}--> {}:{}:{}",
+      primary.file.name,
+      primary.line,
+      primary.col.0 + 1
+    );
+    let _ = writeln!(out, "{pad} |");
+
+    let mut prev_line = None;
+    for line in lines {
+      if prev_line.is_some_and(|prev_line| line > prev_line + 1) {
+        let _ = writeln!(out, "...");
+      }
+      prev_line = Some(line);
+
+      let text = line_text(&primary.file, line);
+      let row = format!("{:>width$} | {text}", line + 1);
+      let _ = writeln!(out, "{}", row.trim_end());
+      let text_len = text.chars().count();
+      for annotation in &annotations {
+        if line < annotation.start.0 || line > annotation.end.0 {
+          continue;
+        }
+        let from = if line == annotation.start.0 {
+          annotation.start.1
+        } else {
+          0
+        };
+        let to = if line == annotation.end.0 {
+          annotation.end.1
+        } else {
+          text_len
+        };
+        let mark = if annotation.is_primary { "^" } else { "-" };
+        let _ = write!(
+          out,
+          "{pad} | {}{}",
+          " ".repeat(from),
+          mark.repeat(to.saturating_sub(from).max(1))
+        );
+        match &annotation.label {
+          Some(label) if line == annotation.end.0 => {
+            let _ = writeln!(out, " {label}");
+          }
+          _ => {
+            let _ = writeln!(out);
+          }
+        }
+      }
+    }
+  }
+
+  let children = msg
+    .children
+    .iter()
+    .map(|child| format!("{}: {}", child.level.to_str(), child.message()));
+  let suggestions = msg.suggestions.iter().flat_map(|suggestion| {
+    suggestion
+      .substitutions
+      .first()
+      .into_iter()
+      .flat_map(|substitution| &substitution.parts)
+      .map(|part| format!("help: {}: `{}`", suggestion.msg, part.snippet))
+  });
+  for note in children.chain(suggestions) {
+    let _ = writeln!(out, "{pad} = {note}");
+  }
+
+  out
+}
+
+/// The text of the 0-based `line` of `file`, without the line break.
+///
+/// Tabs are replaced with spaces, so that columns line up with the marks under them.
+fn line_text(file: &swc_core::common::SourceFile, line: usize) -> String {
+  file
+    .get_line(line)
+    .map(|text| text.trim_end_matches(['\r', '\n']).replace('\t', " "))
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use swc_core::common::{
+    errors::{Applicability, Handler},
+    BytePos, FileName, SourceMap, Span,
+  };
+
+  #[test]
+  fn test_render() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+      FileName::Real("index.js".into()).into(),
+      "let a = 1;\nlet b = 2;\nlet c = 3;\nlet d = 4;\nlet e = a;\nlet f = 6;\n".to_string(),
+    );
+    let span = |lo: u32, hi: u32| Span::new(fm.start_pos + BytePos(lo), fm.start_pos + BytePos(hi));
+
+    let (emitter, errors, warnings) =
+      crate::esbuild::EsbuildEmitter::new("test".into(), Some(cm.clone()));
+    let handler = Handler::with_emitter(true, false, Box::new(emitter.with_code_frame(true)));
+
+    handler
+      .struct_span_err_with_code(
+        span(52, 53),
+        "`a` is not allowed",
+        DiagnosticId::Error("RL0000".into()),
+      )
+      .span_label(span(4, 5), "`a` is declared here")
+      .note("some note")
+      .span_suggestion_with_applicability(
+        span(52, 53),
+        "use `e`",
+        "e".into(),
+        Applicability::MachineApplicable,
+      )
+      .emit();
+    handler.struct_warn("no span").emit();
+
+    assert_eq!(
+      errors.read().unwrap()[0].detail.as_deref(),
+      Some(
+        r#"error[RL0000]: `a` is not allowed
+ --> index.js:5:9
+  |
+1 | let a = 1;
+  |     - `a` is declared here
+2 | let b = 2;
+3 | let c = 3;
+4 | let d = 4;
+5 | let e = a;
+  |         ^
+6 | let f = 6;
+  = note: some note
+  = help: use `e`: `e`
+"#
+      )
+    );
+    assert_eq!(
+      warnings.read().unwrap()[0].detail.as_deref(),
+      Some("warning: no span\n")
+    );
+  }
+}
@@ -0,0 +1,516 @@
+use serde::Serialize;
+use swc_core::{
+  common::{
+    comments::{Comment, SingleThreadedComments},
+    errors::{DiagnosticId, Handler},
+    Span, Spanned,
+  },
+  ecma::{
+    ast::*,
+    codegen,
+    visit::{noop_visit_type, Visit, VisitWith},
+  },
+};
+use swc_plugins_shared::diagnostics;
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+pub struct EsVersionConfig(EsVersion);
+
+impl From<EsVersionConfig> for EsVersion {
+  fn from(value: EsVersionConfig) -> Self {
+    value.0
+  }
+}
+
+impl From<EsVersion> for EsVersionConfig {
+  fn from(value: EsVersion) -> Self {
+    EsVersionConfig(value)
+  }
+}
+
+impl napi::bindgen_prelude::FromNapiValue for EsVersionConfig {
+  unsafe fn from_napi_value(
+    env: napi::sys::napi_env,
+    napi_val: napi::sys::napi_value,
+  ) -> napi::Result<Self> {
+    Ok(EsVersionConfig(
+      serde_json::from_value(serde_json::Value::String(<String>::from_napi_value(
+        env, napi_val,
+      )?))
+      .map_err(|err| {
+        napi::bindgen_prelude::error!(
+          napi::bindgen_prelude::Status::InvalidArg,
+          "EsVersionConfig: {}",
+          err,
+        )
+      })?,
+    ))
+  }
+}
+
+impl napi::bindgen_prelude::ToNapiValue for EsVersionConfig {
+  unsafe fn to_napi_value(
+    env: napi::sys::napi_env,
+    val: Self,
+  ) -> napi::Result<napi::sys::napi_value> {
+    match serde_json::to_value(val.0) {
+      Ok(serde_json::Value::String(s)) => <String>::to_napi_value(env, s),
+      _ => Err(napi::bindgen_prelude::error!(
+        napi::bindgen_prelude::Status::InvalidArg,
+        "EsVersionConfig: failed to serialize {:?}",
+        val.0,
+      )),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+pub enum CommentsMode {
+  /// Keep all the comments.
+  All,
+  /// Only keep legal comments, i.e. comments that start with `!` or contain `@license` or `@preserve`.
+  License,
+  /// Remove all the comments.
+  None,
+}
+
+impl napi::bindgen_prelude::FromNapiValue for CommentsMode {
+  unsafe fn from_napi_value(
+    env: napi::bindgen_prelude::sys::napi_env,
+    napi_val: napi::bindgen_prelude::sys::napi_value,
+  ) -> napi::bindgen_prelude::Result<Self> {
+    let val = <&str>::from_napi_value(env, napi_val).map_err(|e| {
+      napi::bindgen_prelude::error!(
+        e.status,
+        "Failed to convert napi value into enum `{}`. {}",
+        "CommentsMode",
+        e,
+      )
+    })?;
+    match val {
+      "all" => Ok(CommentsMode::All),
+      "license" => Ok(CommentsMode::License),
+      "none" => Ok(CommentsMode::None),
+      _ => Err(napi::bindgen_prelude::error!(
+        napi::bindgen_prelude::Status::InvalidArg,
+        "value `{}` does not match any variant of enum `{}`",
+        val,
+        "CommentsMode"
+      )),
+    }
+  }
+}
+
+impl napi::bindgen_prelude::ToNapiValue for CommentsMode {
+  unsafe fn to_napi_value(
+    env: napi::bindgen_prelude::sys::napi_env,
+    val: Self,
+  ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+    let val = match val {
+      CommentsMode::All => "all",
+      CommentsMode::License => "license",
+      CommentsMode::None => "none",
+    };
+    <&str>::to_napi_value(env, val)
+  }
+}
+
+/// @public
+/// Options of the code generator.
+#[napi(object)]
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CodegenConfig {
+  /// @public
+  /// The ECMAScript version of the output code.
+  ///
+  /// @remarks
+  /// This only affects how the code is printed (e.g.: escapes in strings),
+  /// syntax is not lowered to the target version.
+  /// Code with syntax newer than the target, e.g. arrow functions with `'es5'`, is reported as an error.
+  /// The syntax of ES modules is not checked, since it is handled by the bundler.
+  ///
+  /// Default value: `'esnext'`.
+  #[napi(
+    ts_type = "'es3' | 'es5' | 'es2015' | 'es2016' | 'es2017' | 'es2018' | 'es2019' | 'es2020' | 'es2021' | 'es2022' | 'es2023' | 'es2024' | 'esnext'"
+  )]
+  pub target: Option<EsVersionConfig>,
+  /// @public
+  /// Whether to escape all the non-ASCII characters.
+  ///
+  /// @remarks
+  /// Default value: `false`.
+  pub ascii_only: Option<bool>,
+  /// @public
+  /// Whether to remove whitespaces in the output code.
+  ///
+  /// @remarks
+  /// Default value: `false`.
+  pub minify: Option<bool>,
+  /// @public
+  /// Which comments to keep in the output code.
+  ///
+  /// @remarks
+  /// Annotations like `@__PURE__` and magic comments like `webpackChunkName` are always kept.
+  ///
+  /// Default value: `'all'`.
+  #[napi(ts_type = "'all' | 'license' | 'none'")]
+  pub comments: Option<CommentsMode>,
+  /// @public
+  /// The `sourceRoot` field of the output source map.
+  pub source_root: Option<String>,
+}
+
+impl CodegenConfig {
+  pub fn to_codegen_config(&self) -> codegen::Config {
+    codegen::Config::default()
+      .with_target(self.target.map_or(EsVersion::latest(), Into::into))
+      .with_minify(self.minify.unwrap_or(false))
+      .with_ascii_only(self.ascii_only.unwrap_or(false))
+  }
+
+  /// Report the first syntax of `program` newer than `target`, which cannot be printed as `target`.
+  pub fn check_target(&self, program: &Program, handler: &Handler) {
+    let Some(target) = self.target.map(EsVersion::from) else {
+      return;
+    };
+    let mut checker = TargetChecker {
+      target,
+      found: None,
+    };
+    program.visit_with(&mut checker);
+    if let Some((span, syntax, version)) = checker.found {
+      handler
+        .struct_span_err_with_code(
+          span,
+          &format!(
+            "{syntax} of `{}` cannot be printed as `codegen.target` `{}`, syntax is not lowered",
+            es_version_name(version),
+            es_version_name(target)
+          ),
+          DiagnosticId::Error(diagnostics::UNSUPPORTED_CODEGEN_TARGET.into()),
+        )
+        .emit();
+    }
+  }
+
+  /// Remove the comments that should not be printed according to `comments`.
+  pub fn retain_comments(&self, comments: &SingleThreadedComments) {
+    let mode = self.comments.unwrap_or(CommentsMode::All);
+    if mode == CommentsMode::All {
+      return;
+    }
+
+    let should_keep = |c: &Comment| {
+      is_annotation_comment(c) || (mode == CommentsMode::License && is_legal_comment(c))
+    };
+    let (mut leading, mut trailing) = comments.borrow_all_mut();
+    for list in leading.values_mut().chain(trailing.values_mut()) {
+      list.retain(should_keep);
+    }
+  }
+}
+
+fn es_version_name(version: EsVersion) -> String {
+  match serde_json::to_value(version) {
+    Ok(serde_json::Value::String(name)) => name,
+    _ => format!("{version:?}"),
+  }
+}
+
+/// Finds the first syntax newer than `target`, see `CodegenConfig.check_target`.
+struct TargetChecker {
+  target: EsVersion,
+  found: Option<(Span, &'static str, EsVersion)>,
+}
+
+impl TargetChecker {
+  fn require(&mut self, span: Span, syntax: &'static str, version: EsVersion) {
+    if self.found.is_none() && version > self.target {
+      self.found = Some((span, syntax, version));
+    }
+  }
+}
+
+impl Visit for TargetChecker {
+  noop_visit_type!();
+
+  fn visit_arrow_expr(&mut self, n: &ArrowExpr) {
+    match n.is_async {
+      true => self.require(n.span, "Async functions", EsVersion::Es2017),
+      false => self.require(n.span, "Arrow functions", EsVersion::Es2015),
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_function(&mut self, n: &Function) {
+    match (n.is_async, n.is_generator) {
+      (true, true) => self.require(n.span, "Async generators", EsVersion::Es2018),
+      (true, false) => self.require(n.span, "Async functions", EsVersion::Es2017),
+      (false, true) => self.require(n.span, "Generators", EsVersion::Es2015),
+      (false, false) => {}
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_class(&mut self, n: &Class) {
+    self.require(n.span, "Classes", EsVersion::Es2015);
+    n.visit_children_with(self);
+  }
+
+  fn visit_class_member(&mut self, n: &ClassMember) {
+    match n {
+      ClassMember::ClassProp(ClassProp { span, .. })
+      | ClassMember::PrivateProp(PrivateProp { span, .. }) => {
+        self.require(*span, "Class fields", EsVersion::Es2022)
+      }
+      ClassMember::PrivateMethod(PrivateMethod { span, .. }) => {
+        self.require(*span, "Private methods", EsVersion::Es2022)
+      }
+      ClassMember::StaticBlock(StaticBlock { span, .. }) => {
+        self.require(*span, "Static blocks", EsVersion::Es2022)
+      }
+      _ => {}
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_var_decl(&mut self, n: &VarDecl) {
+    if n.kind != VarDeclKind::Var {
+      self.require(n.span, "`let` and `const`", EsVersion::Es2015);
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_tpl(&mut self, n: &Tpl) {
+    self.require(n.span, "Template literals", EsVersion::Es2015);
+    n.visit_children_with(self);
+  }
+
+  fn visit_expr_or_spread(&mut self, n: &ExprOrSpread) {
+    if let Some(spread) = n.spread {
+      self.require(spread, "Spread elements", EsVersion::Es2015);
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_prop_or_spread(&mut self, n: &PropOrSpread) {
+    if let PropOrSpread::Spread(spread) = n {
+      self.require(spread.dot3_token, "Object spread", EsVersion::Es2018);
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_prop(&mut self, n: &Prop) {
+    match n {
+      Prop::Shorthand(ident) => self.require(ident.span, "Shorthand properties", EsVersion::Es2015),
+      Prop::Method(MethodProp { key, .. }) => {
+        self.require(key.span(), "Shorthand methods", EsVersion::Es2015)
+      }
+      Prop::Getter(GetterProp { span, .. }) | Prop::Setter(SetterProp { span, .. }) => {
+        self.require(*span, "Accessors", EsVersion::Es5)
+      }
+      _ => {}
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_prop_name(&mut self, n: &PropName) {
+    if let PropName::Computed(computed) = n {
+      self.require(computed.span, "Computed property names", EsVersion::Es2015);
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_pat(&mut self, n: &Pat) {
+    match n {
+      Pat::Array(ArrayPat { span, .. }) | Pat::Object(ObjectPat { span, .. }) => {
+        self.require(*span, "Destructuring", EsVersion::Es2015)
+      }
+      Pat::Rest(RestPat { span, .. }) => self.require(*span, "Rest elements", EsVersion::Es2015),
+      Pat::Assign(AssignPat { span, .. }) => {
+        self.require(*span, "Default values", EsVersion::Es2015)
+      }
+      _ => {}
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_object_pat_prop(&mut self, n: &ObjectPatProp) {
+    if let ObjectPatProp::Rest(RestPat { span, .. }) = n {
+      self.require(*span, "Object rest", EsVersion::Es2018);
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_for_of_stmt(&mut self, n: &ForOfStmt) {
+    match n.is_await {
+      true => self.require(n.span, "`for await`", EsVersion::Es2018),
+      false => self.require(n.span, "`for...of`", EsVersion::Es2015),
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_catch_clause(&mut self, n: &CatchClause) {
+    if n.param.is_none() {
+      self.require(n.span, "Optional catch bindings", EsVersion::Es2019);
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_bin_expr(&mut self, n: &BinExpr) {
+    match n.op {
+      BinaryOp::Exp => self.require(n.span, "Exponentiation", EsVersion::Es2016),
+      BinaryOp::NullishCoalescing => self.require(n.span, "Nullish coalescing", EsVersion::Es2020),
+      _ => {}
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_assign_expr(&mut self, n: &AssignExpr) {
+    match n.op {
+      AssignOp::ExpAssign => self.require(n.span, "Exponentiation", EsVersion::Es2016),
+      AssignOp::AndAssign | AssignOp::OrAssign | AssignOp::NullishAssign => {
+        self.require(n.span, "Logical assignments", EsVersion::Es2021)
+      }
+      _ => {}
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_opt_chain_expr(&mut self, n: &OptChainExpr) {
+    self.require(n.span, "Optional chaining", EsVersion::Es2020);
+    n.visit_children_with(self);
+  }
+
+  fn visit_big_int(&mut self, n: &BigInt) {
+    self.require(n.span, "BigInt literals", EsVersion::Es2020);
+  }
+}
+
+/// Comments that affect bundling, which should never be removed.
+fn is_annotation_comment(c: &Comment) -> bool {
+  let text = c.text.trim();
+  text == "#__PURE__" || text == "@__PURE__" || text.starts_with("webpack")
+}
+
+/// Same as esbuild's legal comments.
+fn is_legal_comment(c: &Comment) -> bool {
+  c.text.starts_with('!') || c.text.contains("@license") || c.text.contains("@preserve")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use swc_core::common::{
+    comments::{CommentKind, Comments},
+    BytePos, DUMMY_SP,
+  };
+
+  #[test]
+  fn test_target_checker() {
+    use swc_core::{
+      common::{sync::Lrc, FileName, SourceMap},
+      ecma::parser::parse_file_as_program,
+    };
+
+    let found = |target, code: &str| {
+      let cm: Lrc<SourceMap> = Default::default();
+      let fm = cm.new_source_file(FileName::Anon.into(), code.to_string());
+      let program = parse_file_as_program(
+        &fm,
+        Default::default(),
+        Default::default(),
+        None,
+        &mut vec![],
+      )
+      .unwrap();
+      let mut checker = TargetChecker {
+        target,
+        found: None,
+      };
+      program.visit_with(&mut checker);
+      checker.found.map(|(_, syntax, version)| (syntax, version))
+    };
+
+    assert_eq!(found(EsVersion::Es5, "var a = { b: 1 };"), None);
+    assert_eq!(
+      found(EsVersion::Es5, "var a = () => 1;"),
+      Some(("Arrow functions", EsVersion::Es2015))
+    );
+    assert_eq!(
+      found(
+        EsVersion::Es2015,
+        "import a from 'a';\nexport const b = { ...a };"
+      ),
+      Some(("Object spread", EsVersion::Es2018))
+    );
+    assert_eq!(
+      found(EsVersion::Es2019, "a?.b ?? c;"),
+      Some(("Nullish coalescing", EsVersion::Es2020))
+    );
+    assert_eq!(
+      found(EsVersion::Es2020, "a?.b ?? c;\na ||= b;"),
+      Some(("Logical assignments", EsVersion::Es2021))
+    );
+    assert_eq!(
+      found(EsVersion::Es2021, "class A { #a = 1; }"),
+      Some(("Class fields", EsVersion::Es2022))
+    );
+    assert_eq!(found(EsVersion::Es2022, "class A { #a = 1; }"), None);
+  }
+
+  #[test]
+  fn test_retain_comments() {
+    let comment = |text: &str| Comment {
+      kind: CommentKind::Block,
+      span: DUMMY_SP,
+      text: text.into(),
+    };
+    let comments = || {
+      let comments = SingleThreadedComments::default();
+      comments.add_leading(BytePos(1), comment("! MIT"));
+      comments.add_leading(BytePos(1), comment(" some comment "));
+      comments.add_leading(BytePos(1), comment("#__PURE__"));
+      comments.add_trailing(BytePos(1), comment("webpackChunkName: \"foo\""));
+      comments.add_trailing(BytePos(1), comment(" @license MIT "));
+      comments
+    };
+    let texts = |comments: &SingleThreadedComments| {
+      let (leading, trailing) = comments.borrow_all();
+      leading
+        .values()
+        .chain(trailing.values())
+        .flatten()
+        .map(|c| c.text.to_string())
+        .collect::<Vec<_>>()
+    };
+
+    let c = comments();
+    CodegenConfig::default().retain_comments(&c);
+    assert_eq!(texts(&c).len(), 5);
+
+    let c = comments();
+    CodegenConfig {
+      comments: Some(CommentsMode::License),
+      ..Default::default()
+    }
+    .retain_comments(&c);
+    assert_eq!(
+      texts(&c),
+      vec![
+        "! MIT",
+        "#__PURE__",
+        "webpackChunkName: \"foo\"",
+        " @license MIT "
+      ]
+    );
+
+    let c = comments();
+    CodegenConfig {
+      comments: Some(CommentsMode::None),
+      ..Default::default()
+    }
+    .retain_comments(&c);
+    assert_eq!(texts(&c), vec!["#__PURE__", "webpackChunkName: \"foo\""]);
+  }
+}
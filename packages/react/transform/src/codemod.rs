@@ -0,0 +1,117 @@
+use std::sync::RwLock;
+
+use swc_core::common::{
+  errors::{Applicability, DiagnosticBuilder, Emitter},
+  sync::Lrc,
+  BytePos, Span,
+};
+
+/// A machine-applicable replacement of `span` with `snippet`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fix {
+  pub span: Span,
+  pub snippet: String,
+}
+
+type LRVFix = Lrc<RwLock<Vec<Fix>>>;
+
+/// Collects the machine-applicable suggestions of diagnostics, see `TransformNodiffOptions.codemod`.
+pub struct FixEmitter {
+  fixes: LRVFix,
+}
+
+impl FixEmitter {
+  pub fn new() -> (Self, LRVFix) {
+    let fixes = Lrc::new(RwLock::new(vec![]));
+    (
+      Self {
+        fixes: fixes.clone(),
+      },
+      fixes,
+    )
+  }
+}
+
+impl Emitter for FixEmitter {
+  fn emit(&mut self, msg: &mut DiagnosticBuilder<'_>) {
+    let mut fixes = self.fixes.write().unwrap();
+    for suggestion in &msg.suggestions {
+      if suggestion.applicability != Applicability::MachineApplicable {
+        continue;
+      }
+      if let Some(substitution) = suggestion.substitutions.first() {
+        fixes.extend(substitution.parts.iter().map(|part| Fix {
+          span: part.span,
+          snippet: part.snippet.clone(),
+        }));
+      }
+    }
+  }
+}
+
+/// Apply `fixes` to `source`, whose first byte is at `start_pos`.
+///
+/// Fixes are applied in the order of their positions. A fix overlapping a previous one,
+/// or out of `source`, is skipped.
+pub fn apply_fixes(source: &str, start_pos: BytePos, fixes: &[Fix]) -> String {
+  let mut fixes = fixes
+    .iter()
+    .filter(|fix| !fix.span.is_dummy() && fix.span.lo >= start_pos)
+    .map(|fix| {
+      (
+        (fix.span.lo - start_pos).0 as usize,
+        (fix.span.hi - start_pos).0 as usize,
+        fix.snippet.as_str(),
+      )
+    })
+    .collect::<Vec<_>>();
+  fixes.sort_by_key(|(lo, hi, _)| (*lo, *hi));
+
+  let mut output = String::with_capacity(source.len());
+  let mut pos = 0;
+  for (lo, hi, snippet) in fixes {
+    if lo < pos || hi > source.len() || !source.is_char_boundary(lo) || !source.is_char_boundary(hi)
+    {
+      continue;
+    }
+    output.push_str(&source[pos..lo]);
+    output.push_str(snippet);
+    pos = hi;
+  }
+  output.push_str(&source[pos..]);
+  output
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_apply_fixes() {
+    let start_pos = BytePos(1);
+    let fix = |lo: u32, hi: u32, snippet: &str| Fix {
+      span: Span::new(start_pos + BytePos(lo), start_pos + BytePos(hi)),
+      snippet: snippet.into(),
+    };
+    let source = "let a = b;";
+
+    assert_eq!(apply_fixes(source, start_pos, &[]), source);
+    assert_eq!(
+      apply_fixes(
+        source,
+        start_pos,
+        &[fix(8, 9, "c"), fix(0, 3, "const"), fix(4, 4, "_")]
+      ),
+      "const _a = c;"
+    );
+    // overlapping and out of range
+    assert_eq!(
+      apply_fixes(
+        source,
+        start_pos,
+        &[fix(0, 5, "x"), fix(4, 9, "y"), fix(9, 20, "z")]
+      ),
+      "x = b;"
+    );
+  }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use swc_core::common::errors::{DiagnosticBuilder, DiagnosticId, Level};
+
+/// The severity of a diagnostic code, see `TransformNodiffOptions.diagnostics`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum DiagnosticSeverity {
+  Off,
+  Warn,
+  Error,
+}
+
+impl napi::bindgen_prelude::FromNapiValue for DiagnosticSeverity {
+  unsafe fn from_napi_value(
+    env: napi::bindgen_prelude::sys::napi_env,
+    napi_val: napi::bindgen_prelude::sys::napi_value,
+  ) -> napi::bindgen_prelude::Result<Self> {
+    let val = <&str>::from_napi_value(env, napi_val).map_err(|e| {
+      napi::bindgen_prelude::error!(
+        e.status,
+        "Failed to convert napi value into enum `{}`. {}",
+        "DiagnosticSeverity",
+        e,
+      )
+    })?;
+    match val {
+      "off" => Ok(DiagnosticSeverity::Off),
+      "warn" => Ok(DiagnosticSeverity::Warn),
+      "error" => Ok(DiagnosticSeverity::Error),
+      _ => Err(napi::bindgen_prelude::error!(
+        napi::bindgen_prelude::Status::InvalidArg,
+        "value `{}` does not match any variant of enum `{}`",
+        val,
+        "DiagnosticSeverity"
+      )),
+    }
+  }
+}
+
+impl napi::bindgen_prelude::ToNapiValue for DiagnosticSeverity {
+  unsafe fn to_napi_value(
+    env: napi::bindgen_prelude::sys::napi_env,
+    val: Self,
+  ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+    match val {
+      DiagnosticSeverity::Off => <&str>::to_napi_value(env, "off"),
+      DiagnosticSeverity::Warn => <&str>::to_napi_value(env, "warn"),
+      DiagnosticSeverity::Error => <&str>::to_napi_value(env, "error"),
+    }
+  }
+}
+
+/// Change the level of `db` according to the severity configured for its code.
+///
+/// Returns `false` if the diagnostic is turned off and should not be emitted.
+pub fn apply_severity(
+  severities: &HashMap<String, DiagnosticSeverity>,
+  db: &mut DiagnosticBuilder<'_>,
+) -> bool {
+  let Some(severity) = db.code.as_ref().and_then(|code| match code {
+    DiagnosticId::Error(id) | DiagnosticId::Lint(id) => severities.get(id),
+  }) else {
+    return true;
+  };
+
+  match severity {
+    DiagnosticSeverity::Off => {
+      // Cancelled diagnostics are neither reported nor counted as errors.
+      db.level = Level::Cancelled;
+      false
+    }
+    DiagnosticSeverity::Warn => {
+      db.level = Level::Warning;
+      true
+    }
+    DiagnosticSeverity::Error => {
+      db.level = Level::Error;
+      true
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use swc_core::common::errors::Handler;
+
+  #[test]
+  fn test_apply_severity() {
+    let handler = Handler::with_emitter_writer(Box::new(std::io::sink()), None);
+    let severities = HashMap::from([
+      ("RL0001".to_string(), DiagnosticSeverity::Off),
+      ("RL0002".to_string(), DiagnosticSeverity::Warn),
+      ("RL0003".to_string(), DiagnosticSeverity::Error),
+    ]);
+    let apply = |code: Option<&str>, level| {
+      let mut db = DiagnosticBuilder::new(&handler, level, "message");
+      if let Some(code) = code {
+        db.code(DiagnosticId::Lint(code.into()));
+      }
+      let emit = apply_severity(&severities, &mut db);
+      let level = db.level;
+      db.cancel();
+      (emit, level)
+    };
+
+    assert_eq!(apply(None, Level::Warning), (true, Level::Warning));
+    assert_eq!(apply(Some("RL0000"), Level::Error), (true, Level::Error));
+    assert_eq!(
+      apply(Some("RL0001"), Level::Warning),
+      (false, Level::Cancelled)
+    );
+    assert_eq!(apply(Some("RL0002"), Level::Error), (true, Level::Warning));
+    assert_eq!(apply(Some("RL0003"), Level::Warning), (true, Level::Error));
+  }
+}
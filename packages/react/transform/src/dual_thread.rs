@@ -0,0 +1,582 @@
+use std::{cell::RefCell, rc::Rc};
+
+use napi::Either;
+use swc_core::{
+  base::Compiler,
+  common::{
+    comments::SingleThreadedComments,
+    errors::{Emitter, Handler},
+    sync::Lrc,
+    FileName, FilePathMapping, Mark, SourceMap, DUMMY_SP, GLOBALS,
+  },
+};
+use swc_plugin_compat::napi::CompatVisitorConfig;
+use swc_plugin_dynamic_import::napi::DynamicImportVisitorConfig;
+use swc_plugins_shared::{
+  target_napi::TransformTarget, transform_mode_napi::TransformMode, utils::calc_hash,
+};
+
+use crate::{
+  cancellation::{Cancellation, Cancelled},
+  esbuild::EsbuildEmitter,
+  pipeline::TransformPass,
+  print_program, recovery, run_passes, MultiEmitter, PassContext, PassStage,
+  TransformNodiffOptions, TransformNodiffOutput,
+};
+
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct TransformDualThreadOptions {
+  /// Options of the main thread (`LEPUS`) output.
+  pub main_thread: TransformNodiffOptions,
+  /// Options of the background (`JS`) output.
+  pub background: TransformNodiffOptions,
+  /// Cancel the transform of both threads if it is not finished within this many milliseconds.
+  /// `timeout` of `mainThread` and `background` is not supported.
+  pub timeout: Option<u32>,
+}
+
+#[napi(object)]
+pub struct TransformDualThreadOutput {
+  pub main_thread: TransformNodiffOutput,
+  pub background: TransformNodiffOutput,
+}
+
+/// The `dynamicImport` config, or `None` if the pass is disabled.
+fn dynamic_import_config(options: &TransformNodiffOptions) -> Option<DynamicImportVisitorConfig> {
+  match options.dynamic_import.clone().unwrap_or(Either::A(true)) {
+    Either::A(true) => Some(Default::default()),
+    Either::A(false) => None,
+    Either::B(config) => Some(config),
+  }
+}
+
+/// A string representation of the options `pass` depends on, besides the ones
+/// `validate_dual_thread_options` requires to be the same for both threads.
+fn pass_options_key(pass: TransformPass, options: &TransformNodiffOptions) -> String {
+  match pass {
+    TransformPass::Typescript | TransformPass::Simplify | TransformPass::Resolver => {
+      Default::default()
+    }
+    // `layer` only goes into `webpackChunkName` comments, which are renamed
+    // after forking, see `rename_chunk_layer`.
+    TransformPass::DynamicImport => format!(
+      "{:?}",
+      dynamic_import_config(options).map(|config| config.runtime_pkg)
+    ),
+    TransformPass::Refresh => format!("{:?}", options.refresh),
+    // `compat` only depends on `target` for the rewrites limited to `LEPUS`.
+    TransformPass::Compat | TransformPass::CompatPost => match &options.compat {
+      Either::B(config)
+        if config.target != TransformTarget::LEPUS
+          || (config.remove_component_attr_regex.is_none()
+            && !config.simplify_ctor_like_react_lynx_2) =>
+      {
+        format!(
+          "{:?}",
+          CompatVisitorConfig {
+            target: TransformTarget::JS,
+            ..config.clone()
+          }
+        )
+      }
+      compat => format!("{compat:?}"),
+    },
+    TransformPass::Worklet => format!("{:?}", options.worklet),
+    TransformPass::CssScope => format!("{:?}", options.css_scope),
+    // `list` only depends on whether `snapshot` is enabled, not on its target.
+    TransformPass::List => format!("{:?}", !matches!(options.snapshot, Some(Either::A(false)))),
+    TransformPass::Snapshot | TransformPass::React => format!("{:?}", options.snapshot),
+    TransformPass::DirectiveDCE => format!("{:?}", options.directive_dce),
+    TransformPass::DefineDCE => format!("{:?}", options.define_dce),
+    TransformPass::Shake => format!("{:?}", options.shake),
+    TransformPass::Inject => format!("{:?}", options.inject),
+  }
+}
+
+/// Rename the `webpackChunkName` comments added by a shared `dynamicImport` pass
+/// from the `layer` of the main thread to the `layer` of `options`.
+fn rename_chunk_layer(
+  comments: &SingleThreadedComments,
+  main_thread: &TransformNodiffOptions,
+  options: &TransformNodiffOptions,
+) {
+  let (Some(from), Some(to)) = (
+    dynamic_import_config(main_thread).map(|config| config.layer),
+    dynamic_import_config(options).map(|config| config.layer),
+  ) else {
+    return;
+  };
+  if from == to {
+    return;
+  }
+  let from = format!("-{from}\"");
+  let (mut leading, _) = comments.borrow_all_mut();
+  for comment in leading.values_mut().flatten() {
+    // Comments written by users have real spans.
+    if comment.span != DUMMY_SP || !comment.text.starts_with("webpackChunkName: \"") {
+      continue;
+    }
+    if let Some(name) = comment.text.strip_suffix(&from) {
+      comment.text = format!("{name}-{to}\"").into();
+    }
+  }
+}
+
+/// Split the pipelines of both threads into the passes that run once before forking,
+/// and the passes that run for each thread after forking.
+///
+/// The longest common prefix of both pipelines, whose passes are configured the same, is shared.
+/// Every pass still runs in its own position, so the outputs are the same as two separate
+/// transforms. How much is saved depends on the options: with the default pipeline and the
+/// options of the webpack loader, the threads fork at `worklet`, or at `snapshot` if `worklet`
+/// is disabled, whose `target` differs.
+fn split_pipelines(
+  main_thread: &[TransformPass],
+  background: &[TransformPass],
+  main_thread_options: &TransformNodiffOptions,
+  background_options: &TransformNodiffOptions,
+) -> (Vec<TransformPass>, Vec<TransformPass>, Vec<TransformPass>) {
+  let shared_len = main_thread
+    .iter()
+    .zip(background)
+    .take_while(|(main_thread_pass, background_pass)| {
+      main_thread_pass == background_pass
+        && pass_options_key(**main_thread_pass, main_thread_options)
+          == pass_options_key(**main_thread_pass, background_options)
+    })
+    .count();
+  (
+    main_thread[..shared_len].to_vec(),
+    main_thread[shared_len..].to_vec(),
+    background[shared_len..].to_vec(),
+  )
+}
+
+/// Both threads must be parsed the same way to share the AST.
+///
+/// `codemod`, `cacheDir` and `timeout` of either thread are rejected rather than ignored,
+/// see `TransformDualThreadOptions.timeout`.
+pub fn validate_dual_thread_options(options: &TransformDualThreadOptions) -> Result<(), String> {
+  let TransformDualThreadOptions {
+    main_thread,
+    background,
+    timeout: _,
+  } = options;
+  for (name, options) in [("mainThread", main_thread), ("background", background)] {
+    if options.codemod == Some(true) {
+      return Err(format!("`{name}.codemod` is not supported"));
+    }
+    if options.cache_dir.is_some() {
+      return Err(format!("`{name}.cacheDir` is not supported"));
+    }
+    if options.timeout.is_some() {
+      return Err(format!(
+        "`{name}.timeout` is not supported, use `timeout` instead"
+      ));
+    }
+  }
+  let parse_options = |options: &TransformNodiffOptions| {
+    format!(
+      "{:?}{:?}{:?}{:?}{:?}",
+      options.filename, options.mode, options.syntax_config, options.is_module, options.recover
+    )
+  };
+  if parse_options(main_thread) != parse_options(background) {
+    return Err(
+      "`filename`, `mode`, `syntaxConfig`, `isModule` and `recover` must be the same for `mainThread` and `background`"
+        .into(),
+    );
+  }
+  Ok(())
+}
+
+fn clone_comments(comments: &SingleThreadedComments) -> SingleThreadedComments {
+  let (leading, trailing) = comments.borrow_all();
+  SingleThreadedComments::from_leading_and_trailing(
+    Rc::new(RefCell::new(leading.clone())),
+    Rc::new(RefCell::new(trailing.clone())),
+  )
+}
+
+/// Transform `code` for both the main thread and the background with a single parse.
+///
+/// The passes shared by both threads (see `split_pipelines`) run once, then the AST
+/// is forked for the rest of the passes. Diagnostics of the shared passes are reported
+/// in both outputs.
+pub fn transform_react_lynx_dual_thread_inner(
+  code: String,
+  options: TransformDualThreadOptions,
+  cancellation: &Cancellation,
+) -> Result<TransformDualThreadOutput, Cancelled> {
+  let TransformDualThreadOptions {
+    main_thread,
+    background,
+    timeout,
+  } = options;
+  let cancellation = cancellation.with_timeout(timeout);
+
+  let content_hash = match main_thread.mode {
+    Some(TransformMode::Test) => "test".into(),
+    _ => calc_hash(code.as_str()),
+  };
+  let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+  let fm = cm.new_source_file(
+    FileName::Real(main_thread.filename.clone().into()).into(),
+    code,
+  );
+  let c = Compiler::new(cm.clone());
+
+  let new_handler = |options: &TransformNodiffOptions| {
+    let (esbuild_emitter, errors, warnings) =
+      EsbuildEmitter::new(options.plugin_name.clone(), Some(c.cm.clone()));
+    let esbuild_emitter = esbuild_emitter.with_code_frame(options.code_frame == Some(true));
+    let emitters: Vec<Box<dyn Emitter>> = vec![Box::new(esbuild_emitter)];
+    let emitter =
+      Box::new(MultiEmitter::new(emitters).with_severities(options.diagnostics.clone()));
+    (
+      Handler::with_emitter(true, false, emitter),
+      errors,
+      warnings,
+    )
+  };
+  let (handler, errors, warnings) = new_handler(&main_thread);
+
+  GLOBALS.set(&Default::default(), || {
+    let comments = SingleThreadedComments::default();
+    let program = recovery::parse(&c, fm, &handler, &main_thread, &comments);
+    let Some(program) = program else {
+      let output = || TransformNodiffOutput {
+        code: match main_thread.recover {
+          Some(true) => recovery::stub_module(&errors.read().unwrap()),
+          _ => "".into(),
+        },
+        map: None,
+        errors: errors.read().unwrap().clone(),
+        warnings: warnings.read().unwrap().clone(),
+        profile: None,
+        metadata: Default::default(),
+        codemod: None,
+      };
+      return Ok(TransformDualThreadOutput {
+        main_thread: output(),
+        background: output(),
+      });
+    };
+
+    let (shared_pipeline, main_thread_pipeline, background_pipeline) = split_pipelines(
+      &main_thread.resolved_pipeline(),
+      &background.resolved_pipeline(),
+      &main_thread,
+      &background,
+    );
+    let unresolved_mark = Mark::new();
+    let top_level_mark = Mark::new();
+
+    let diagnostics_count =
+      || (errors.read().unwrap().len() + warnings.read().unwrap().len()) as u32;
+    let (program, shared_profile, shared_metadata) = run_passes(
+      &PassContext {
+        cm: &cm,
+        comments: &comments,
+        handler: &handler,
+        options: &main_thread,
+        content_hash: &content_hash,
+        diagnostics_count: &diagnostics_count,
+        unresolved_mark,
+        top_level_mark,
+        cancellation: &cancellation,
+      },
+      program,
+      &shared_pipeline,
+      PassStage::Head,
+    )?;
+
+    let fork = |options: &TransformNodiffOptions, pipeline: &[TransformPass]| {
+      let program = program.clone();
+      let comments = clone_comments(&comments);
+      if shared_pipeline.contains(&TransformPass::DynamicImport) {
+        rename_chunk_layer(&comments, &main_thread, options);
+      }
+      let (handler, thread_errors, thread_warnings) = new_handler(options);
+      let diagnostics_count =
+        || (thread_errors.read().unwrap().len() + thread_warnings.read().unwrap().len()) as u32;
+
+      let (program, profile, metadata) = run_passes(
+        &PassContext {
+          cm: &cm,
+          comments: &comments,
+          handler: &handler,
+          options,
+          content_hash: &content_hash,
+          diagnostics_count: &diagnostics_count,
+          unresolved_mark,
+          top_level_mark,
+          cancellation: &cancellation,
+        },
+        program,
+        pipeline,
+        PassStage::Tail,
+      )?;
+      let result = print_program(&c, &program, &comments, &handler, options);
+
+      let mut metadata_with_shared = shared_metadata.clone();
+      metadata_with_shared.extend(metadata);
+      let (code, map) = match result {
+        Some(result) => (result.code, result.map),
+        None => ("".into(), None),
+      };
+
+      let errors = [
+        errors.read().unwrap().clone(),
+        thread_errors.read().unwrap().clone(),
+      ]
+      .concat();
+      let warnings = [
+        warnings.read().unwrap().clone(),
+        thread_warnings.read().unwrap().clone(),
+      ]
+      .concat();
+      Ok(TransformNodiffOutput {
+        code,
+        map,
+        errors,
+        warnings,
+        profile: profile
+          .map(|profile| [shared_profile.clone().unwrap_or_default(), profile].concat()),
+        metadata: metadata_with_shared,
+        codemod: None,
+      })
+    };
+
+    Ok(TransformDualThreadOutput {
+      main_thread: fork(&main_thread, &main_thread_pipeline)?,
+      background: fork(&background, &background_pipeline)?,
+    })
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use swc_plugin_css_scope::napi::{CSSScope, CSSScopeVisitorConfig};
+  use swc_plugin_snapshot::napi::JSXTransformerConfig;
+  use swc_plugin_worklet::napi::WorkletVisitorConfig;
+
+  fn options(target: TransformTarget, layer: &str) -> TransformNodiffOptions {
+    TransformNodiffOptions {
+      mode: Some(TransformMode::Test),
+      filename: "index.jsx".into(),
+      snapshot: Some(Either::B(JSXTransformerConfig {
+        target,
+        filename: "index.jsx".into(),
+        ..Default::default()
+      })),
+      dynamic_import: Some(Either::B(DynamicImportVisitorConfig {
+        layer: layer.into(),
+        ..Default::default()
+      })),
+      ..Default::default()
+    }
+  }
+
+  fn worklet_options(target: TransformTarget) -> TransformNodiffOptions {
+    TransformNodiffOptions {
+      worklet: Either::B(WorkletVisitorConfig {
+        target,
+        filename: "index.jsx".into(),
+        ..Default::default()
+      }),
+      ..options(target, "main-thread")
+    }
+  }
+
+  #[test]
+  fn test_split_pipelines() {
+    let split = |main_thread: &TransformNodiffOptions, background: &TransformNodiffOptions| {
+      split_pipelines(
+        &main_thread.resolved_pipeline(),
+        &background.resolved_pipeline(),
+        main_thread,
+        background,
+      )
+    };
+
+    // everything before `snapshot`, the first pass depending on the target, is shared
+    let (shared, main_thread_rest, background_rest) = split(
+      &options(TransformTarget::LEPUS, "main-thread"),
+      &options(TransformTarget::JS, "main-thread"),
+    );
+    assert_eq!(
+      shared,
+      vec![
+        TransformPass::Typescript,
+        TransformPass::DynamicImport,
+        TransformPass::Refresh,
+        TransformPass::Compat,
+        TransformPass::Worklet,
+        TransformPass::CssScope,
+        TransformPass::List,
+      ]
+    );
+    assert_eq!(main_thread_rest[0], TransformPass::Snapshot);
+    assert_eq!(main_thread_rest, background_rest);
+
+    // `cssScope` is not hoisted over `worklet`
+    let (shared, main_thread_rest, background_rest) = split(
+      &worklet_options(TransformTarget::LEPUS),
+      &worklet_options(TransformTarget::JS),
+    );
+    assert_eq!(
+      shared,
+      vec![
+        TransformPass::Typescript,
+        TransformPass::DynamicImport,
+        TransformPass::Refresh,
+        TransformPass::Compat,
+      ]
+    );
+    assert_eq!(main_thread_rest[0], TransformPass::Worklet);
+    assert_eq!(main_thread_rest, background_rest);
+
+    // `layer` of `dynamicImport` and `target` of `compat`, which differ between the threads
+    // of the webpack loader, do not stop sharing
+    let compat = |target| {
+      Either::B(CompatVisitorConfig {
+        target,
+        ..Default::default()
+      })
+    };
+    let (shared, main_thread_rest, background_rest) = split(
+      &TransformNodiffOptions {
+        compat: compat(TransformTarget::LEPUS),
+        ..worklet_options(TransformTarget::LEPUS)
+      },
+      &TransformNodiffOptions {
+        compat: compat(TransformTarget::JS),
+        dynamic_import: Some(Either::B(DynamicImportVisitorConfig {
+          layer: "background".into(),
+          ..Default::default()
+        })),
+        ..worklet_options(TransformTarget::JS)
+      },
+    );
+    assert_eq!(
+      shared,
+      vec![
+        TransformPass::Typescript,
+        TransformPass::DynamicImport,
+        TransformPass::Refresh,
+        TransformPass::Compat,
+      ]
+    );
+    assert_eq!(main_thread_rest[0], TransformPass::Worklet);
+    assert_eq!(main_thread_rest, background_rest);
+
+    // unless `compat` rewrites the main thread only
+    let (shared, _, _) = split(
+      &TransformNodiffOptions {
+        compat: Either::B(CompatVisitorConfig {
+          remove_component_attr_regex: Some("^on".into()),
+          ..Default::default()
+        }),
+        ..options(TransformTarget::LEPUS, "main-thread")
+      },
+      &TransformNodiffOptions {
+        compat: compat(TransformTarget::JS),
+        ..options(TransformTarget::JS, "background")
+      },
+    );
+    assert_eq!(
+      shared,
+      vec![
+        TransformPass::Typescript,
+        TransformPass::DynamicImport,
+        TransformPass::Refresh,
+      ]
+    );
+
+    // custom pipelines are never reordered
+    let (shared, main_thread_rest, background_rest) = split(
+      &custom_pipeline_options(TransformTarget::LEPUS, MAIN_THREAD_PIPELINE),
+      &custom_pipeline_options(TransformTarget::JS, BACKGROUND_PIPELINE),
+    );
+    assert!(shared.is_empty());
+    assert_eq!(main_thread_rest, MAIN_THREAD_PIPELINE);
+    assert_eq!(background_rest, BACKGROUND_PIPELINE);
+  }
+
+  const MAIN_THREAD_PIPELINE: &[TransformPass] = &[
+    TransformPass::CssScope,
+    TransformPass::Typescript,
+    TransformPass::DynamicImport,
+    TransformPass::Snapshot,
+    TransformPass::Shake,
+    TransformPass::React,
+  ];
+  const BACKGROUND_PIPELINE: &[TransformPass] = &[
+    TransformPass::Typescript,
+    TransformPass::CssScope,
+    TransformPass::DynamicImport,
+    TransformPass::Snapshot,
+    TransformPass::Shake,
+    TransformPass::React,
+  ];
+
+  fn custom_pipeline_options(
+    target: TransformTarget,
+    pipeline: &[TransformPass],
+  ) -> TransformNodiffOptions {
+    TransformNodiffOptions {
+      pipeline: Some(pipeline.to_vec()),
+      css_scope: Either::B(CSSScopeVisitorConfig {
+        mode: CSSScope::Modules,
+        filename: "index.jsx".into(),
+      }),
+      ..options(target, "main-thread")
+    }
+  }
+
+  #[test]
+  fn test_validate_dual_thread_options() {
+    let options = || TransformNodiffOptions {
+      mode: Some(TransformMode::Test),
+      ..Default::default()
+    };
+    let validate = |background: TransformNodiffOptions| {
+      validate_dual_thread_options(&TransformDualThreadOptions {
+        main_thread: options(),
+        background,
+        timeout: Some(1000),
+      })
+    };
+
+    assert_eq!(validate(options()), Ok(()));
+    assert!(validate(TransformNodiffOptions {
+      filename: "other.js".into(),
+      ..options()
+    })
+    .is_err());
+    assert_eq!(
+      validate(TransformNodiffOptions {
+        codemod: Some(true),
+        ..options()
+      }),
+      Err("`background.codemod` is not supported".into())
+    );
+    assert_eq!(
+      validate(TransformNodiffOptions {
+        cache_dir: Some("node_modules/.cache".into()),
+        ..options()
+      }),
+      Err("`background.cacheDir` is not supported".into())
+    );
+    assert_eq!(
+      validate(TransformNodiffOptions {
+        timeout: Some(1000),
+        ..options()
+      }),
+      Err("`background.timeout` is not supported, use `timeout` instead".into())
+    );
+  }
+}
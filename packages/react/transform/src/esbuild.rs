@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
 use std::sync::RwLock;
+
+use crate::code_frame;
 use swc_core::common::{
   errors::{DiagnosticBuilder, DiagnosticId, Emitter, Level},
   sync::Lrc,
-  FileLines, SourceMapperDyn,
+  FileLines, SourceMapperDyn, Span,
 };
 
 /// This is esbuild's PartialMessage definition.
@@ -41,7 +43,46 @@ pub struct PartialLocation {
   pub suggestion: Option<String>,
 }
 
-type LRVPartialMessage = Lrc<RwLock<Vec<PartialMessage>>>;
+/// Convert `span` to an esbuild location, including the text of its first line.
+pub fn span_to_location(sm: &SourceMapperDyn, span: Span) -> PartialLocation {
+  let loc = sm.lookup_char_pos(span.lo());
+  let filename = sm.span_to_filename(span);
+
+  let mut location = PartialLocation {
+    file: Some(filename.to_string()),
+    namespace: None,
+    line: Some(loc.line as u32),
+    column: Some(loc.col.0 as u32),
+    length: Some(span.hi().0 - span.lo().0),
+    line_text: None,
+    suggestion: None,
+  };
+
+  if let Ok(FileLines { lines, file }) = sm.span_to_lines(span) {
+    lines.iter().for_each(|line| {
+      if line.line_index + 1 == loc.line {
+        if let Some(line_text) = file.get_line(line.line_index) {
+          location.line_text = Some(line_text.to_string());
+        }
+      }
+    });
+  }
+
+  location
+}
+
+/// Whether a diagnostic of `level` is reported as an error or a warning,
+/// or `None` if it is not reported at all.
+pub fn is_error(level: Level) -> Option<bool> {
+  match level {
+    Level::Bug | Level::Fatal | Level::PhaseFatal | Level::Error => Some(true),
+    // esbuild only has errors and warnings
+    Level::Warning | Level::Note | Level::Help | Level::FailureNote => Some(false),
+    Level::Cancelled => None,
+  }
+}
+
+pub type LRVPartialMessage = Lrc<RwLock<Vec<PartialMessage>>>;
 
 pub struct EsbuildEmitter {
   pub errors: LRVPartialMessage,
@@ -49,6 +90,9 @@ pub struct EsbuildEmitter {
 
   plugin_name: String,
   source_map: Option<Lrc<SourceMapperDyn>>,
+  /// Whether to render the code frame of messages into their `detail`,
+  /// see `TransformNodiffOptions.codeFrame`.
+  code_frame: bool,
 }
 
 impl EsbuildEmitter {
@@ -66,15 +110,90 @@ impl EsbuildEmitter {
 
         plugin_name,
         source_map,
+        code_frame: false,
       },
       errors,
       warnings,
     )
   }
+
+  pub fn with_code_frame(self, code_frame: bool) -> Self {
+    Self { code_frame, ..self }
+  }
+
+  fn location(&self, span: Span) -> Option<PartialLocation> {
+    match &self.source_map {
+      Some(sm) if !span.is_dummy() => Some(span_to_location(&**sm, span)),
+      _ => None,
+    }
+  }
+
+  /// Labeled spans and sub-diagnostics (notes and help) of `msg`.
+  fn notes(&self, msg: &DiagnosticBuilder<'_>) -> Option<Vec<PartialNote>> {
+    let labels = msg.span.span_labels().into_iter().filter_map(|label| {
+      Some(PartialNote {
+        text: Some(label.label?),
+        location: self.location(label.span),
+      })
+    });
+    let children = msg
+      .children
+      .iter()
+      .filter(|child| child.level != Level::Cancelled)
+      .map(|child| PartialNote {
+        text: Some(child.message()),
+        location: child
+          .span
+          .primary_span()
+          .and_then(|span| self.location(span)),
+      });
+
+    let primary_span = msg.span.primary_span();
+    // Suggestions replacing the primary span are put on the `location` instead, see `emit`.
+    // Removals cannot be told apart from replacing with an empty string in esbuild,
+    // so they are notes on the removed span without a `suggestion`.
+    let suggestions = msg.suggestions.iter().flat_map(move |suggestion| {
+      suggestion
+        .substitutions
+        .first()
+        .into_iter()
+        .flat_map(|substitution| &substitution.parts)
+        .filter(move |part| part.snippet.is_empty() || Some(part.span) != primary_span)
+        .map(move |part| PartialNote {
+          text: Some(suggestion.msg.clone()),
+          location: self.location(part.span).map(|location| PartialLocation {
+            suggestion: (!part.snippet.is_empty()).then(|| part.snippet.clone()),
+            ..location
+          }),
+        })
+    });
+
+    let notes = labels
+      .chain(children)
+      .chain(suggestions)
+      .collect::<Vec<_>>();
+    (!notes.is_empty()).then_some(notes)
+  }
+
+  /// The replacement of the primary span suggested by `msg`, if any.
+  fn suggestion(msg: &DiagnosticBuilder<'_>) -> Option<String> {
+    let primary_span = msg.span.primary_span()?;
+    msg
+      .suggestions
+      .iter()
+      .filter_map(|suggestion| suggestion.substitutions.first())
+      .flat_map(|substitution| &substitution.parts)
+      .find(|part| part.span == primary_span && !part.snippet.is_empty())
+      .map(|part| part.snippet.clone())
+  }
 }
 
 impl Emitter for EsbuildEmitter {
   fn emit(&mut self, msg: &mut DiagnosticBuilder<'_>) {
+    let Some(is_error) = is_error(msg.level) else {
+      return;
+    };
+
     let partial_message = PartialMessage {
       id: msg.code.as_ref().map(|code| match code {
         DiagnosticId::Error(id) => id.to_string(),
@@ -82,50 +201,24 @@ impl Emitter for EsbuildEmitter {
       }),
       plugin_name: Some(self.plugin_name.clone()),
       text: Some(msg.message().to_string()),
-      location: if let (Some(sm), Some(primary_span)) =
-        (self.source_map.as_ref(), msg.span.primary_span().as_ref())
-      {
-        let loc = sm.lookup_char_pos(primary_span.lo());
-        let filename = sm.span_to_filename(*primary_span);
-
-        let mut location = PartialLocation {
-          file: Some(filename.to_string()),
-          namespace: None,
-          line: Some(loc.line as u32),
-          column: Some(loc.col.0 as u32),
-          length: Some(primary_span.hi().0 - primary_span.lo().0),
-          line_text: None,
-          suggestion: None,
-        };
-
-        if let Ok(FileLines { lines, file }) = sm.span_to_lines(*primary_span) {
-          lines.iter().for_each(|line| {
-            if line.line_index + 1 == loc.line {
-              if let Some(line_text) = file.get_line(line.line_index) {
-                location.line_text = Some(line_text.to_string());
-              }
-            }
-          });
-        }
-
-        Some(location)
-      } else {
-        None
+      location: match (self.source_map.as_ref(), msg.span.primary_span()) {
+        (Some(sm), Some(primary_span)) => Some(PartialLocation {
+          suggestion: Self::suggestion(msg),
+          ..span_to_location(&**sm, primary_span)
+        }),
+        _ => None,
+      },
+      notes: self.notes(msg),
+      detail: match &self.source_map {
+        Some(sm) if self.code_frame => Some(code_frame::render(&**sm, msg)),
+        _ => None,
       },
-      notes: None,
-      detail: None,
     };
 
-    match msg.level {
-      Level::Bug | Level::Fatal | Level::PhaseFatal | Level::Error => {
-        self.errors.write().unwrap().push(partial_message);
-      }
-      Level::Warning => {
-        self.warnings.write().unwrap().push(partial_message);
-      }
-      _ => {
-        todo!()
-      }
+    if is_error {
+      self.errors.write().unwrap().push(partial_message);
+    } else {
+      self.warnings.write().unwrap().push(partial_message);
     }
   }
 }
@@ -134,6 +227,113 @@ impl Emitter for EsbuildEmitter {
 mod tests {
   use super::*;
   use serde_json::json;
+  use swc_core::common::{
+    errors::{Applicability, Handler},
+    BytePos, FileName, SourceMap,
+  };
+
+  #[test]
+  fn test_emit_notes() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+      FileName::Real("index.js".into()).into(),
+      "let a = 1;\nlet b = a;\n".to_string(),
+    );
+    let span = |lo: u32, hi: u32| Span::new(fm.start_pos + BytePos(lo), fm.start_pos + BytePos(hi));
+
+    let (emitter, errors, warnings) = EsbuildEmitter::new("test".into(), Some(cm.clone()));
+    let handler = Handler::with_emitter(true, false, Box::new(emitter));
+
+    handler
+      .struct_span_warn(span(19, 20), "`a` is used here")
+      .span_label(span(4, 5), "`a` is declared here")
+      .note("some note")
+      .span_help(span(15, 16), "rename `b`")
+      .emit();
+    handler.struct_span_err(span(4, 5), "no notes").emit();
+    handler.note_without_error("a note");
+    DiagnosticBuilder::new(&handler, Level::Help, "a help").emit();
+
+    let warnings = warnings.read().unwrap();
+    assert_eq!(warnings.len(), 3);
+    let notes = warnings[0].notes.as_ref().unwrap();
+    assert_eq!(notes.len(), 3);
+    assert_eq!(notes[0].text.as_deref(), Some("`a` is declared here"));
+    let location = notes[0].location.as_ref().unwrap();
+    assert_eq!((location.line, location.column), (Some(1), Some(4)));
+    assert_eq!(notes[1].text.as_deref(), Some("some note"));
+    assert!(notes[1].location.is_none());
+    assert_eq!(notes[2].text.as_deref(), Some("rename `b`"));
+    assert_eq!(notes[2].location.as_ref().unwrap().line, Some(2));
+    assert_eq!(warnings[1].text.as_deref(), Some("a note"));
+    assert_eq!(warnings[2].text.as_deref(), Some("a help"));
+
+    let errors = errors.read().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].notes.is_none());
+  }
+
+  #[test]
+  fn test_emit_suggestions() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+      FileName::Real("index.js".into()).into(),
+      "let a = 1;\n".to_string(),
+    );
+    let span = |lo: u32, hi: u32| Span::new(fm.start_pos + BytePos(lo), fm.start_pos + BytePos(hi));
+
+    let (emitter, _, warnings) = EsbuildEmitter::new("test".into(), Some(cm.clone()));
+    let handler = Handler::with_emitter(true, false, Box::new(emitter));
+
+    handler
+      .struct_span_warn(span(0, 3), "`let` is used")
+      .span_suggestion_with_applicability(
+        span(0, 3),
+        "use `const`",
+        "const".into(),
+        Applicability::MachineApplicable,
+      )
+      .span_suggestion_with_applicability(
+        span(4, 5),
+        "rename `a`",
+        "b".into(),
+        Applicability::MachineApplicable,
+      )
+      .emit();
+
+    handler
+      .struct_span_warn(span(0, 10), "`a` is unused")
+      .span_suggestion_with_applicability(
+        span(0, 10),
+        "remove `a`",
+        String::new(),
+        Applicability::MachineApplicable,
+      )
+      .emit();
+
+    let warnings = warnings.read().unwrap();
+    let location = warnings[0].location.as_ref().unwrap();
+    assert_eq!(location.suggestion.as_deref(), Some("const"));
+    let notes = warnings[0].notes.as_ref().unwrap();
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].text.as_deref(), Some("rename `a`"));
+    let location = notes[0].location.as_ref().unwrap();
+    assert_eq!(
+      (location.column, location.suggestion.as_deref()),
+      (Some(4), Some("b"))
+    );
+
+    // a removal is a note without `suggestion`
+    assert!(warnings[1].location.as_ref().unwrap().suggestion.is_none());
+    let notes = warnings[1].notes.as_ref().unwrap();
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].text.as_deref(), Some("remove `a`"));
+    let location = notes[0].location.as_ref().unwrap();
+    assert_eq!(
+      (location.length, location.suggestion.as_deref()),
+      (Some(10), None)
+    );
+  }
 
   #[test]
   fn test_partial_message() {
@@ -3,35 +3,52 @@
 
 #[macro_use]
 extern crate napi_derive;
+mod ast;
+mod batch;
 mod bundle;
+mod cache;
+mod cancellation;
+mod code_frame;
+mod codegen_config;
+mod codemod;
+mod diagnostics;
+mod dual_thread;
 mod esbuild;
+mod metadata;
+mod minify_config;
+mod pipeline;
+mod profile;
+mod recovery;
+mod sarif;
 mod swc_plugin_compat_post;
 mod swc_plugin_extract_str;
 mod swc_plugin_refresh;
+mod swc_plugin_snapshot_dce;
 mod swc_plugin_worklet_post_process;
 
-use std::vec;
+use std::{cell::RefCell, collections::HashMap, vec};
 
-use napi::{bindgen_prelude::AsyncTask, Either, Env, Task};
+use napi::{bindgen_prelude::AsyncTask, Either, Env, JsObject, Task};
 
 use rustc_hash::FxBuildHasher;
 
+use serde::Serialize;
+
 use swc_core::{
   atoms::Atom,
   base::{
     config::{GlobalPassOption, IsModule, SourceMapsConfig},
-    Compiler, PrintArgs,
+    sourcemap, Compiler, PrintArgs, TransformOutput,
   },
   common::{
     comments::SingleThreadedComments,
-    errors::{DiagnosticBuilder, Emitter, Handler, HANDLER},
+    errors::{DiagnosticBuilder, DiagnosticId, Emitter, Handler, HANDLER},
     pass::Optional,
     sync::Lrc,
     FileName, FilePathMapping, Mark, SourceMap, GLOBALS,
   },
   ecma::{
     ast::*,
-    codegen,
     parser::{Syntax, TsSyntax},
     transforms::{
       base::{
@@ -62,7 +79,14 @@ use swc_plugin_snapshot::napi::{JSXTransformer, JSXTransformerConfig};
 use swc_plugin_worklet::napi::{WorkletVisitor, WorkletVisitorConfig};
 use swc_plugins_shared::{transform_mode_napi::TransformMode, utils::calc_hash};
 
-#[derive(Debug, Clone, Copy)]
+use cancellation::{AbortListener, Cancellation, Cancelled};
+use codegen_config::CodegenConfig;
+use diagnostics::DiagnosticSeverity;
+use metadata::TransformMetadata;
+use pipeline::TransformPass;
+use profile::PassProfile;
+
+#[derive(Debug, Clone, Copy, Serialize)]
 pub struct SyntaxConfig(Syntax);
 
 impl From<SyntaxConfig> for Syntax {
@@ -117,7 +141,7 @@ impl napi::bindgen_prelude::ToNapiValue for SyntaxConfig {
   }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[derive(Debug, PartialEq, Clone, Copy, Default, Serialize)]
 pub struct IsModuleConfig(IsModule);
 
 impl From<IsModuleConfig> for IsModule {
@@ -174,7 +198,7 @@ impl napi::bindgen_prelude::ToNapiValue for IsModuleConfig {
 }
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct TransformNodiffOptions {
   /// @internal
   /// This is used internally to make sure the test output is consistent.
@@ -183,28 +207,121 @@ pub struct TransformNodiffOptions {
   pub plugin_name: String,
   pub filename: String,
   pub source_file_name: Option<String>,
+  #[serde(serialize_with = "swc_plugins_shared::napi_serde::either")]
   pub sourcemap: Either<bool, String>,
   pub source_map_columns: Option<bool>,
   pub inline_sources_content: Option<bool>,
   /// @public
+  /// The source map of the input code in JSON format, e.g. from a previous loader.
+  /// It is composed into the output source map.
+  pub input_source_map: Option<String>,
+  /// @public
+  /// Options of the code generator, e.g. `target`, `asciiOnly` and `comments`.
+  pub codegen: Option<CodegenConfig>,
+  /// @public
   /// This is swc syntax config in JSON format
   #[napi(ts_type = "string")]
   pub syntax_config: Option<SyntaxConfig>,
   #[napi(ts_type = "boolean | 'unknown'")]
   pub is_module: Option<IsModuleConfig>,
+  #[serde(serialize_with = "swc_plugins_shared::napi_serde::either")]
   pub css_scope: Either<bool, CSSScopeVisitorConfig>,
+  #[serde(serialize_with = "swc_plugins_shared::napi_serde::option_either")]
   pub snapshot: Option<Either<bool, JSXTransformerConfig>>,
+  #[serde(serialize_with = "swc_plugins_shared::napi_serde::either")]
   pub shake: Either<bool, ShakeVisitorConfig>,
+  #[serde(serialize_with = "swc_plugins_shared::napi_serde::either")]
   pub compat: Either<bool, CompatVisitorConfig>,
+  #[serde(serialize_with = "swc_plugins_shared::napi_serde::either")]
   pub refresh: Either<bool, RefreshVisitorConfig>,
   #[napi(js_name = "defineDCE")]
+  #[serde(serialize_with = "swc_plugins_shared::napi_serde::either")]
   pub define_dce: Either<bool, DefineDCEVisitorConfig>,
   #[napi(js_name = "directiveDCE")]
+  #[serde(serialize_with = "swc_plugins_shared::napi_serde::either")]
   pub directive_dce: Either<bool, DirectiveDCEVisitorConfig>,
+  #[serde(serialize_with = "swc_plugins_shared::napi_serde::either")]
   pub worklet: Either<bool, WorkletVisitorConfig>,
+  #[serde(serialize_with = "swc_plugins_shared::napi_serde::option_either")]
   pub dynamic_import: Option<Either<bool, DynamicImportVisitorConfig>>,
   /// @internal
+  #[serde(serialize_with = "swc_plugins_shared::napi_serde::option_either")]
   pub inject: Option<Either<bool, InjectVisitorConfig>>,
+  /// @public
+  /// The ordered list of passes to run. Defaults to the built-in pipeline.
+  ///
+  /// @remarks
+  /// A pass may appear more than once. Whether a pass is enabled and how it is
+  /// configured is still controlled by its own option, e.g. `compat`.
+  #[napi(
+    ts_type = "Array<'typescript' | 'dynamicImport' | 'refresh' | 'compat' | 'worklet' | 'cssScope' | 'list' | 'snapshot' | 'directiveDCE' | 'defineDCE' | 'simplify' | 'shake' | 'react' | 'resolver' | 'compatPost' | 'inject'>"
+  )]
+  pub pipeline: Option<Vec<TransformPass>>,
+  /// @public
+  /// Whether to collect per-pass timing and AST size into `TransformNodiffOutput.profile`.
+  pub profile: Option<bool>,
+  /// @public
+  /// The directory to cache transform results in. The cache is disabled by default.
+  ///
+  /// @remarks
+  /// Entries are keyed by the source code, the options and the version of the transform.
+  /// The cache is bypassed when `profile` is enabled, and is not supported in the WebAssembly build.
+  // Left out of the cache key, since it does not affect the output.
+  #[serde(skip)]
+  pub cache_dir: Option<String>,
+  /// @public
+  /// Cancel the transform if it is not finished within `timeout` milliseconds.
+  ///
+  /// @remarks
+  /// The timeout is checked between passes, so a single pass is never interrupted.
+  /// A cancelled transform rejects (or throws) with an error whose `code` is `'Cancelled'`.
+  // Left out of the cache key, since it does not affect the output.
+  #[serde(skip)]
+  pub timeout: Option<u32>,
+  /// @public
+  /// Apply the machine-applicable fixes of the deprecation warnings of `compat` to the input code,
+  /// and return the rewritten code in `TransformNodiffOutput.codemod`.
+  ///
+  /// @remarks
+  /// The fixes come with the warnings, so `compat.disableDeprecatedWarning` must not be set.
+  /// Some deprecations, e.g. `<component is={...} />`, have no fix and still need to be migrated by hand.
+  /// Not supported by `transformReactLynxAst`, which has no input code to apply the fixes to.
+  pub codemod: Option<bool>,
+  /// @public
+  /// The severity of diagnostics by their codes, e.g. `{ RL1005: 'off', RL3001: 'warn', RL1103: 'error' }`.
+  ///
+  /// @remarks
+  /// Every diagnostic emitted by the plugins has a stable code, which is the `id` of the message.
+  /// `'off'` drops the diagnostic, `'warn'` and `'error'` report it as a warning or an error.
+  /// The deprecated warnings `RL1001` to `RL1006` are not emitted at all with `compat.disableDeprecatedWarning`.
+  #[napi(ts_type = "Record<string, 'off' | 'warn' | 'error'>")]
+  pub diagnostics: Option<HashMap<String, DiagnosticSeverity>>,
+  /// @public
+  /// Keep transforming when the code has syntax errors, e.g. in the dev server.
+  ///
+  /// @remarks
+  /// The passes run on the program recovered by the parser, and every syntax error is still reported in `errors`.
+  /// If the code cannot be parsed at all, `code` is a module that throws the syntax errors when evaluated,
+  /// instead of an empty string.
+  /// Not supported by `transformReactLynxAst`, whose program is already parsed.
+  pub recover: Option<bool>,
+  /// @public
+  /// Render a code frame of each error and warning in its `detail`, e.g. for printing them in a terminal.
+  ///
+  /// @remarks
+  /// The code frame shows the annotated lines with two lines of context, `^` under the span of the diagnostic,
+  /// `-` under its labeled spans, and its notes and suggestions.
+  pub code_frame: Option<bool>,
+}
+
+impl TransformNodiffOptions {
+  /// `pipeline`, or the default pipeline if it is not set.
+  pub(crate) fn resolved_pipeline(&self) -> Vec<TransformPass> {
+    self
+      .pipeline
+      .clone()
+      .unwrap_or_else(|| pipeline::default_pipeline(self))
+  }
 }
 
 impl Default for TransformNodiffOptions {
@@ -217,6 +334,8 @@ impl Default for TransformNodiffOptions {
       sourcemap: Either::A(false),
       source_map_columns: None,
       inline_sources_content: None,
+      input_source_map: None,
+      codegen: None,
       syntax_config: None,
       is_module: Default::default(),
       css_scope: Either::B(Default::default()),
@@ -229,6 +348,14 @@ impl Default for TransformNodiffOptions {
       worklet: Either::A(false),
       dynamic_import: Some(Either::B(Default::default())),
       inject: Some(Either::A(false)),
+      pipeline: None,
+      profile: None,
+      cache_dir: None,
+      timeout: None,
+      codemod: None,
+      diagnostics: None,
+      recover: None,
+      code_frame: None,
     }
   }
 }
@@ -242,152 +369,326 @@ pub struct TransformNodiffOutput {
   pub errors: Vec<esbuild::PartialMessage>,
   // #[napi(ts_type = "Array<import('esbuild').PartialMessage>")]
   pub warnings: Vec<esbuild::PartialMessage>,
+
+  /// Per-pass profiling results, only available when `profile` is enabled.
+  pub profile: Option<Vec<PassProfile>>,
+
+  /// Snapshots, worklets, CSS imports and dynamic imports produced by the transform.
+  pub metadata: TransformMetadata,
+
+  /// The input code with the fixes applied, only available when `codemod` is enabled.
+  pub codemod: Option<String>,
 }
 
 /// A multi emitter that forwards to multiple emitters.
 pub struct MultiEmitter {
   emitters: Vec<Box<dyn Emitter>>,
+  severities: HashMap<String, DiagnosticSeverity>,
 }
 
 impl MultiEmitter {
   pub fn new(emitters: Vec<Box<dyn Emitter>>) -> Self {
-    Self { emitters }
+    Self {
+      emitters,
+      severities: Default::default(),
+    }
+  }
+
+  /// Change the severity of diagnostics by their codes, see `TransformNodiffOptions.diagnostics`.
+  pub fn with_severities(self, severities: Option<HashMap<String, DiagnosticSeverity>>) -> Self {
+    Self {
+      severities: severities.unwrap_or_default(),
+      ..self
+    }
   }
 }
 
 impl Emitter for MultiEmitter {
   fn emit(&mut self, db: &mut DiagnosticBuilder<'_>) {
+    if !diagnostics::apply_severity(&self.severities, db) {
+      return;
+    }
     for emitter in &mut self.emitters {
       emitter.emit(db);
     }
   }
 }
 
+/// Parse the `inputSourceMap` option, emitting a warning if it is invalid.
+pub(crate) fn read_input_source_map(
+  input_source_map: Option<&str>,
+  handler: &Handler,
+) -> Option<sourcemap::SourceMap> {
+  let input_source_map = input_source_map?;
+  match sourcemap::SourceMap::from_slice(input_source_map.as_bytes()) {
+    Ok(map) => Some(map),
+    Err(err) => {
+      handler
+        .struct_warn(&format!("failed to read input source map: {err}"))
+        .code(DiagnosticId::Lint(
+          swc_plugins_shared::diagnostics::INVALID_INPUT_SOURCE_MAP.into(),
+        ))
+        .emit();
+      None
+    }
+  }
+}
+
 pub struct TransformTask {
   pub code: String,
   pub options: TransformNodiffOptions,
+  pub cancellation: Cancellation,
+  pub abort_listener: Option<AbortListener>,
 }
 
 fn transform_react_lynx_inner(
   code: String,
   options: TransformNodiffOptions,
-) -> TransformNodiffOutput {
+  cancellation: &Cancellation,
+) -> Result<TransformNodiffOutput, Cancelled> {
+  transform_react_lynx_with_source_map(
+    Lrc::new(SourceMap::new(FilePathMapping::empty())),
+    code,
+    options,
+    cancellation,
+  )
+}
+
+fn transform_react_lynx_with_source_map(
+  cm: Lrc<SourceMap>,
+  code: String,
+  options: TransformNodiffOptions,
+  cancellation: &Cancellation,
+) -> Result<TransformNodiffOutput, Cancelled> {
+  let cancellation = cancellation.with_timeout(options.timeout);
+  let cache = match (&options.cache_dir, options.profile) {
+    (Some(cache_dir), None | Some(false)) => {
+      Some(cache::TransformCache::new(cache_dir, &code, &options))
+    }
+    _ => None,
+  };
+  if let Some(output) = cache.as_ref().and_then(|cache| cache.get()) {
+    return Ok(output);
+  }
+
   let content_hash = match options.mode {
     Some(TransformMode::Test) => "test".into(),
     _ => calc_hash(code.as_str()),
   };
   let comments = SingleThreadedComments::default();
-  let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
   let fm = cm.new_source_file(FileName::Real(options.filename.clone().into()).into(), code);
 
   let c = Compiler::new(cm.clone());
 
   let (esbuild_emitter, errors, warnings) =
     esbuild::EsbuildEmitter::new(options.plugin_name.clone(), Some(c.cm.clone()));
-  let esbuild_emitter = Box::new(esbuild_emitter);
-  let emitter = Box::new(MultiEmitter::new(vec![esbuild_emitter]));
+  let esbuild_emitter = esbuild_emitter.with_code_frame(options.code_frame == Some(true));
+  let (fix_emitter, fixes) = codemod::FixEmitter::new();
+  let emitters: Vec<Box<dyn Emitter>> = vec![Box::new(esbuild_emitter), Box::new(fix_emitter)];
+  let emitter = Box::new(MultiEmitter::new(emitters).with_severities(options.diagnostics.clone()));
   let handler = Handler::with_emitter(true, false, emitter);
 
   let result = GLOBALS.set(&Default::default(), || {
-    let program = c.parse_js(
-      fm,
-      &handler,
-      EsVersion::latest(),
-      options.syntax_config.unwrap_or_default().into(),
-      options.is_module.unwrap_or_default().into(),
-      Some(&comments),
-    );
+    let program = recovery::parse(&c, fm.clone(), &handler, &options, &comments);
     let program = match program {
-      Ok(program) => program,
-      Err(_) => {
-        return TransformNodiffOutput {
-          code: "".into(),
+      Some(program) => program,
+      None => {
+        return Ok(TransformNodiffOutput {
+          code: match options.recover {
+            Some(true) => recovery::stub_module(&errors.read().unwrap()),
+            _ => "".into(),
+          },
           map: None,
           errors: errors.read().unwrap().clone(),
           warnings: warnings.read().unwrap().clone(),
-        };
+          profile: None,
+          metadata: Default::default(),
+          codemod: None,
+        });
       }
     };
 
-    let unresolved_mark = Mark::new();
-    let top_level_mark = Mark::new();
+    let diagnostics_count =
+      || (errors.read().unwrap().len() + warnings.read().unwrap().len()) as u32;
+    let ctx = PassContext {
+      cm: &cm,
+      comments: &comments,
+      handler: &handler,
+      options: &options,
+      content_hash: &content_hash,
+      diagnostics_count: &diagnostics_count,
+      unresolved_mark: Mark::new(),
+      top_level_mark: Mark::new(),
+      cancellation: &cancellation,
+    };
+    let (program, pass_profiles, metadata) = run_passes(
+      &ctx,
+      program,
+      &options.resolved_pipeline(),
+      PassStage::Whole,
+    )?;
 
-    let simplify_pass_1 = Optional::new(
-      simplifier(
-        top_level_mark,
-        simplify::Config {
-          dce: simplify::dce::Config {
-            preserve_imports_with_side_effects: false,
-            ..Default::default()
-          },
-          ..Default::default()
-        },
-      ),
-      match &options.directive_dce {
-        Either::A(config) => *config,
-        Either::B(_) => true,
-      } || match &options.define_dce {
-        Either::A(config) => *config,
-        Either::B(_) => true,
-      },
-    );
+    let result = print_program(&c, &program, &comments, &handler, &options);
 
-    let directive_dce_plugin = match options.directive_dce {
-      Either::A(config) => Optional::new(
-        visit_mut_pass(DirectiveDCEVisitor::new(Default::default())),
-        config,
-      ),
-      Either::B(config) => Optional::new(visit_mut_pass(DirectiveDCEVisitor::new(config)), true),
-    };
+    match result {
+      Some(result) => Ok(TransformNodiffOutput {
+        code: result.code,
+        map: result.map,
+        errors: vec![],
+        warnings: vec![],
+        profile: pass_profiles,
+        metadata,
+        codemod: None,
+      }),
+      None => {
+        return Ok(TransformNodiffOutput {
+          code: "".into(),
+          map: None,
+          errors: errors.read().unwrap().clone(),
+          warnings: warnings.read().unwrap().clone(),
+          profile: None,
+          metadata: Default::default(),
+          codemod: None,
+        });
+      }
+    }
+  })?;
+
+  let r = TransformNodiffOutput {
+    code: result.code,
+    map: result.map,
+    errors: errors.read().unwrap().clone(),
+    warnings: warnings.read().unwrap().clone(),
+    profile: result.profile,
+    metadata: result.metadata,
+    codemod: match options.codemod {
+      Some(true) => Some(codemod::apply_fixes(
+        &fm.src,
+        fm.start_pos,
+        &fixes.read().unwrap(),
+      )),
+      _ => None,
+    },
+  };
+
+  if let Some(cache) = cache {
+    cache.set(&r);
+  }
+
+  Ok(r)
+}
+
+/// Everything the passes need besides the program itself.
+pub(crate) struct PassContext<'a> {
+  pub cm: &'a Lrc<SourceMap>,
+  pub comments: &'a SingleThreadedComments,
+  /// Diagnostics are emitted to it.
+  pub handler: &'a Handler,
+  pub options: &'a TransformNodiffOptions,
+  pub content_hash: &'a str,
+  /// Only used for profiling.
+  pub diagnostics_count: &'a dyn Fn() -> u32,
+  pub unresolved_mark: Mark,
+  pub top_level_mark: Mark,
+  /// Checked between passes.
+  pub cancellation: &'a Cancellation,
+}
+
+/// Which of the fixed passes around the pipeline `run_passes` runs.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum PassStage {
+  /// `fixer` and `resolver`, the pipeline, then `hygiene` and `fixer`.
+  Whole,
+  /// `fixer` and `resolver`, then the pipeline.
+  Head,
+  /// The pipeline, then `hygiene` and `fixer`.
+  Tail,
+}
 
-    let define_dce_plugin = {
-      let opts = GlobalPassOption {
-        vars: match &options.define_dce {
-          Either::A(_) => Default::default(),
-          Either::B(config) => {
-            let mut map = indexmap::IndexMap::<_, _, FxBuildHasher>::default();
-            for (key, value) in &config.define {
-              map.insert(key.as_str().into(), value.as_str().into());
-            }
-            map
+/// Run `pipeline` on `program`, see `transform_react_lynx_with_source_map`.
+///
+/// Must be called inside `GLOBALS.set`.
+fn run_passes(
+  ctx: &PassContext,
+  program: Program,
+  pipeline: &[TransformPass],
+  stage: PassStage,
+) -> Result<(Program, Option<Vec<PassProfile>>, TransformMetadata), Cancelled> {
+  let PassContext {
+    cm,
+    comments,
+    handler,
+    options,
+    content_hash,
+    diagnostics_count,
+    unresolved_mark,
+    top_level_mark,
+    cancellation,
+  } = *ctx;
+  let metadata = RefCell::new(TransformMetadata::default());
+
+  let directive_dce_plugin = || match &options.directive_dce {
+    Either::A(config) => Optional::new(
+      visit_mut_pass(DirectiveDCEVisitor::new(Default::default())),
+      *config,
+    ),
+    Either::B(config) => Optional::new(
+      visit_mut_pass(DirectiveDCEVisitor::new(config.clone())),
+      true,
+    ),
+  };
+
+  let define_dce_plugin = || {
+    let opts = GlobalPassOption {
+      vars: match &options.define_dce {
+        Either::A(_) => Default::default(),
+        Either::B(config) => {
+          let mut map = indexmap::IndexMap::<_, _, FxBuildHasher>::default();
+          for (key, value) in &config.define {
+            map.insert(key.as_str().into(), value.as_str().into());
           }
-        },
-        envs: Default::default(),
-        typeofs: Default::default(),
-      };
-
-      Optional::new(
-        opts.build(&cm, &handler),
-        matches!(options.define_dce, Either::B(_)),
-      )
+          map
+        }
+      },
+      envs: Default::default(),
+      typeofs: Default::default(),
     };
 
-    let css_scope_plugin = match options.css_scope {
-      Either::A(enabled) => Optional::new(
-        visit_mut_pass(CSSScopeVisitor::new(
-          CSSScopeVisitorConfig::default(),
-          Some(&comments),
-        )),
-        enabled,
-      ),
-      Either::B(config) => Optional::new(
-        visit_mut_pass(CSSScopeVisitor::new(config, Some(&comments))),
-        true,
-      ),
-    };
+    Optional::new(
+      opts.build(cm, handler),
+      matches!(options.define_dce, Either::B(_)),
+    )
+  };
 
-    let (snapshot_plugin_config, enabled) = match &options.snapshot.unwrap_or(Either::A(true)) {
-      Either::A(config) => (
-        JSXTransformerConfig {
-          filename: options.filename,
-          ..Default::default()
-        },
-        *config,
-      ),
+  let css_scope_plugin = || {
+    let (config, enabled) = match &options.css_scope {
+      Either::A(enabled) => (CSSScopeVisitorConfig::default(), *enabled),
       Either::B(config) => (config.clone(), true),
     };
+    Optional::new(
+      metadata::collect(CSSScopeVisitor::new(config, Some(&comments)), |v| {
+        metadata
+          .borrow_mut()
+          .add_css_imports(cm, v.take_scoped_imports())
+      }),
+      enabled,
+    )
+  };
 
-    let react_transformer = Optional::new(
+  let (snapshot_plugin_config, enabled) = match &options.snapshot.clone().unwrap_or(Either::A(true))
+  {
+    Either::A(config) => (
+      JSXTransformerConfig {
+        filename: options.filename.clone(),
+        ..Default::default()
+      },
+      *config,
+    ),
+    Either::B(config) => (config.clone(), true),
+  };
+
+  let react_transformer = || {
+    Optional::new(
       react::react(
         cm.clone(),
         Some(&comments),
@@ -410,31 +711,42 @@ fn transform_react_lynx_inner(
         unresolved_mark,
       ),
       enabled && !snapshot_plugin_config.preserve_jsx,
-    );
+    )
+  };
 
-    let snapshot_plugin = Optional::new(
-      visit_mut_pass(
+  let snapshot_plugin = || {
+    Optional::new(
+      metadata::collect(
         JSXTransformer::new(
-          snapshot_plugin_config,
+          snapshot_plugin_config.clone(),
           Some(&comments),
           options.mode.unwrap_or(TransformMode::Production),
         )
-        .with_content_hash(content_hash.clone()),
+        .with_content_hash(content_hash.to_string()),
+        |v| {
+          metadata
+            .borrow_mut()
+            .add_snapshots(cm, v.take_created_snapshots())
+        },
       ),
       enabled,
-    );
+    )
+  };
 
-    let list_plugin = Optional::new(
+  let list_plugin = || {
+    Optional::new(
       visit_mut_pass(swc_plugin_list::ListVisitor::new(Some(&comments))),
       enabled,
-    );
+    )
+  };
 
-    let shake_plugin = match options.shake.clone() {
-      Either::A(config) => Optional::new(visit_mut_pass(ShakeVisitor::default()), config),
-      Either::B(config) => Optional::new(visit_mut_pass(ShakeVisitor::new(config)), true),
-    };
+  let shake_plugin = || match options.shake.clone() {
+    Either::A(config) => Optional::new(visit_mut_pass(ShakeVisitor::default()), config),
+    Either::B(config) => Optional::new(visit_mut_pass(ShakeVisitor::new(config)), true),
+  };
 
-    let simplify_pass = simplifier(
+  let simplify_pass = || {
+    simplifier(
       top_level_mark,
       simplify::Config {
         dce: simplify::dce::Config {
@@ -443,102 +755,118 @@ fn transform_react_lynx_inner(
         },
         ..Default::default()
       },
-    );
+    )
+  };
 
-    let compat_plugin = match options.compat.clone() {
-      Either::A(config) => Optional::new(
-        visit_mut_pass(CompatVisitor::new(
-          CompatVisitorConfig::default(),
-          Some(&comments),
-        )),
-        config,
-      ),
-      Either::B(config) => Optional::new(
-        visit_mut_pass(CompatVisitor::new(config, Some(&comments))),
-        true,
-      ),
-    };
+  let compat_plugin = || match options.compat.clone() {
+    Either::A(config) => Optional::new(
+      visit_mut_pass(CompatVisitor::new(
+        CompatVisitorConfig::default(),
+        Some(&comments),
+      )),
+      config,
+    ),
+    Either::B(config) => Optional::new(
+      visit_mut_pass(CompatVisitor::new(config, Some(&comments))),
+      true,
+    ),
+  };
 
-    let compat_post_plugin = match options.compat {
-      Either::A(config) => Optional::new(
-        visit_mut_pass(CompatPostVisitor::new(
-          Default::default(),
-          unresolved_mark,
-          top_level_mark,
-        )),
+  let compat_post_plugin = || match options.compat.clone() {
+    Either::A(config) => Optional::new(
+      visit_mut_pass(CompatPostVisitor::new(
+        Default::default(),
+        unresolved_mark,
+        top_level_mark,
+      )),
+      config,
+    ),
+    Either::B(config) => Optional::new(
+      visit_mut_pass(CompatPostVisitor::new(
         config,
-      ),
-      Either::B(config) => Optional::new(
-        visit_mut_pass(CompatPostVisitor::new(
-          config,
-          unresolved_mark,
-          top_level_mark,
-        )),
-        true,
-      ),
-    };
+        unresolved_mark,
+        top_level_mark,
+      )),
+      true,
+    ),
+  };
 
-    let refresh_plugin = match options.refresh {
-      Either::A(config) => Optional::new(
-        visit_mut_pass(RefreshVisitor::new(
-          RefreshVisitorConfig::default(),
-          content_hash.clone(),
-        )),
-        config,
-      ),
-      Either::B(config) => Optional::new(
-        visit_mut_pass(RefreshVisitor::new(config, content_hash.clone())),
-        true,
-      ),
-    };
+  let refresh_plugin = || match options.refresh.clone() {
+    Either::A(config) => Optional::new(
+      visit_mut_pass(RefreshVisitor::new(
+        RefreshVisitorConfig::default(),
+        content_hash.to_string(),
+      )),
+      config,
+    ),
+    Either::B(config) => Optional::new(
+      visit_mut_pass(RefreshVisitor::new(config, content_hash.to_string())),
+      true,
+    ),
+  };
 
-    let worklet_plugin = match options.worklet {
-      Either::A(config) => Optional::new(
-        visit_mut_pass(WorkletVisitor::default().with_content_hash(content_hash)),
-        config,
-      ),
-      Either::B(config) => Optional::new(
-        visit_mut_pass(
-          WorkletVisitor::new(options.mode.unwrap_or(TransformMode::Production), config)
-            .with_content_hash(content_hash),
-        ),
+  let worklet_plugin = || {
+    let (worklet_visitor, enabled) = match options.worklet.clone() {
+      Either::A(config) => (WorkletVisitor::default(), config),
+      Either::B(config) => (
+        WorkletVisitor::new(options.mode.unwrap_or(TransformMode::Production), config),
         true,
       ),
     };
-
-    let dynamic_import_plugin = match options.dynamic_import.unwrap_or(Either::A(true)) {
-      Either::A(config) => Optional::new(
-        visit_mut_pass(DynamicImportVisitor::new(
-          Default::default(),
-          Some(&comments),
-        )),
-        config,
-      ),
-      Either::B(config) => Optional::new(
-        visit_mut_pass(DynamicImportVisitor::new(config, Some(&comments))),
-        true,
+    Optional::new(
+      metadata::collect(
+        worklet_visitor.with_content_hash(content_hash.to_string()),
+        |v| {
+          metadata
+            .borrow_mut()
+            .add_worklets(cm, v.take_registered_worklets())
+        },
       ),
-    };
+      enabled,
+    )
+  };
 
-    let inject_plugin = match options.inject.unwrap_or(Either::A(false)) {
-      Either::A(config) => Optional::new(
-        visit_mut_pass(InjectVisitor::new(
-          Default::default(),
-          unresolved_mark,
-          top_level_mark,
-        )),
-        config,
-      ),
-      Either::B(config) => Optional::new(
-        visit_mut_pass(InjectVisitor::new(config, unresolved_mark, top_level_mark)),
-        true,
-      ),
+  let dynamic_import_plugin = || {
+    let (config, enabled) = match options.dynamic_import.clone().unwrap_or(Either::A(true)) {
+      Either::A(config) => (Default::default(), config),
+      Either::B(config) => (config, true),
     };
+    Optional::new(
+      metadata::collect(DynamicImportVisitor::new(config, Some(&comments)), |v| {
+        metadata
+          .borrow_mut()
+          .add_dynamic_imports(cm, v.take_rewritten_imports())
+      }),
+      enabled,
+    )
+  };
 
-    let pass = (
-      &mut fixer(Some(&comments)),
-      resolver(unresolved_mark, top_level_mark, true),
-      typescript::typescript(
+  let inject_plugin = || match options.inject.clone().unwrap_or(Either::A(false)) {
+    Either::A(config) => Optional::new(
+      visit_mut_pass(InjectVisitor::new(
+        Default::default(),
+        unresolved_mark,
+        top_level_mark,
+      )),
+      config,
+    ),
+    Either::B(config) => Optional::new(
+      visit_mut_pass(InjectVisitor::new(config, unresolved_mark, top_level_mark)),
+      true,
+    ),
+  };
+
+  let mut passes: Vec<(&str, Box<dyn Pass + '_>)> = vec![];
+  if stage != PassStage::Tail {
+    passes.push(("fixer", Box::new(fixer(Some(comments)))));
+    passes.push((
+      "resolver",
+      Box::new(resolver(unresolved_mark, top_level_mark, true)),
+    ));
+  }
+  for pass in pipeline {
+    let plugin: Box<dyn Pass> = match pass {
+      TransformPass::Typescript => Box::new(typescript::typescript(
         typescript::Config {
           verbatim_module_syntax: false,
           import_not_used_as_values: typescript::ImportsNotUsedAsValues::Remove,
@@ -546,88 +874,111 @@ fn transform_react_lynx_inner(
         },
         unresolved_mark,
         top_level_mark,
-      ),
-      dynamic_import_plugin,
-      refresh_plugin,
-      compat_plugin,
-      worklet_plugin,
-      css_scope_plugin,
-      (list_plugin, snapshot_plugin),
-      directive_dce_plugin,
-      define_dce_plugin,
-      simplify_pass_1, // do simplify after DCE above to make shake below works better
-      (
-        shake_plugin,
-        simplify_pass,
-        react_transformer,
-        // TODO(hongzhiyuan.hzy): if `ident` we added above is correctly marked, this pass will be unnecessary
-        resolver(unresolved_mark, top_level_mark, true),
-        compat_post_plugin,
-        inject_plugin,
-        hygiene_with_config(Config {
-          top_level_mark,
-          ..Default::default()
-        }),
-        &mut fixer(Some(&comments)),
-      ),
-    );
-
-    let program = helpers::HELPERS.set(&helpers::Helpers::new(true), || {
-      HANDLER.set(&handler, || program.apply(pass))
-    });
+      )),
+      TransformPass::DynamicImport => Box::new(dynamic_import_plugin()),
+      TransformPass::Refresh => Box::new(refresh_plugin()),
+      TransformPass::Compat => Box::new(compat_plugin()),
+      TransformPass::Worklet => Box::new(worklet_plugin()),
+      TransformPass::CssScope => Box::new(css_scope_plugin()),
+      TransformPass::List => Box::new(list_plugin()),
+      TransformPass::Snapshot => Box::new(snapshot_plugin()),
+      TransformPass::DirectiveDCE => Box::new(directive_dce_plugin()),
+      TransformPass::DefineDCE => Box::new(define_dce_plugin()),
+      TransformPass::Simplify => Box::new(simplify_pass()),
+      TransformPass::Shake => Box::new(shake_plugin()),
+      TransformPass::React => Box::new(react_transformer()),
+      TransformPass::Resolver => Box::new(resolver(unresolved_mark, top_level_mark, true)),
+      TransformPass::CompatPost => Box::new(compat_post_plugin()),
+      TransformPass::Inject => Box::new(inject_plugin()),
+    };
+    passes.push((pass.as_str(), plugin));
+  }
+  if stage != PassStage::Head {
+    passes.push((
+      "hygiene",
+      Box::new(hygiene_with_config(Config {
+        top_level_mark,
+        ..Default::default()
+      })),
+    ));
+    passes.push(("fixer", Box::new(fixer(Some(comments)))));
+  }
 
-    let result = c.print(
-      &program,
-      PrintArgs {
-        output: None,
-        source_root: "".into(), // TODO: add root
-        source_file_name: options.source_file_name.as_deref(),
-        source_map_url: None,
-        source_map_ignore_list: None,
-        output_path: None,
-        inline_sources_content: options.inline_sources_content.unwrap_or(true),
-        source_map: match options.sourcemap {
-          Either::A(b) => SourceMapsConfig::Bool(b),
-          Either::B(s) => SourceMapsConfig::Str(s),
-        },
-        source_map_names: &Default::default(),
-        orig: None,
-        comments: Some(&comments),
-        emit_source_map_columns: options.source_map_columns.unwrap_or(true),
-        preamble: "",
-        codegen_config: codegen::Config::default()
-          .with_target(EsVersion::latest())
-          .with_minify(false)
-          .with_ascii_only(false),
-      },
-    );
+  let mut pass_profiles = options.profile.unwrap_or(false).then(Vec::new);
 
-    match result {
-      Ok(result) => TransformNodiffOutput {
-        code: result.code,
-        map: result.map,
-        errors: vec![],
-        warnings: vec![],
-      },
-      Err(_) => {
-        return TransformNodiffOutput {
-          code: "".into(),
-          map: None,
-          errors: errors.read().unwrap().clone(),
-          warnings: warnings.read().unwrap().clone(),
+  let program = helpers::HELPERS.set(&helpers::Helpers::new(true), || {
+    HANDLER.set(handler, || {
+      let mut program = program;
+      for (name, pass) in &mut passes {
+        cancellation.check()?;
+        let Some(pass_profiles) = pass_profiles.as_mut() else {
+          program.mutate(pass);
+          continue;
         };
+
+        let nodes_before = profile::count_nodes(&program);
+        let diagnostics_before = diagnostics_count();
+        let start = profile::now();
+        program.mutate(pass);
+        pass_profiles.push(PassProfile {
+          name: name.to_string(),
+          duration: profile::now() - start,
+          nodes_before,
+          nodes_after: profile::count_nodes(&program),
+          diagnostics: diagnostics_count() - diagnostics_before,
+        });
       }
-    }
-  });
+      cancellation.check()?;
+      Ok(program)
+    })
+  })?;
 
-  let r = TransformNodiffOutput {
-    code: result.code,
-    map: result.map,
-    errors: errors.read().unwrap().clone(),
-    warnings: warnings.read().unwrap().clone(),
-  };
+  Ok((program, pass_profiles, metadata.take()))
+}
+
+/// Print `program` according to `options.codegen`, `options.sourcemap`, etc.
+fn print_program(
+  c: &Compiler,
+  program: &Program,
+  comments: &SingleThreadedComments,
+  handler: &Handler,
+  options: &TransformNodiffOptions,
+) -> Option<TransformOutput> {
+  let codegen_config = options.codegen.clone().unwrap_or_default();
+  codegen_config.retain_comments(comments);
+  codegen_config.check_target(program, handler);
+
+  c.print(
+    program,
+    PrintArgs {
+      output: None,
+      source_root: Some(codegen_config.source_root.as_deref().unwrap_or("")),
+      source_file_name: options.source_file_name.as_deref(),
+      source_map_url: None,
+      source_map_ignore_list: None,
+      output_path: None,
+      inline_sources_content: options.inline_sources_content.unwrap_or(true),
+      source_map: match &options.sourcemap {
+        Either::A(b) => SourceMapsConfig::Bool(*b),
+        Either::B(s) => SourceMapsConfig::Str(s.clone()),
+      },
+      source_map_names: &Default::default(),
+      orig: read_input_source_map(options.input_source_map.as_deref(), handler),
+      comments: Some(comments),
+      emit_source_map_columns: options.source_map_columns.unwrap_or(true),
+      preamble: "",
+      codegen_config: codegen_config.to_codegen_config(),
+    },
+  )
+  .ok()
+}
 
-  r
+pub(crate) fn validate_options(options: &TransformNodiffOptions) -> napi::Result<()> {
+  if let Some(pipeline) = &options.pipeline {
+    pipeline::validate_pipeline(pipeline)
+      .map_err(|err| napi::Error::new(napi::Status::InvalidArg, err))?;
+  }
+  Ok(())
 }
 
 #[napi]
@@ -636,7 +987,9 @@ pub fn transform_react_lynx_sync(
   code: String,
   options: Option<TransformNodiffOptions>,
 ) -> napi::Result<TransformNodiffOutput> {
-  let out = transform_react_lynx_inner(code, options.unwrap_or_default());
+  let options = options.unwrap_or_default();
+  validate_options(&options)?;
+  let out = transform_react_lynx_inner(code, options, &Default::default())?;
   napi::Result::Ok(out)
 }
 
@@ -645,29 +998,215 @@ impl Task for TransformTask {
   type Output = TransformNodiffOutput;
   type JsValue = TransformNodiffOutput;
   fn compute(&mut self) -> napi::Result<Self::Output> {
-    let out = transform_react_lynx_inner(self.code.clone(), self.options.clone());
+    let out =
+      transform_react_lynx_inner(self.code.clone(), self.options.clone(), &self.cancellation)?;
     Ok(out)
   }
   fn resolve(&mut self, _: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
     napi::Result::Ok(output)
   }
+  fn finally(&mut self, env: napi::Env) -> napi::Result<()> {
+    remove_abort_listener(env, &mut self.abort_listener)
+  }
+}
+
+/// Create the `Cancellation` of an async task, which is aborted along with `signal`,
+/// and the listener of `signal` to be removed once the task settles.
+fn listen_signal(
+  env: Env,
+  signal: Option<JsObject>,
+) -> napi::Result<(Cancellation, Option<AbortListener>)> {
+  let cancellation = Cancellation::default();
+  let abort_listener = match signal {
+    Some(signal) => cancellation.listen(env, signal)?,
+    None => None,
+  };
+  Ok((cancellation, abort_listener))
+}
+
+fn remove_abort_listener(env: Env, abort_listener: &mut Option<AbortListener>) -> napi::Result<()> {
+  match abort_listener.take() {
+    Some(abort_listener) => abort_listener.remove(env),
+    None => Ok(()),
+  }
 }
 
 #[napi]
 pub fn transform_react_lynx(
-  _env: Env,
+  env: Env,
   code: String,
   options: Option<TransformNodiffOptions>,
+  #[napi(ts_arg_type = "AbortSignal")] signal: Option<JsObject>,
 ) -> napi::Result<AsyncTask<TransformTask>> {
+  let options = options.unwrap_or_default();
+  validate_options(&options)?;
+  let (cancellation, abort_listener) = listen_signal(env, signal)?;
   Ok(AsyncTask::new(TransformTask {
     code,
-    options: options.unwrap_or_default(),
+    options,
+    cancellation,
+    abort_listener,
   }))
 }
 
+fn validate_ast_options(options: &TransformNodiffOptions) -> napi::Result<()> {
+  validate_options(options)?;
+  ast::validate_ast_options(options).map_err(|err| napi::Error::new(napi::Status::InvalidArg, err))
+}
+
+#[napi]
+pub fn transform_react_lynx_ast_sync(
+  _env: Env,
+  input: ast::TransformAstInput,
+  options: Option<TransformNodiffOptions>,
+) -> napi::Result<ast::TransformNodiffAstOutput> {
+  let options = options.unwrap_or_default();
+  validate_ast_options(&options)?;
+  let out = ast::transform_react_lynx_ast_inner(input, options, &Default::default())?;
+  napi::Result::Ok(out)
+}
+
+pub struct TransformAstTask {
+  pub input: ast::TransformAstInput,
+  pub options: TransformNodiffOptions,
+  pub cancellation: Cancellation,
+  pub abort_listener: Option<AbortListener>,
+}
+
+#[napi]
+impl Task for TransformAstTask {
+  type Output = ast::TransformNodiffAstOutput;
+  type JsValue = ast::TransformNodiffAstOutput;
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    let out = ast::transform_react_lynx_ast_inner(
+      std::mem::take(&mut self.input),
+      self.options.clone(),
+      &self.cancellation,
+    )?;
+    Ok(out)
+  }
+  fn resolve(&mut self, _: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    napi::Result::Ok(output)
+  }
+  fn finally(&mut self, env: napi::Env) -> napi::Result<()> {
+    remove_abort_listener(env, &mut self.abort_listener)
+  }
+}
+
+#[napi]
+pub fn transform_react_lynx_ast(
+  env: Env,
+  input: ast::TransformAstInput,
+  options: Option<TransformNodiffOptions>,
+  #[napi(ts_arg_type = "AbortSignal")] signal: Option<JsObject>,
+) -> napi::Result<AsyncTask<TransformAstTask>> {
+  let options = options.unwrap_or_default();
+  validate_ast_options(&options)?;
+  let (cancellation, abort_listener) = listen_signal(env, signal)?;
+  Ok(AsyncTask::new(TransformAstTask {
+    input,
+    options,
+    cancellation,
+    abort_listener,
+  }))
+}
+
+fn validate_dual_thread_options(
+  options: &dual_thread::TransformDualThreadOptions,
+) -> napi::Result<()> {
+  validate_options(&options.main_thread)?;
+  validate_options(&options.background)?;
+  dual_thread::validate_dual_thread_options(options)
+    .map_err(|err| napi::Error::new(napi::Status::InvalidArg, err))
+}
+
+#[napi]
+pub fn transform_react_lynx_dual_thread_sync(
+  _env: Env,
+  code: String,
+  options: dual_thread::TransformDualThreadOptions,
+) -> napi::Result<dual_thread::TransformDualThreadOutput> {
+  validate_dual_thread_options(&options)?;
+  let out =
+    dual_thread::transform_react_lynx_dual_thread_inner(code, options, &Default::default())?;
+  napi::Result::Ok(out)
+}
+
+pub struct TransformDualThreadTask {
+  pub code: String,
+  pub options: dual_thread::TransformDualThreadOptions,
+  pub cancellation: Cancellation,
+  pub abort_listener: Option<AbortListener>,
+}
+
+#[napi]
+impl Task for TransformDualThreadTask {
+  type Output = dual_thread::TransformDualThreadOutput;
+  type JsValue = dual_thread::TransformDualThreadOutput;
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    let out = dual_thread::transform_react_lynx_dual_thread_inner(
+      self.code.clone(),
+      self.options.clone(),
+      &self.cancellation,
+    )?;
+    Ok(out)
+  }
+  fn resolve(&mut self, _: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    napi::Result::Ok(output)
+  }
+  fn finally(&mut self, env: napi::Env) -> napi::Result<()> {
+    remove_abort_listener(env, &mut self.abort_listener)
+  }
+}
+
+#[napi]
+pub fn transform_react_lynx_dual_thread(
+  env: Env,
+  code: String,
+  options: dual_thread::TransformDualThreadOptions,
+  #[napi(ts_arg_type = "AbortSignal")] signal: Option<JsObject>,
+) -> napi::Result<AsyncTask<TransformDualThreadTask>> {
+  validate_dual_thread_options(&options)?;
+  let (cancellation, abort_listener) = listen_signal(env, signal)?;
+  Ok(AsyncTask::new(TransformDualThreadTask {
+    code,
+    options,
+    cancellation,
+    abort_listener,
+  }))
+}
+
+pub struct TransformBatchTask {
+  pub files: Vec<batch::TransformBatchFile>,
+}
+
+#[napi]
+impl Task for TransformBatchTask {
+  type Output = Vec<TransformNodiffOutput>;
+  type JsValue = Vec<TransformNodiffOutput>;
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    Ok(batch::transform_react_lynx_batch_inner(std::mem::take(
+      &mut self.files,
+    )))
+  }
+  fn resolve(&mut self, _: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    napi::Result::Ok(output)
+  }
+}
+
+#[napi]
+pub fn transform_react_lynx_batch(
+  _env: Env,
+  files: Vec<batch::TransformBatchFile>,
+) -> napi::Result<AsyncTask<TransformBatchTask>> {
+  Ok(AsyncTask::new(TransformBatchTask { files }))
+}
+
 pub struct BundleTransformTask {
   pub code: String,
   pub options: bundle::TransformNodiffBundleOptions,
+  pub cancellation: Cancellation,
+  pub abort_listener: Option<AbortListener>,
 }
 
 #[napi]
@@ -675,12 +1214,19 @@ impl Task for BundleTransformTask {
   type Output = bundle::TransformNodiffBundleOutput;
   type JsValue = bundle::TransformNodiffBundleOutput;
   fn compute(&mut self) -> napi::Result<Self::Output> {
-    let out = bundle::transform_bundle_result_inner(self.code.clone(), self.options.clone());
+    let out = bundle::transform_bundle_result_inner(
+      self.code.clone(),
+      self.options.clone(),
+      &self.cancellation,
+    )?;
     Ok(out)
   }
   fn resolve(&mut self, _: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
     napi::Result::Ok(output)
   }
+  fn finally(&mut self, env: napi::Env) -> napi::Result<()> {
+    remove_abort_listener(env, &mut self.abort_listener)
+  }
 }
 
 #[napi]
@@ -689,22 +1235,41 @@ pub fn transform_bundle_result_sync(
   code: String,
   options: Option<bundle::TransformNodiffBundleOptions>,
 ) -> napi::Result<bundle::TransformNodiffBundleOutput> {
-  let out = bundle::transform_bundle_result_inner(code, options.unwrap_or_default());
+  let out =
+    bundle::transform_bundle_result_inner(code, options.unwrap_or_default(), &Default::default())?;
   napi::Result::Ok(out)
 }
 
 #[napi]
 pub fn transform_bundle_result(
-  _env: Env,
+  env: Env,
   code: String,
   options: Option<bundle::TransformNodiffBundleOptions>,
+  #[napi(ts_arg_type = "AbortSignal")] signal: Option<JsObject>,
 ) -> napi::Result<AsyncTask<BundleTransformTask>> {
+  let (cancellation, abort_listener) = listen_signal(env, signal)?;
   Ok(AsyncTask::new(BundleTransformTask {
     code,
     options: options.unwrap_or_default(),
+    cancellation,
+    abort_listener,
   }))
 }
 
+/// Serialize the errors and warnings of transforms into a SARIF 2.1.0 log,
+/// e.g. for uploading them to code scanning dashboards.
+///
+/// The log has a single run, with every diagnostic code of the plugins as its rules.
+/// The `file` of the locations is used as the artifact uri as is,
+/// so it should be made relative to the root of the repository beforehand.
+#[napi]
+pub fn format_sarif(
+  errors: Vec<esbuild::PartialMessage>,
+  warnings: Vec<esbuild::PartialMessage>,
+) -> String {
+  sarif::format_sarif(&errors, &warnings)
+}
+
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 mod wasm {
   use ::napi::{JsObject, NapiValue};
@@ -756,7 +1321,13 @@ mod wasm {
       let _ = exports.create_named_method("transformReactLynxSync", crate::__napi__transform_react_lynx_sync);
       let _ = exports.create_named_method("transformBundleResultSync", crate::__napi__transform_bundle_result_sync);
       let _ = exports.create_named_method("transformReactLynx", crate::__napi__transform_react_lynx);
+      let _ = exports.create_named_method("transformReactLynxBatch", crate::__napi__transform_react_lynx_batch);
+      let _ = exports.create_named_method("transformReactLynxAstSync", crate::__napi__transform_react_lynx_ast_sync);
+      let _ = exports.create_named_method("transformReactLynxAst", crate::__napi__transform_react_lynx_ast);
+      let _ = exports.create_named_method("transformReactLynxDualThreadSync", crate::__napi__transform_react_lynx_dual_thread_sync);
+      let _ = exports.create_named_method("transformReactLynxDualThread", crate::__napi__transform_react_lynx_dual_thread);
       let _ = exports.create_named_method("transformBundleResult", crate::__napi__transform_bundle_result);
+      let _ = exports.create_named_method("formatSarif", crate::__napi__format_sarif);
     }
   }
 }
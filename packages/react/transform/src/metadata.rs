@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+use swc_core::{
+  common::{SourceMap, Span},
+  ecma::{
+    ast::{Pass, Program},
+    visit::{VisitMut, VisitMutWith},
+  },
+};
+use swc_plugin_css_scope::ScopedCSSImport;
+use swc_plugin_dynamic_import::RewrittenDynamicImport;
+use swc_plugin_snapshot::CreatedSnapshot;
+use swc_plugin_worklet::RegisteredWorklet;
+
+use crate::esbuild::{span_to_location, PartialLocation};
+
+/// Artifacts produced by the transform, see `TransformNodiffOutput.metadata`.
+#[napi(object)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransformMetadata {
+  /// Snapshots created from JSX elements.
+  pub snapshots: Vec<SnapshotMetadata>,
+  /// Worklets registered from `'main thread'` and `'use worklet'` functions.
+  pub worklets: Vec<WorkletMetadata>,
+  /// CSS imports rewritten with `?cssId=`.
+  pub css_imports: Vec<CssImportMetadata>,
+  /// `import()` calls rewritten for lazy bundles.
+  pub dynamic_imports: Vec<DynamicImportMetadata>,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotMetadata {
+  /// The snapshot id, e.g. `__snapshot_da39a_test_1`.
+  pub id: String,
+  /// Location of the JSX element.
+  pub location: Option<PartialLocation>,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkletMetadata {
+  /// The hash the worklet is registered with.
+  pub hash: String,
+  #[napi(js_name = "type", ts_type = "'main-thread' | 'ui'")]
+  #[serde(rename = "type")]
+  pub worklet_type: String,
+  /// Location of the worklet function.
+  pub location: Option<PartialLocation>,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CssImportMetadata {
+  /// The original import source, without `?cssId=`.
+  pub src: String,
+  pub css_id: u32,
+  /// Location of the import declaration.
+  pub location: Option<PartialLocation>,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DynamicImportMetadata {
+  /// The module id, absent if it is not a string literal.
+  pub src: Option<String>,
+  /// Whether the call is replaced with the runtime `__dynamicImport`.
+  pub lazy: bool,
+  /// Location of the `import()` call.
+  pub location: Option<PartialLocation>,
+}
+
+fn location(cm: &SourceMap, span: Span) -> Option<PartialLocation> {
+  (!span.is_dummy()).then(|| span_to_location(cm, span))
+}
+
+impl TransformMetadata {
+  /// Append the entries of `other`.
+  pub fn extend(&mut self, other: TransformMetadata) {
+    self.snapshots.extend(other.snapshots);
+    self.worklets.extend(other.worklets);
+    self.css_imports.extend(other.css_imports);
+    self.dynamic_imports.extend(other.dynamic_imports);
+  }
+
+  pub fn add_snapshots(&mut self, cm: &SourceMap, snapshots: Vec<CreatedSnapshot>) {
+    self
+      .snapshots
+      .extend(snapshots.into_iter().map(|snapshot| SnapshotMetadata {
+        id: snapshot.id,
+        location: location(cm, snapshot.span),
+      }));
+  }
+
+  pub fn add_worklets(&mut self, cm: &SourceMap, worklets: Vec<RegisteredWorklet>) {
+    self
+      .worklets
+      .extend(worklets.into_iter().map(|worklet| WorkletMetadata {
+        hash: worklet.hash,
+        worklet_type: worklet.worklet_type,
+        location: location(cm, worklet.span),
+      }));
+  }
+
+  pub fn add_css_imports(&mut self, cm: &SourceMap, imports: Vec<ScopedCSSImport>) {
+    self
+      .css_imports
+      .extend(imports.into_iter().map(|import| CssImportMetadata {
+        src: import.src,
+        css_id: import.css_id as u32,
+        location: location(cm, import.span),
+      }));
+  }
+
+  pub fn add_dynamic_imports(&mut self, cm: &SourceMap, imports: Vec<RewrittenDynamicImport>) {
+    self
+      .dynamic_imports
+      .extend(imports.into_iter().map(|import| DynamicImportMetadata {
+        src: import.src,
+        lazy: import.lazy,
+        location: location(cm, import.span),
+      }));
+  }
+}
+
+/// A pass that runs `visitor` and then calls `on_done` with it,
+/// so that the results of the visitor can be collected.
+pub struct Collect<V, F> {
+  visitor: V,
+  on_done: F,
+}
+
+pub fn collect<V, F>(visitor: V, on_done: F) -> Collect<V, F>
+where
+  V: VisitMut,
+  F: FnMut(&mut V),
+{
+  Collect { visitor, on_done }
+}
+
+impl<V, F> Pass for Collect<V, F>
+where
+  V: VisitMut,
+  F: FnMut(&mut V),
+{
+  fn process(&mut self, program: &mut Program) {
+    program.visit_mut_with(&mut self.visitor);
+    (self.on_done)(&mut self.visitor);
+  }
+}
@@ -0,0 +1,195 @@
+use indexmap::IndexSet;
+use swc_core::{
+  atoms::Atom,
+  common::{util::take::Take, Mark, SyntaxContext, DUMMY_SP},
+  ecma::{
+    ast::*,
+    minifier::option::{CompressOptions, MangleOptions, MinifyOptions},
+    visit::{noop_visit_mut_type, noop_visit_type, Visit, VisitMut, VisitMutWith, VisitWith},
+  },
+};
+
+/// Names that the runtime looks up on the main thread, which are never mangled.
+const RUNTIME_NAMES: &[&str] = &["_EXTRACT_STR", "registerWorklet"];
+
+/// @public
+/// Options of the minifier of `transformBundleResult`.
+#[napi(object)]
+#[derive(Clone, Debug, Default)]
+pub struct MinifyConfig {
+  /// @public
+  /// Whether to compress the code, e.g. remove dead code and inline variables.
+  ///
+  /// @remarks
+  /// Default value: `true`.
+  pub compress: Option<bool>,
+  /// @public
+  /// Whether to mangle the names of local variables.
+  ///
+  /// @remarks
+  /// Top-level names are never mangled, since they are shared by the chunks.
+  ///
+  /// Default value: `true`.
+  pub mangle: Option<bool>,
+  /// @public
+  /// Whether to keep the names of functions.
+  ///
+  /// @remarks
+  /// Default value: `false`.
+  pub keep_fnames: Option<bool>,
+  /// @public
+  /// Names that are never mangled.
+  ///
+  /// @remarks
+  /// `_EXTRACT_STR`, `registerWorklet` and the Lynx PAPI globals like `__CreateView` are always kept.
+  pub reserved: Option<Vec<String>>,
+}
+
+impl MinifyConfig {
+  pub fn to_minify_options(&self, program: &Program) -> MinifyOptions {
+    let keep_fnames = self.keep_fnames.unwrap_or(false);
+
+    let mut reserved = RUNTIME_NAMES
+      .iter()
+      .map(|name| Atom::from(*name))
+      .collect::<IndexSet<_>>();
+    reserved.extend(
+      self
+        .reserved
+        .iter()
+        .flatten()
+        .map(|name| Atom::from(&**name)),
+    );
+    let mut collector = PapiCollector::default();
+    program.visit_with(&mut collector);
+    reserved.extend(collector.names);
+
+    MinifyOptions {
+      compress: self.compress.unwrap_or(true).then(|| CompressOptions {
+        keep_fnames,
+        ..Default::default()
+      }),
+      mangle: self.mangle.unwrap_or(true).then(|| MangleOptions {
+        top_level: Some(false),
+        keep_fn_names: keep_fnames,
+        reserved: reserved.into_iter().collect(),
+        ..Default::default()
+      }),
+      ..Default::default()
+    }
+  }
+}
+
+/// Collects the names of the Lynx PAPI, e.g. `__CreateView` and `__SetAttribute`,
+/// which may be declared by the bundle as well as by the runtime.
+#[derive(Default)]
+struct PapiCollector {
+  names: IndexSet<Atom>,
+}
+
+impl Visit for PapiCollector {
+  noop_visit_type!();
+
+  fn visit_ident(&mut self, n: &Ident) {
+    let is_papi = n
+      .sym
+      .strip_prefix("__")
+      .is_some_and(|name| name.starts_with(|c: char| c.is_ascii_uppercase()));
+    if is_papi {
+      self.names.insert(n.sym.clone());
+    }
+  }
+}
+
+/// The global that stands for the `_EXTRACT_STR` tables while minifying, see `ExtractStrTables`.
+const EXTRACT_STR_TABLE: &str = "__EXTRACT_STR_TABLE__";
+
+/// Hides the `_EXTRACT_STR` tables from the compressor, which would otherwise inline
+/// `_EXTRACT_STR[i]` back into the strings they replace.
+///
+/// Each table is replaced with an opaque call `__EXTRACT_STR_TABLE__(i)` before minifying,
+/// and restored wherever the call is moved to afterwards.
+pub struct ExtractStrTables {
+  unresolved_mark: Mark,
+  tables: Vec<Expr>,
+  restoring: bool,
+}
+
+impl ExtractStrTables {
+  pub fn new(unresolved_mark: Mark) -> Self {
+    Self {
+      unresolved_mark,
+      tables: vec![],
+      restoring: false,
+    }
+  }
+
+  pub fn hide(&mut self, program: &mut Program) {
+    self.restoring = false;
+    program.visit_mut_with(self);
+  }
+
+  pub fn restore(&mut self, program: &mut Program) {
+    self.restoring = true;
+    program.visit_mut_with(self);
+  }
+}
+
+impl VisitMut for ExtractStrTables {
+  noop_visit_mut_type!();
+
+  fn visit_mut_var_declarator(&mut self, n: &mut VarDeclarator) {
+    n.visit_mut_children_with(self);
+    if self.restoring {
+      return;
+    }
+    let (Pat::Ident(name), Some(init)) = (&n.name, &mut n.init) else {
+      return;
+    };
+    if name.sym != "_EXTRACT_STR" || !init.is_array() {
+      return;
+    }
+    let callee = Ident::new(
+      EXTRACT_STR_TABLE.into(),
+      DUMMY_SP,
+      SyntaxContext::empty().apply_mark(self.unresolved_mark),
+    );
+    let table = std::mem::replace(
+      init,
+      Box::new(Expr::Call(CallExpr {
+        callee: Callee::Expr(Box::new(callee.into())),
+        args: vec![Expr::from(self.tables.len() as f64).into()],
+        ..Default::default()
+      })),
+    );
+    self.tables.push(*table);
+  }
+
+  fn visit_mut_expr(&mut self, n: &mut Expr) {
+    n.visit_mut_children_with(self);
+    if !self.restoring {
+      return;
+    }
+    let Expr::Call(CallExpr {
+      callee: Callee::Expr(callee),
+      args,
+      ..
+    }) = n
+    else {
+      return;
+    };
+    let is_table = callee
+      .as_ident()
+      .is_some_and(|ident| ident.sym == EXTRACT_STR_TABLE);
+    let index = match args.as_slice() {
+      [ExprOrSpread { spread: None, expr }] => expr.as_lit().and_then(|lit| match lit {
+        Lit::Num(num) => Some(num.value as usize),
+        _ => None,
+      }),
+      _ => None,
+    };
+    if let (true, Some(table)) = (is_table, index.and_then(|i| self.tables.get_mut(i))) {
+      *n = table.take();
+    }
+  }
+}
@@ -0,0 +1,269 @@
+use napi::Either;
+use serde::Serialize;
+
+use crate::TransformNodiffOptions;
+
+/// A single pass of `transformReactLynx` that can be placed in
+/// `TransformNodiffOptions.pipeline`.
+///
+/// `fixer`, `resolver` and `hygiene` that wrap the whole pipeline are always
+/// applied and cannot be configured.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize)]
+pub enum TransformPass {
+  Typescript,
+  DynamicImport,
+  Refresh,
+  Compat,
+  Worklet,
+  CssScope,
+  List,
+  Snapshot,
+  DirectiveDCE,
+  DefineDCE,
+  Simplify,
+  Shake,
+  React,
+  Resolver,
+  CompatPost,
+  Inject,
+}
+
+impl TransformPass {
+  pub const ALL: [TransformPass; 16] = [
+    TransformPass::Typescript,
+    TransformPass::DynamicImport,
+    TransformPass::Refresh,
+    TransformPass::Compat,
+    TransformPass::Worklet,
+    TransformPass::CssScope,
+    TransformPass::List,
+    TransformPass::Snapshot,
+    TransformPass::DirectiveDCE,
+    TransformPass::DefineDCE,
+    TransformPass::Simplify,
+    TransformPass::Shake,
+    TransformPass::React,
+    TransformPass::Resolver,
+    TransformPass::CompatPost,
+    TransformPass::Inject,
+  ];
+
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      TransformPass::Typescript => "typescript",
+      TransformPass::DynamicImport => "dynamicImport",
+      TransformPass::Refresh => "refresh",
+      TransformPass::Compat => "compat",
+      TransformPass::Worklet => "worklet",
+      TransformPass::CssScope => "cssScope",
+      TransformPass::List => "list",
+      TransformPass::Snapshot => "snapshot",
+      TransformPass::DirectiveDCE => "directiveDCE",
+      TransformPass::DefineDCE => "defineDCE",
+      TransformPass::Simplify => "simplify",
+      TransformPass::Shake => "shake",
+      TransformPass::React => "react",
+      TransformPass::Resolver => "resolver",
+      TransformPass::CompatPost => "compatPost",
+      TransformPass::Inject => "inject",
+    }
+  }
+}
+
+impl std::str::FromStr for TransformPass {
+  type Err = ();
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    TransformPass::ALL
+      .into_iter()
+      .find(|pass| pass.as_str() == s)
+      .ok_or(())
+  }
+}
+
+impl napi::bindgen_prelude::FromNapiValue for TransformPass {
+  unsafe fn from_napi_value(
+    env: napi::bindgen_prelude::sys::napi_env,
+    napi_val: napi::bindgen_prelude::sys::napi_value,
+  ) -> napi::bindgen_prelude::Result<Self> {
+    let val = <&str>::from_napi_value(env, napi_val).map_err(|e| {
+      napi::bindgen_prelude::error!(
+        e.status,
+        "Failed to convert napi value into enum `{}`. {}",
+        "TransformPass",
+        e,
+      )
+    })?;
+    val.parse::<TransformPass>().map_err(|_| {
+      napi::bindgen_prelude::error!(
+        napi::bindgen_prelude::Status::InvalidArg,
+        "value `{}` does not match any variant of enum `{}`",
+        val,
+        "TransformPass"
+      )
+    })
+  }
+}
+
+impl napi::bindgen_prelude::ToNapiValue for TransformPass {
+  unsafe fn to_napi_value(
+    env: napi::bindgen_prelude::sys::napi_env,
+    val: Self,
+  ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+    <&str>::to_napi_value(env, val.as_str())
+  }
+}
+
+/// The pipeline used when `TransformNodiffOptions.pipeline` is not given.
+///
+/// The `simplify` right after DCE is only added when one of the DCE passes is
+/// enabled, to make `shake` below work better.
+pub fn default_pipeline(options: &TransformNodiffOptions) -> Vec<TransformPass> {
+  let dce_enabled = match &options.directive_dce {
+    Either::A(config) => *config,
+    Either::B(_) => true,
+  } || match &options.define_dce {
+    Either::A(config) => *config,
+    Either::B(_) => true,
+  };
+
+  let mut pipeline = vec![
+    TransformPass::Typescript,
+    TransformPass::DynamicImport,
+    TransformPass::Refresh,
+    TransformPass::Compat,
+    TransformPass::Worklet,
+    TransformPass::CssScope,
+    TransformPass::List,
+    TransformPass::Snapshot,
+    TransformPass::DirectiveDCE,
+    TransformPass::DefineDCE,
+  ];
+  if dce_enabled {
+    pipeline.push(TransformPass::Simplify);
+  }
+  pipeline.extend([
+    TransformPass::Shake,
+    TransformPass::Simplify,
+    TransformPass::React,
+    // TODO(hongzhiyuan.hzy): if `ident` we added above is correctly marked, this pass will be unnecessary
+    TransformPass::Resolver,
+    TransformPass::CompatPost,
+    TransformPass::Inject,
+  ]);
+  pipeline
+}
+
+/// Ordering constraints between passes, as `(before, after)` pairs.
+///
+/// - `compat`, `cssScope` and `list` rewrite JSX (or add the `@jsxCSSId` pragma) that `snapshot` consumes.
+/// - `react` compiles away all the remaining JSX, so every JSX-aware pass must run before it.
+const ORDERING: [(TransformPass, TransformPass); 7] = [
+  (TransformPass::Compat, TransformPass::Snapshot),
+  (TransformPass::CssScope, TransformPass::Snapshot),
+  (TransformPass::List, TransformPass::Snapshot),
+  (TransformPass::Compat, TransformPass::React),
+  (TransformPass::CssScope, TransformPass::React),
+  (TransformPass::List, TransformPass::React),
+  (TransformPass::Snapshot, TransformPass::React),
+];
+
+/// Check that `pipeline` is in an order the plugins can handle.
+///
+/// Passes may be repeated or omitted, but every occurrence of `before` must
+/// come before the first occurrence of `after`.
+pub fn validate_pipeline(pipeline: &[TransformPass]) -> Result<(), String> {
+  for (before, after) in ORDERING {
+    let Some(after_index) = pipeline.iter().position(|pass| *pass == after) else {
+      continue;
+    };
+    if let Some(before_index) = pipeline.iter().rposition(|pass| *pass == before) {
+      if before_index > after_index {
+        return Err(format!(
+          "Invalid pipeline: `{}` (at index {}) must run before `{}` (at index {})",
+          before.as_str(),
+          before_index,
+          after.as_str(),
+          after_index,
+        ));
+      }
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_default_pipeline_is_valid() {
+    let options = TransformNodiffOptions::default();
+    let pipeline = default_pipeline(&options);
+
+    assert!(validate_pipeline(&pipeline).is_ok());
+    assert_eq!(
+      pipeline
+        .iter()
+        .filter(|pass| **pass == TransformPass::Simplify)
+        .count(),
+      1
+    );
+
+    let options = TransformNodiffOptions {
+      directive_dce: Either::A(true),
+      ..Default::default()
+    };
+    assert_eq!(
+      default_pipeline(&options)
+        .iter()
+        .filter(|pass| **pass == TransformPass::Simplify)
+        .count(),
+      2
+    );
+  }
+
+  #[test]
+  fn test_pipeline_allows_repeated_passes() {
+    let pipeline = [
+      TransformPass::Typescript,
+      TransformPass::List,
+      TransformPass::Snapshot,
+      TransformPass::Simplify,
+      TransformPass::Inject,
+      TransformPass::Simplify,
+    ];
+
+    assert!(validate_pipeline(&pipeline).is_ok());
+  }
+
+  #[test]
+  fn test_pipeline_rejects_snapshot_before_list() {
+    let pipeline = [
+      TransformPass::Typescript,
+      TransformPass::Snapshot,
+      TransformPass::List,
+    ];
+
+    assert_eq!(
+      validate_pipeline(&pipeline),
+      Err("Invalid pipeline: `list` (at index 2) must run before `snapshot` (at index 1)".into())
+    );
+  }
+
+  #[test]
+  fn test_pipeline_rejects_jsx_pass_after_react() {
+    let pipeline = [TransformPass::React, TransformPass::CssScope];
+
+    assert!(validate_pipeline(&pipeline).is_err());
+  }
+
+  #[test]
+  fn test_transform_pass_names() {
+    for pass in TransformPass::ALL {
+      assert_eq!(pass.as_str().parse(), Ok(pass));
+    }
+    assert_eq!("fixer".parse::<TransformPass>(), Err(()));
+  }
+}
@@ -0,0 +1,114 @@
+use swc_core::ecma::{
+  ast::*,
+  visit::{noop_visit_type, Visit, VisitWith},
+};
+
+/// Profiling result of a single pass, see `TransformNodiffOptions.profile`.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct PassProfile {
+  /// The name of the pass, e.g. `snapshot`.
+  pub name: String,
+  /// Wall time spent in the pass, in milliseconds.
+  pub duration: f64,
+  /// Number of AST nodes before the pass.
+  pub nodes_before: u32,
+  /// Number of AST nodes after the pass.
+  pub nodes_after: u32,
+  /// Number of errors and warnings emitted by the pass.
+  pub diagnostics: u32,
+}
+
+/// Counts expressions, statements, module declarations, patterns, properties
+/// and JSX attributes/children, which is a good enough measure of AST size.
+#[derive(Default)]
+struct NodeCounter {
+  count: u32,
+}
+
+macro_rules! count {
+  ($($name:ident: $ty:ty),*) => {
+    $(
+      fn $name(&mut self, n: &$ty) {
+        self.count += 1;
+        n.visit_children_with(self);
+      }
+    )*
+  };
+}
+
+impl Visit for NodeCounter {
+  noop_visit_type!();
+
+  count!(
+    visit_expr: Expr,
+    visit_stmt: Stmt,
+    visit_module_decl: ModuleDecl,
+    visit_pat: Pat,
+    visit_prop: Prop,
+    visit_jsx_attr_or_spread: JSXAttrOrSpread,
+    visit_jsx_element_child: JSXElementChild
+  );
+}
+
+pub fn count_nodes(program: &Program) -> u32 {
+  let mut counter = NodeCounter::default();
+  program.visit_with(&mut counter);
+  counter.count
+}
+
+/// A monotonic timestamp in milliseconds.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub fn now() -> f64 {
+  use once_cell::sync::Lazy;
+  use std::time::Instant;
+
+  static START: Lazy<Instant> = Lazy::new(Instant::now);
+  START.elapsed().as_secs_f64() * 1000.0
+}
+
+/// A monotonic timestamp in milliseconds.
+///
+/// `std::time::Instant` is not available on `wasm32-unknown-unknown`, so it
+/// is imported from the host, see `src/wasm.js`.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub fn now() -> f64 {
+  #[link(wasm_import_module = "react_transform")]
+  extern "C" {
+    fn performance_now() -> f64;
+  }
+
+  unsafe { performance_now() }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap},
+    ecma::parser::{parse_file_as_program, EsSyntax, Syntax},
+  };
+
+  #[test]
+  fn test_count_nodes() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+      FileName::Anon.into(),
+      "import a from 'a';\nconst b = <view id={a} />;".to_string(),
+    );
+    let program = parse_file_as_program(
+      &fm,
+      Syntax::Es(EsSyntax {
+        jsx: true,
+        ..Default::default()
+      }),
+      EsVersion::latest(),
+      None,
+      &mut vec![],
+    )
+    .unwrap();
+
+    // import decl, var decl stmt, `b` pattern, JSX element, `id` attribute, `a` identifier
+    assert_eq!(count_nodes(&program), 6);
+  }
+}
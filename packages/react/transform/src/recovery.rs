@@ -0,0 +1,93 @@
+use swc_core::{
+  base::{config::IsModule, Compiler},
+  common::{comments::SingleThreadedComments, errors::Handler, sync::Lrc, SourceFile},
+  ecma::{
+    ast::{EsVersion, Program},
+    parser::{
+      parse_file_as_commonjs, parse_file_as_module, parse_file_as_program, parse_file_as_script,
+    },
+  },
+};
+
+use crate::{esbuild::PartialMessage, TransformNodiffOptions};
+
+/// Parse `fm`, emitting syntax errors to `handler`.
+///
+/// Without `options.recover`, this fails on any syntax error, like `Compiler::parse_js`.
+/// With it, the program recovered from the recoverable errors is returned instead,
+/// and only fatal errors fail.
+pub fn parse(
+  c: &Compiler,
+  fm: Lrc<SourceFile>,
+  handler: &Handler,
+  options: &TransformNodiffOptions,
+  comments: &SingleThreadedComments,
+) -> Option<Program> {
+  let syntax = options.syntax_config.unwrap_or_default().into();
+  let is_module: IsModule = options.is_module.unwrap_or_default().into();
+
+  if options.recover != Some(true) {
+    return c
+      .parse_js(
+        fm,
+        handler,
+        EsVersion::latest(),
+        syntax,
+        is_module,
+        Some(comments),
+      )
+      .ok();
+  }
+
+  let target = EsVersion::latest();
+  let mut errors = vec![];
+  let program = match is_module {
+    IsModule::Bool(true) => {
+      parse_file_as_module(&fm, syntax, target, Some(comments), &mut errors).map(Program::Module)
+    }
+    IsModule::Bool(false) => {
+      parse_file_as_script(&fm, syntax, target, Some(comments), &mut errors).map(Program::Script)
+    }
+    IsModule::CommonJS => {
+      parse_file_as_commonjs(&fm, syntax, target, Some(comments), &mut errors).map(Program::Script)
+    }
+    IsModule::Unknown => parse_file_as_program(&fm, syntax, target, Some(comments), &mut errors),
+  };
+
+  for error in errors {
+    error.into_diagnostic(handler).emit();
+  }
+  program
+    .map_err(|error| error.into_diagnostic(handler).emit())
+    .ok()
+}
+
+/// A module that throws `errors` when it is evaluated, which takes the place of
+/// a module that cannot be parsed, see `TransformNodiffOptions.recover`.
+///
+/// The locations are printed as `file:line:column` with 1-based columns like other tools,
+/// while the columns of esbuild messages are 0-based.
+pub fn stub_module(errors: &[PartialMessage]) -> String {
+  let message = errors
+    .iter()
+    .map(|error| {
+      let text = error.text.as_deref().unwrap_or_default();
+      match &error.location {
+        Some(location) => format!(
+          "{}:{}:{}: {}",
+          location.file.as_deref().unwrap_or_default(),
+          location.line.unwrap_or_default(),
+          location.column.unwrap_or_default() + 1,
+          text
+        ),
+        None => text.to_string(),
+      }
+    })
+    .collect::<Vec<_>>()
+    .join("\n");
+
+  format!(
+    "throw new SyntaxError({});\n",
+    serde_json::to_string(&message).unwrap_or_default()
+  )
+}
@@ -0,0 +1,254 @@
+use convert_case::{Case, Casing};
+use serde_json::{json, Value};
+use swc_plugins_shared::diagnostics::{DefaultLevel, CODES};
+
+use crate::esbuild::{PartialLocation, PartialMessage};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// The SARIF `reportingDescriptor`s of every diagnostic code, in the order of `CODES`.
+fn rules() -> Vec<Value> {
+  CODES
+    .iter()
+    .map(|code| {
+      json!({
+        "id": code.id,
+        "name": code.name.from_case(Case::UpperSnake).to_case(Case::Pascal),
+        "shortDescription": { "text": code.description },
+        "defaultConfiguration": {
+          "level": match code.level {
+            DefaultLevel::Warning => "warning",
+            DefaultLevel::Error => "error",
+          },
+        },
+      })
+    })
+    .collect()
+}
+
+/// The 0-based end column in chars of a span starting at the char `column` of `line_text`,
+/// which is `length` bytes long, or `None` if the span does not end within the line.
+fn end_column(line_text: &str, column: u32, length: u32) -> Option<u32> {
+  let start = match line_text.char_indices().nth(column as usize) {
+    Some((start, _)) => start,
+    None if column as usize == line_text.chars().count() => line_text.len(),
+    None => return None,
+  };
+  let end = start.checked_add(length as usize)?;
+  // `get` also rejects ends in the middle of a char.
+  line_text.get(..end).map(|text| text.chars().count() as u32)
+}
+
+/// The SARIF `physicalLocation` of an esbuild location.
+fn physical_location(location: &PartialLocation) -> Value {
+  let mut region = json!({});
+  if let Some(line) = location.line {
+    region["startLine"] = line.into();
+  }
+  if let Some(column) = location.column {
+    // esbuild columns are 0-based, SARIF columns are 1-based.
+    region["startColumn"] = (column + 1).into();
+    if let (Some(length), Some(line_text)) = (location.length, &location.line_text) {
+      if let Some(end_column) = end_column(line_text, column, length) {
+        region["endColumn"] = (end_column + 1).into();
+      }
+    }
+  }
+
+  json!({
+    "artifactLocation": { "uri": location.file.as_deref().unwrap_or_default() },
+    "region": region,
+  })
+}
+
+/// The SARIF `result` of an esbuild message.
+fn result(message: &PartialMessage, level: &str) -> Value {
+  let notes = message.notes.as_deref().unwrap_or_default();
+  // Notes without a location are appended to the message, like esbuild prints them.
+  let text = std::iter::once(message.text.as_deref().unwrap_or_default())
+    .chain(
+      notes
+        .iter()
+        .filter(|note| note.location.is_none())
+        .filter_map(|note| note.text.as_deref()),
+    )
+    .collect::<Vec<_>>()
+    .join("\n");
+
+  let mut result = json!({
+    "level": level,
+    "message": { "text": text },
+  });
+  if let Some(id) = &message.id {
+    result["ruleId"] = id.as_str().into();
+    if let Some(index) = CODES.iter().position(|code| code.id == id) {
+      result["ruleIndex"] = index.into();
+    }
+  }
+  if let Some(location) = &message.location {
+    result["locations"] = json!([{ "physicalLocation": physical_location(location) }]);
+  }
+  let related_locations = notes
+    .iter()
+    .filter_map(|note| {
+      Some((
+        note.text.as_deref().unwrap_or_default(),
+        note.location.as_ref()?,
+      ))
+    })
+    .enumerate()
+    .map(|(index, (text, location))| {
+      json!({
+        "id": index,
+        "message": { "text": text },
+        "physicalLocation": physical_location(location),
+      })
+    })
+    .collect::<Vec<_>>();
+  if !related_locations.is_empty() {
+    result["relatedLocations"] = related_locations.into();
+  }
+
+  result
+}
+
+/// Serialize `errors` and `warnings` into a SARIF 2.1.0 log with a single run,
+/// which has every diagnostic code of the plugins as its rules.
+pub fn format_sarif(errors: &[PartialMessage], warnings: &[PartialMessage]) -> String {
+  let results = errors
+    .iter()
+    .map(|error| result(error, "error"))
+    .chain(warnings.iter().map(|warning| result(warning, "warning")))
+    .collect::<Vec<_>>();
+
+  let log = json!({
+    "$schema": SARIF_SCHEMA,
+    "version": "2.1.0",
+    "runs": [{
+      "tool": {
+        "driver": {
+          "name": "ReactLynx",
+          "version": env!("LYNX_REACT_VERSION"),
+          "rules": rules(),
+        },
+      },
+      "results": results,
+    }],
+  });
+  serde_json::to_string_pretty(&log).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::esbuild::PartialNote;
+
+  #[test]
+  fn test_format_sarif() {
+    let location = |line: u32, column: u32, length: u32| PartialLocation {
+      file: Some("src/App.jsx".into()),
+      namespace: None,
+      line: Some(line),
+      column: Some(column),
+      length: Some(length),
+      line_text: Some("<view lynx-key={a} />".into()),
+      suggestion: None,
+    };
+    let warning = PartialMessage {
+      id: Some("RL1005".into()),
+      plugin_name: Some("test".into()),
+      text: Some("`lynx-key` is deprecated".into()),
+      location: Some(location(1, 6, 8)),
+      notes: Some(vec![
+        PartialNote {
+          text: Some("use `key` instead".into()),
+          location: Some(location(1, 6, 100)),
+        },
+        PartialNote {
+          text: Some("some note".into()),
+          location: None,
+        },
+      ]),
+      detail: None,
+    };
+    let error = PartialMessage {
+      id: None,
+      plugin_name: Some("test".into()),
+      text: Some("syntax error".into()),
+      location: None,
+      notes: None,
+      detail: None,
+    };
+
+    let log: Value = serde_json::from_str(&format_sarif(&[error], &[warning])).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["version"], env!("LYNX_REACT_VERSION"));
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), CODES.len());
+    assert_eq!(
+      rules[1],
+      json!({
+        "id": "RL1001",
+        "name": "DeprecatedComponentsPkg",
+        "shortDescription": { "text": "`compat`: the old components package is removed." },
+        "defaultConfiguration": { "level": "warning" },
+      })
+    );
+    assert_eq!(
+      run["results"],
+      json!([
+        {
+          "level": "error",
+          "message": { "text": "syntax error" },
+        },
+        {
+          "level": "warning",
+          "message": { "text": "`lynx-key` is deprecated\nsome note" },
+          "ruleId": "RL1005",
+          "ruleIndex": 5,
+          "locations": [{
+            "physicalLocation": {
+              "artifactLocation": { "uri": "src/App.jsx" },
+              "region": { "startLine": 1, "startColumn": 7, "endColumn": 15 },
+            },
+          }],
+          "relatedLocations": [{
+            "id": 0,
+            "message": { "text": "use `key` instead" },
+            "physicalLocation": {
+              "artifactLocation": { "uri": "src/App.jsx" },
+              "region": { "startLine": 1, "startColumn": 7 },
+            },
+          }],
+        },
+      ])
+    );
+  }
+
+  #[test]
+  fn test_end_column() {
+    // `length` is in bytes while `column` is in chars
+    let line_text = "<text>\u{4f60}\u{597d}</text>; <view lynx-key={a} />";
+    assert_eq!(end_column(line_text, 6, 6), Some(8));
+    assert_eq!(end_column(line_text, 23, 8), Some(31));
+    assert_eq!(end_column(line_text, 6, 5), None);
+    assert_eq!(end_column(line_text, 23, 100), None);
+    assert_eq!(end_column("abc", 3, 0), Some(3));
+    assert_eq!(end_column("abc", 4, 0), None);
+
+    let location = PartialLocation {
+      file: Some("src/App.jsx".into()),
+      namespace: None,
+      line: Some(1),
+      column: Some(23),
+      length: Some(8),
+      line_text: Some(line_text.into()),
+      suggestion: None,
+    };
+    assert_eq!(
+      physical_location(&location)["region"],
+      json!({ "startLine": 1, "startColumn": 24, "endColumn": 32 })
+    );
+  }
+}
@@ -1,12 +1,65 @@
+use indexmap::{IndexMap, IndexSet};
 use napi_derive::napi;
 use serde::{Deserialize, Serialize};
 use std::vec;
 use swc_core::{
-  common::DUMMY_SP,
+  common::{util::take::Take, Spanned, DUMMY_SP},
   ecma::ast::*,
-  ecma::visit::{VisitMut, VisitMutWith},
+  ecma::visit::{Visit, VisitMut, VisitMutWith, VisitWith},
   quote,
 };
+use swc_plugins_shared::jsx_helpers::jsx_text_to_str;
+
+/// {@inheritdoc ExtractStrConfig.strategy}
+/// @public
+#[derive(PartialEq, Serialize, Deserialize, Clone, Copy, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum ExtractStrStrategy {
+  /// Extract every string literal of at least `strLength`, in the order they are first seen.
+  #[default]
+  Length,
+  /// Extract the string literals of at least `strLength` whose extraction makes the bundles smaller,
+  /// the most frequent first.
+  Size,
+}
+
+impl napi::bindgen_prelude::FromNapiValue for ExtractStrStrategy {
+  unsafe fn from_napi_value(
+    env: napi::bindgen_prelude::sys::napi_env,
+    napi_val: napi::bindgen_prelude::sys::napi_value,
+  ) -> napi::bindgen_prelude::Result<Self> {
+    let val = <&str>::from_napi_value(env, napi_val).map_err(|e| {
+      napi::bindgen_prelude::error!(
+        e.status,
+        "Failed to convert napi value into enum `{}`. {}",
+        "ExtractStrStrategy",
+        e,
+      )
+    })?;
+    match val {
+      "length" => Ok(ExtractStrStrategy::Length),
+      "size" => Ok(ExtractStrStrategy::Size),
+      _ => Err(napi::bindgen_prelude::error!(
+        napi::bindgen_prelude::Status::InvalidArg,
+        "value `{}` does not match any variant of enum `{}`",
+        val,
+        "ExtractStrStrategy"
+      )),
+    }
+  }
+}
+
+impl napi::bindgen_prelude::ToNapiValue for ExtractStrStrategy {
+  unsafe fn to_napi_value(
+    env: napi::bindgen_prelude::sys::napi_env,
+    val: Self,
+  ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+    match val {
+      ExtractStrStrategy::Length => <&str>::to_napi_value(env, "length"),
+      ExtractStrStrategy::Size => <&str>::to_napi_value(env, "size"),
+    }
+  }
+}
 
 /// {@inheritdoc PluginReactLynxOptions.extractStr}
 /// @public
@@ -39,6 +92,55 @@ pub struct ExtractStrConfig {
   pub str_length: u32,
   /// @internal
   pub extracted_str_arr: Option<Vec<String>>,
+  /// @public
+  /// How string literals are selected for extraction.
+  ///
+  /// @remarks
+  /// - `'length'`: every string literal of at least `strLength`, in the order they are first seen.
+  /// - `'size'`: only the string literals of at least `strLength` that occur often enough for
+  ///   `_EXTRACT_STR[i]` and the table entry to be smaller than the literals they replace,
+  ///   the most frequent first so that they get the shortest indexes.
+  ///
+  /// Default value: `'length'`.
+  #[napi(ts_type = "'length' | 'size'")]
+  pub strategy: Option<ExtractStrStrategy>,
+  /// @public
+  /// A string table shared by several bundles, in the format of `extractStrManifest` of the output.
+  ///
+  /// @remarks
+  /// The strings of the table keep their indexes in `_EXTRACT_STR`, and the main thread bundle appends
+  /// the new strings it extracts to the end of the table, in a deterministic order.
+  /// Transform the bundles one after another with the `extractStrManifest` of the previous one,
+  /// and pass the `strings` of the final table as `extractedStrArr` of the background bundles.
+  pub manifest: Option<String>,
+  /// @public
+  /// Whether to extract the strings of template literals, string property keys and JSX texts as well.
+  ///
+  /// @remarks
+  /// `` `${a}long string` `` becomes `` `${a}${_EXTRACT_STR[i]}` ``, `{ "long key": a }` becomes
+  /// `{ [_EXTRACT_STR[i]]: a }` and `<text>long text</text>` becomes `<text>{_EXTRACT_STR[i]}</text>`.
+  /// Tagged templates and `__proto__` keys are never extracted.
+  ///
+  /// Default value: `false`.
+  pub all_positions: Option<bool>,
+}
+
+/// The string table shared by several bundles, see `ExtractStrConfig.manifest`.
+#[derive(PartialEq, Serialize, Deserialize, Clone, Debug)]
+pub struct ExtractStrManifest {
+  pub version: u32,
+  pub strings: Vec<String>,
+}
+
+impl ExtractStrManifest {
+  pub const VERSION: u32 = 1;
+
+  pub fn new(strings: Vec<String>) -> Self {
+    Self {
+      version: Self::VERSION,
+      strings,
+    }
+  }
 }
 
 impl Default for ExtractStrConfig {
@@ -46,13 +148,135 @@ impl Default for ExtractStrConfig {
     ExtractStrConfig {
       str_length: 20,
       extracted_str_arr: None,
+      strategy: None,
+      manifest: None,
+      all_positions: None,
     }
   }
 }
 
+/// The occurrences of a string, see `StrCounter`.
+#[derive(Default)]
+struct StrOccurrences {
+  count: usize,
+  /// The total length of the occurrences, including quotes and escapes.
+  bytes: usize,
+  /// The total length of the syntax needed around `_EXTRACT_STR[i]`, e.g. `${}` in template literals.
+  overhead: usize,
+}
+
+/// Counts the strings that `ExtractStrVisitor` may replace.
+#[derive(Default)]
+struct StrCounter {
+  occurrences: IndexMap<String, StrOccurrences>,
+  /// See `ExtractStrConfig.allPositions`.
+  all_positions: bool,
+}
+
+impl StrCounter {
+  fn add(&mut self, value: &str, bytes: usize, overhead: usize) {
+    let occurrences = self.occurrences.entry(value.to_string()).or_default();
+    occurrences.count += 1;
+    occurrences.bytes += bytes;
+    occurrences.overhead += overhead;
+  }
+}
+
+impl Visit for StrCounter {
+  fn visit_expr_stmt(&mut self, n: &ExprStmt) {
+    if !is_directive(n) {
+      n.visit_children_with(self);
+    }
+  }
+
+  fn visit_expr(&mut self, expr: &Expr) {
+    match expr {
+      Expr::Lit(Lit::Str(str)) => self.add(&str.value, str_lit_len(str), 0),
+      _ => expr.visit_children_with(self),
+    }
+  }
+
+  fn visit_tpl(&mut self, tpl: &Tpl) {
+    tpl.visit_children_with(self);
+    if !self.all_positions {
+      return;
+    }
+    for quasi in &tpl.quasis {
+      if let Some(cooked) = &quasi.cooked {
+        self.add(cooked, quasi.raw.len(), TPL_OVERHEAD);
+      }
+    }
+  }
+
+  fn visit_tagged_tpl(&mut self, n: &TaggedTpl) {
+    n.tag.visit_with(self);
+    n.tpl.exprs.visit_with(self);
+  }
+
+  fn visit_prop(&mut self, n: &Prop) {
+    n.visit_children_with(self);
+    if !self.all_positions {
+      return;
+    }
+    if let Some(str) = prop_str_key(n) {
+      self.add(&str.value, str_lit_len(str), COMPUTED_KEY_OVERHEAD);
+    }
+  }
+
+  fn visit_jsx_text(&mut self, n: &JSXText) {
+    if !self.all_positions {
+      return;
+    }
+    let value = jsx_text_to_str(&n.value);
+    if !value.is_empty() {
+      self.add(&value, n.raw.len(), JSX_TEXT_OVERHEAD);
+    }
+  }
+}
+
+/// The length of `${}` around a string extracted from a template literal.
+const TPL_OVERHEAD: usize = 3;
+/// The length of `[]` around a string extracted from a property key.
+const COMPUTED_KEY_OVERHEAD: usize = 2;
+/// The length of `{}` around a string extracted from a JSX text.
+const JSX_TEXT_OVERHEAD: usize = 2;
+
+/// The length of a string literal in the output code.
+fn str_lit_len(str: &Str) -> usize {
+  match &str.raw {
+    Some(raw) => raw.len(),
+    None => str.value.len() + 2,
+  }
+}
+
+/// The string key of an object literal property that can be made computed.
+///
+/// `__proto__` is skipped, since `{ __proto__: a }` sets the prototype but `{ ["__proto__"]: a }` does not.
+fn prop_str_key(n: &Prop) -> Option<&Str> {
+  let key = match n {
+    Prop::KeyValue(KeyValueProp { key, .. })
+    | Prop::Method(MethodProp { key, .. })
+    | Prop::Getter(GetterProp { key, .. })
+    | Prop::Setter(SetterProp { key, .. }) => key,
+    Prop::Shorthand(_) | Prop::Assign(_) => return None,
+  };
+  match key {
+    PropName::Str(str) if &*str.value != "__proto__" => Some(str),
+    _ => None,
+  }
+}
+
 pub struct ExtractStrVisitor {
   opts: ExtractStrConfig,
   pub select_str_vec: Vec<String>,
+  /// The estimated number of bytes saved by the extraction,
+  /// i.e. the length of the replaced literals minus the length of `_EXTRACT_STR[i]`s and of the table.
+  pub saved_bytes: i64,
+  /// The strings of at least `str_length` that are not in `extracted_str_arr`, and are left inline.
+  unresolved: IndexSet<String>,
+  /// The number of the references to the table, i.e. the strings replaced with `_EXTRACT_STR[i]`
+  /// and the `__EXTRACT_STR_IDENT_FLAG__`s replaced with `_EXTRACT_STR`.
+  replaced: usize,
   extracted_str_arr: Option<Vec<String>>,
   arr_name: Ident,
   is_found_str_flag: bool,
@@ -69,19 +293,145 @@ impl ExtractStrVisitor {
     ExtractStrVisitor {
       opts: opts.clone(),
       select_str_vec: vec![],
+      saved_bytes: 0,
+      unresolved: Default::default(),
+      replaced: 0,
       extracted_str_arr: opts.extracted_str_arr,
       arr_name: IdentName::new("_EXTRACT_STR".into(), DUMMY_SP).into(),
       is_found_str_flag: false,
     }
   }
-}
 
-impl VisitMut for ExtractStrVisitor {
-  fn visit_mut_module(&mut self, n: &mut Module) {
-    n.visit_mut_children_with(self);
-    if self.opts.extracted_str_arr.is_some() {
-      return;
+  /// Start the table of the main thread bundle with the strings of `manifest`.
+  pub fn with_manifest(self, manifest: ExtractStrManifest) -> Self {
+    Self {
+      select_str_vec: manifest.strings,
+      ..self
     }
+  }
+
+  /// The table after the main thread bundle, `None` for the background bundle.
+  pub fn manifest(&self) -> Option<ExtractStrManifest> {
+    self
+      .extracted_str_arr
+      .is_none()
+      .then(|| ExtractStrManifest::new(self.select_str_vec.clone()))
+  }
+
+  /// The strings left inline since they are not in the table, `None` for the main thread bundle.
+  pub fn unresolved(&self) -> Option<Vec<String>> {
+    self
+      .extracted_str_arr
+      .is_some()
+      .then(|| self.unresolved.iter().cloned().collect())
+  }
+
+  fn strategy(&self) -> ExtractStrStrategy {
+    self.opts.strategy.unwrap_or_default()
+  }
+
+  fn all_positions(&self) -> bool {
+    self.opts.all_positions.unwrap_or(false)
+  }
+
+  /// Whether strings are replaced at this point.
+  /// In the background bundle, only the strings after `__EXTRACT_STR_FLAG__` are.
+  fn is_replacing(&self) -> bool {
+    self.extracted_str_arr.is_none() || self.is_found_str_flag
+  }
+
+  /// The index of `value` in the table, if it should be extracted.
+  ///
+  /// In the main thread bundle, `value` is added to the table with the `'length'` strategy.
+  fn index_of(&mut self, value: &str) -> Option<usize> {
+    if value.len() < self.opts.str_length as usize {
+      return None;
+    }
+    match &self.extracted_str_arr {
+      // js
+      Some(arr) => {
+        let position = arr.iter().position(|x| x == value);
+        if position.is_none() {
+          self.unresolved.insert(value.to_string());
+        }
+        position
+      }
+      // lepus
+      None => match (
+        self.select_str_vec.iter().position(|x| x == value),
+        self.strategy(),
+      ) {
+        (Some(i), _) => Some(i),
+        // strings are selected beforehand by `select_by_size`
+        (None, ExtractStrStrategy::Size) => None,
+        (None, ExtractStrStrategy::Length) => {
+          self.select_str_vec.push(value.to_string());
+          Some(self.select_str_vec.len() - 1)
+        }
+      },
+    }
+  }
+
+  /// `_EXTRACT_STR[index]`, replacing `bytes` of code with it and `overhead` bytes of syntax around it.
+  fn member_expr(&mut self, index: usize, bytes: usize, overhead: usize) -> Expr {
+    self.saved_bytes += bytes as i64 - (self.member_expr_len(index) + overhead) as i64;
+    self.replaced += 1;
+    Expr::Member(MemberExpr {
+      span: DUMMY_SP,
+      obj: Box::new(Expr::Ident(self.arr_name.clone())),
+      prop: MemberProp::Computed(ComputedPropName {
+        span: DUMMY_SP,
+        expr: Box::new(Expr::Lit(Lit::Num(Number {
+          value: index as f64,
+          span: DUMMY_SP,
+          raw: None,
+        }))),
+      }),
+    })
+  }
+
+  /// The length of `_EXTRACT_STR[index]`.
+  fn member_expr_len(&self, index: usize) -> usize {
+    self.arr_name.sym.len() + 2 + index.to_string().len()
+  }
+
+  /// Select the strings of `n` to be extracted with the `'size'` strategy,
+  /// most frequent first, skipping those whose extraction does not save any bytes.
+  fn select_by_size<N: VisitWith<StrCounter>>(&mut self, n: &N) {
+    let mut counter = StrCounter {
+      all_positions: self.all_positions(),
+      ..Default::default()
+    };
+    n.visit_with(&mut counter);
+
+    let mut candidates = counter
+      .occurrences
+      .into_iter()
+      .filter(|(value, _)| {
+        value.len() >= self.opts.str_length as usize && !self.select_str_vec.contains(value)
+      })
+      .collect::<Vec<_>>();
+    // A stable sort keeps strings with the same count in the order they are first seen.
+    candidates.sort_by(|(_, a), (_, b)| b.count.cmp(&a.count));
+
+    for (value, occurrences) in candidates {
+      let index = self.select_str_vec.len();
+      let cost = occurrences.count * self.member_expr_len(index)
+        + occurrences.overhead
+        + table_entry_len(&value);
+      if occurrences.bytes > cost {
+        self.select_str_vec.push(value);
+      }
+    }
+  }
+
+  /// The declaration of the `_EXTRACT_STR` table of the main thread bundle.
+  fn table_stmt(&mut self) -> Stmt {
+    self.saved_bytes -= self
+      .select_str_vec
+      .iter()
+      .map(|s| table_entry_len(s) as i64)
+      .sum::<i64>();
     let str_arr = self
       .select_str_vec
       .iter()
@@ -97,23 +447,128 @@ impl VisitMut for ExtractStrVisitor {
         })
       })
       .collect::<Vec<Option<ExprOrSpread>>>();
-    let stmt = quote!(
+    quote!(
         r#"var $name = $arr;"# as Stmt,
         name = self.arr_name.clone(),
         arr: Expr = Expr::Array(ArrayLit {
             span: DUMMY_SP,
             elems: str_arr
         })
-    );
+    )
+  }
+}
+
+/// The body of the IIFE of `stmt`, e.g. `(function () { ... })()`, `!function () { ... }()`,
+/// `(() => { ... })()` and `(function () { ... }).call(this)`.
+fn iife_body(stmt: &mut Stmt) -> Option<&mut Vec<Stmt>> {
+  let Stmt::Expr(ExprStmt { expr, .. }) = stmt else {
+    return None;
+  };
+  let mut expr = &mut **expr;
+  while let Expr::Paren(ParenExpr { expr: inner, .. }) | Expr::Unary(UnaryExpr { arg: inner, .. }) =
+    expr
+  {
+    expr = &mut **inner;
+  }
+  match expr {
+    Expr::Call(CallExpr {
+      callee: Callee::Expr(callee),
+      ..
+    }) => fn_body(callee, true),
+    _ => None,
+  }
+}
+
+/// The body of the function `callee`, or of `callee` of `callee.call()` if `allow_call`.
+fn fn_body(callee: &mut Expr, allow_call: bool) -> Option<&mut Vec<Stmt>> {
+  match callee {
+    Expr::Paren(ParenExpr { expr, .. }) => fn_body(expr, allow_call),
+    Expr::Member(MemberExpr {
+      obj,
+      prop: MemberProp::Ident(prop),
+      ..
+    }) if allow_call && (prop.sym == "call" || prop.sym == "apply") => fn_body(obj, false),
+    Expr::Fn(FnExpr { function, .. }) => function.body.as_mut().map(|body| &mut body.stmts),
+    Expr::Arrow(ArrowExpr { body, .. }) => match &mut **body {
+      BlockStmtOrExpr::BlockStmt(body) => Some(&mut body.stmts),
+      BlockStmtOrExpr::Expr(_) => None,
+    },
+    _ => None,
+  }
+}
+
+/// Whether `n` is a string literal statement, e.g. the directive `"use strict"`.
+fn is_directive(n: &ExprStmt) -> bool {
+  matches!(&*n.expr, Expr::Lit(Lit::Str(_)))
+}
+
+/// The number of the directives at the start of `stmts`.
+fn directive_count(stmts: &[Stmt]) -> usize {
+  stmts
+    .iter()
+    .take_while(|stmt| matches!(stmt, Stmt::Expr(n) if is_directive(n)))
+    .count()
+}
+
+/// The length of `value` in the `_EXTRACT_STR` table, including quotes and a comma.
+fn table_entry_len(value: &str) -> usize {
+  value.len() + 3
+}
+
+fn empty_quasi(tail: bool) -> TplElement {
+  TplElement {
+    span: DUMMY_SP,
+    tail,
+    cooked: Some("".into()),
+    raw: "".into(),
+  }
+}
+
+impl VisitMut for ExtractStrVisitor {
+  fn visit_mut_module(&mut self, n: &mut Module) {
+    if self.opts.extracted_str_arr.is_none() && self.strategy() == ExtractStrStrategy::Size {
+      self.select_by_size(n);
+    }
+    n.visit_mut_children_with(self);
+    if self.opts.extracted_str_arr.is_some() {
+      return;
+    }
+    let stmt = self.table_stmt();
     n.body.insert(0, ModuleItem::Stmt(stmt));
   }
+  fn visit_mut_script(&mut self, n: &mut Script) {
+    if self.opts.extracted_str_arr.is_none() && self.strategy() == ExtractStrStrategy::Size {
+      self.select_by_size(n);
+    }
+    let mut stmts_with_strs = vec![];
+    for (i, stmt) in n.body.iter_mut().enumerate() {
+      let replaced = self.replaced;
+      stmt.visit_mut_with(self);
+      if self.replaced > replaced {
+        stmts_with_strs.push(i);
+      }
+    }
+    if self.opts.extracted_str_arr.is_some() {
+      return;
+    }
+    let stmt = self.table_stmt();
+    // Keep the table in the scope of the IIFE of the bundle if all the references to it are there.
+    if let [i] = stmts_with_strs[..] {
+      if let Some(body) = iife_body(&mut n.body[i]) {
+        body.insert(directive_count(body), stmt);
+        return;
+      }
+    }
+    n.body.insert(directive_count(&n.body), stmt);
+  }
   fn visit_mut_ident(&mut self, i: &mut Ident) {
     if i.sym.as_ref() == "__EXTRACT_STR_IDENT_FLAG__" {
       *i = self.arr_name.clone();
+      self.replaced += 1;
     }
   }
   fn visit_mut_expr(&mut self, expr: &mut Expr) {
-    if self.extracted_str_arr.is_some() && !self.is_found_str_flag {
+    if !self.is_replacing() {
       match expr {
         Expr::Call(CallExpr {
           callee: Callee::Expr(callee_expr),
@@ -147,51 +602,9 @@ impl VisitMut for ExtractStrVisitor {
     } else {
       match expr {
         Expr::Lit(Lit::Str(str)) => {
-          if str.value.to_string().len() < self.opts.str_length as usize {
-            return;
+          if let Some(index) = self.index_of(&str.value) {
+            *expr = self.member_expr(index, str_lit_len(str), 0);
           }
-          let index: f64 = match &self.extracted_str_arr {
-            Some(arr) => {
-              // js
-              let position = arr.iter().position(|x| x == str.value.as_ref());
-              match position {
-                Some(i) => i as f64,
-                None => {
-                  expr.visit_mut_children_with(self);
-                  return;
-                }
-              }
-            }
-            None => {
-              // lepus
-              let position = self
-                .select_str_vec
-                .iter()
-                .position(|x| x == str.value.as_ref());
-              match position {
-                Some(i) => i as f64,
-                None => {
-                  let i = self.select_str_vec.len();
-                  self.select_str_vec.push(str.value.to_string());
-                  i as f64
-                }
-              }
-            }
-          };
-          let container = Expr::Ident(self.arr_name.clone());
-          let index_expr = Expr::Lit(Lit::Num(Number {
-            value: index,
-            span: DUMMY_SP,
-            raw: None,
-          }));
-          *expr = Expr::Member(MemberExpr {
-            span: DUMMY_SP,
-            obj: Box::new(container),
-            prop: MemberProp::Computed(ComputedPropName {
-              span: DUMMY_SP,
-              expr: Box::new(index_expr),
-            }),
-          });
         }
         _ => {
           expr.visit_mut_children_with(self);
@@ -199,6 +612,95 @@ impl VisitMut for ExtractStrVisitor {
       }
     }
   }
+  /// `` `${a}long string` `` becomes `` `${a}${_EXTRACT_STR[i]}` `` with `allPositions`.
+  fn visit_mut_tpl(&mut self, tpl: &mut Tpl) {
+    tpl.visit_mut_children_with(self);
+    if !self.all_positions() || !self.is_replacing() {
+      return;
+    }
+
+    let mut exprs = tpl.exprs.take().into_iter();
+    let mut quasis = vec![];
+    for quasi in tpl.quasis.take() {
+      match quasi
+        .cooked
+        .as_ref()
+        .and_then(|cooked| self.index_of(cooked))
+      {
+        Some(index) => {
+          let member_expr = self.member_expr(index, quasi.raw.len(), TPL_OVERHEAD);
+          quasis.push(empty_quasi(false));
+          tpl.exprs.push(Box::new(member_expr));
+          quasis.push(empty_quasi(quasi.tail));
+        }
+        None => quasis.push(quasi),
+      }
+      tpl.exprs.extend(exprs.next());
+    }
+    tpl.quasis = quasis;
+  }
+  /// Directives like `"use strict"` are never extracted.
+  fn visit_mut_expr_stmt(&mut self, n: &mut ExprStmt) {
+    if !is_directive(n) {
+      n.visit_mut_children_with(self);
+    }
+  }
+  /// The strings of tagged templates are passed to the tag as is, so they are never extracted.
+  fn visit_mut_tagged_tpl(&mut self, n: &mut TaggedTpl) {
+    n.tag.visit_mut_with(self);
+    n.tpl.exprs.visit_mut_with(self);
+  }
+  /// `{ "long key": a }` becomes `{ [_EXTRACT_STR[i]]: a }` with `allPositions`.
+  fn visit_mut_prop(&mut self, n: &mut Prop) {
+    n.visit_mut_children_with(self);
+    if !self.all_positions() || !self.is_replacing() {
+      return;
+    }
+
+    let Some(str) = prop_str_key(n) else {
+      return;
+    };
+    let (value, bytes) = (str.value.clone(), str_lit_len(str));
+    let Some(index) = self.index_of(&value) else {
+      return;
+    };
+    let member_expr = self.member_expr(index, bytes, COMPUTED_KEY_OVERHEAD);
+    if let Prop::KeyValue(KeyValueProp { key, .. })
+    | Prop::Method(MethodProp { key, .. })
+    | Prop::Getter(GetterProp { key, .. })
+    | Prop::Setter(SetterProp { key, .. }) = n
+    {
+      *key = PropName::Computed(ComputedPropName {
+        span: key.span(),
+        expr: Box::new(member_expr),
+      });
+    }
+  }
+  /// `<text>long text</text>` becomes `<text>{_EXTRACT_STR[i]}</text>` with `allPositions`.
+  ///
+  /// The string is the text with the whitespaces trimmed as JSX does, so the children are the same.
+  fn visit_mut_jsx_element_child(&mut self, n: &mut JSXElementChild) {
+    n.visit_mut_children_with(self);
+    if !self.all_positions() || !self.is_replacing() {
+      return;
+    }
+
+    let JSXElementChild::JSXText(text) = n else {
+      return;
+    };
+    let value = jsx_text_to_str(&text.value);
+    if value.is_empty() {
+      return;
+    }
+    let Some(index) = self.index_of(&value) else {
+      return;
+    };
+    let member_expr = self.member_expr(index, text.raw.len(), JSX_TEXT_OVERHEAD);
+    *n = JSXElementChild::JSXExprContainer(JSXExprContainer {
+      span: text.span,
+      expr: JSXExpr::Expr(Box::new(member_expr)),
+    });
+  }
 }
 
 #[cfg(test)]
@@ -216,6 +718,8 @@ mod tests {
   };
 
   use crate::swc_plugin_extract_str::ExtractStrConfig;
+  use crate::swc_plugin_extract_str::ExtractStrManifest;
+  use crate::swc_plugin_extract_str::ExtractStrStrategy;
   use crate::swc_plugin_extract_str::ExtractStrVisitor;
   test!(
     module,
@@ -226,7 +730,10 @@ mod tests {
       resolver(Mark::new(), Mark::new(), true),
       visit_mut_pass(ExtractStrVisitor::new(ExtractStrConfig {
         str_length: 1,
-        extracted_str_arr: None
+        extracted_str_arr: None,
+        strategy: None,
+        manifest: None,
+        all_positions: None,
       })),
       hygiene_with_config(Default::default()),
     ),
@@ -263,7 +770,10 @@ mod tests {
           "789".to_string(),
           "111".to_string(),
           "asdasdasd".to_string()
-        ])
+        ]),
+        strategy: None,
+        manifest: None,
+        all_positions: None,
       })),
       hygiene_with_config(Default::default()),
     ),
@@ -291,4 +801,318 @@ mod tests {
     }
   "#
   );
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      ..Default::default()
+    }),
+    |_| (
+      resolver(Mark::new(), Mark::new(), true),
+      visit_mut_pass(ExtractStrVisitor::new(ExtractStrConfig {
+        str_length: 1,
+        extracted_str_arr: None,
+        strategy: Some(ExtractStrStrategy::Size),
+        manifest: None,
+        all_positions: None,
+      })),
+      hygiene_with_config(Default::default()),
+    ),
+    should_extract_str_by_size,
+    r#"
+    console.log('once, but long enough to be worth nothing');
+    console.log('twice, and long enough to save bytes');
+    console.log('twice, and long enough to save bytes');
+    console.log('short', 'short', 'short', 'short');
+    console.log('three times, which is the most frequent');
+    console.log('three times, which is the most frequent');
+    console.log('three times, which is the most frequent');
+    "#
+  );
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      ..Default::default()
+    }),
+    |_| (
+      resolver(Mark::new(), Mark::new(), true),
+      visit_mut_pass(ExtractStrVisitor::new(ExtractStrConfig {
+        str_length: 3,
+        extracted_str_arr: None,
+        strategy: None,
+        manifest: None,
+        all_positions: Some(true),
+      })),
+      hygiene_with_config(Default::default()),
+    ),
+    should_extract_str_from_tpl_and_keys,
+    r#"
+    const a = `abc${x}def${y}`;
+    const b = `x${`abc`}x`;
+    const c = tag`abc${'def'}ghi`;
+    const d = {
+      'abc': 1,
+      'def'() {},
+      get 'ghi'() {},
+      set 'ghi'(v) {},
+      '__proto__': null,
+      ab: 2,
+      [`def`]: 3,
+    };
+    class E {
+      'abc'() {}
+    }
+    const { 'abc': f } = d;
+    "#
+  );
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      ..Default::default()
+    }),
+    |_| (
+      resolver(Mark::new(), Mark::new(), true),
+      visit_mut_pass(ExtractStrVisitor::new(ExtractStrConfig {
+        str_length: 3,
+        extracted_str_arr: Some(vec!["abc".to_string(), "def".to_string()]),
+        strategy: None,
+        manifest: None,
+        all_positions: Some(true),
+      })),
+      hygiene_with_config(Default::default()),
+    ),
+    should_extract_str_from_tpl_and_keys_with_arr,
+    r#"
+    const before = { 'abc': `abc${x}` };
+    __EXTRACT_STR_FLAG__(z=lynxCoreInject.tt._params.updateData._EXTRACT_STR,z);
+    const a = `abc${x}ghi`;
+    const d = { 'def': 1, 'ghi': 2 };
+    "#
+  );
+
+  test!(
+    Syntax::Es(EsSyntax {
+      ..Default::default()
+    }),
+    |_| (
+      resolver(Mark::new(), Mark::new(), false),
+      visit_mut_pass(ExtractStrVisitor::new(ExtractStrConfig {
+        str_length: 3,
+        extracted_str_arr: None,
+        strategy: None,
+        manifest: None,
+        all_positions: Some(true),
+      })),
+      hygiene_with_config(Default::default()),
+    ),
+    should_extract_str_in_iife,
+    r#"
+    "use strict";
+    var chunk = "ab";
+    !function () {
+      "use strict";
+      var _EXTRACT_STR = 1;
+      console.log("hello", `world ${chunk}`);
+    }();
+  "#
+  );
+
+  test!(
+    Syntax::Es(EsSyntax {
+      ..Default::default()
+    }),
+    |_| (
+      resolver(Mark::new(), Mark::new(), false),
+      visit_mut_pass(ExtractStrVisitor::new(ExtractStrConfig {
+        str_length: 3,
+        extracted_str_arr: None,
+        strategy: None,
+        manifest: None,
+        all_positions: None,
+      })),
+      hygiene_with_config(Default::default()),
+    ),
+    should_extract_str_in_script,
+    r#"
+    "use strict";
+    (function () {
+      console.log("hello");
+    }).call(this);
+    console.log("world");
+  "#
+  );
+
+  test!(
+    Syntax::Es(EsSyntax {
+      ..Default::default()
+    }),
+    |_| (
+      resolver(Mark::new(), Mark::new(), false),
+      visit_mut_pass(ExtractStrVisitor::new(ExtractStrConfig {
+        str_length: 3,
+        extracted_str_arr: None,
+        strategy: None,
+        manifest: None,
+        all_positions: None,
+      })),
+      hygiene_with_config(Default::default()),
+    ),
+    should_extract_str_with_flag_outside_iife,
+    r#"
+    "use strict";
+    (function () {
+      console.log("hello");
+    })();
+    globalThis.abc = { _EXTRACT_STR: __EXTRACT_STR_IDENT_FLAG__ };
+  "#
+  );
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      jsx: true,
+      ..Default::default()
+    }),
+    |_| (
+      resolver(Mark::new(), Mark::new(), true),
+      visit_mut_pass(ExtractStrVisitor::new(ExtractStrConfig {
+        str_length: 3,
+        extracted_str_arr: None,
+        strategy: None,
+        manifest: None,
+        all_positions: Some(true),
+      })),
+      hygiene_with_config(Default::default()),
+    ),
+    should_extract_str_from_jsx_text,
+    r#"
+    const a = <text>  hello world  </text>;
+    const b = <text>
+      hello
+        world
+    </text>;
+    const c = <view>
+      <text>ab</text>
+    </view>;
+    const d = <>hello world{x}hello world</>;
+    const e = <text>fish &amp; chips</text>;
+    "#
+  );
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      jsx: true,
+      ..Default::default()
+    }),
+    |_| (
+      resolver(Mark::new(), Mark::new(), true),
+      visit_mut_pass(ExtractStrVisitor::new(ExtractStrConfig {
+        str_length: 3,
+        extracted_str_arr: None,
+        strategy: None,
+        manifest: None,
+        all_positions: None,
+      })),
+      hygiene_with_config(Default::default()),
+    ),
+    should_not_extract_str_from_tpl_keys_and_jsx_text_by_default,
+    r#"
+    const a = `hello world${x}` + 'hello world';
+    const b = { 'hello world': `hello world` };
+    const c = <text>hello world</text>;
+    "#
+  );
+
+  #[test]
+  fn test_saved_bytes() {
+    use swc_core::ecma::{parser::parse_file_as_module, visit::VisitMutWith};
+
+    let saved_bytes = |strategy, code: &str| {
+      let cm: swc_core::common::sync::Lrc<swc_core::common::SourceMap> = Default::default();
+      let fm = cm.new_source_file(swc_core::common::FileName::Anon.into(), code.to_string());
+      let mut module = parse_file_as_module(
+        &fm,
+        Default::default(),
+        Default::default(),
+        None,
+        &mut vec![],
+      )
+      .unwrap();
+      let mut visitor = ExtractStrVisitor::new(ExtractStrConfig {
+        str_length: 1,
+        extracted_str_arr: None,
+        strategy: Some(strategy),
+        manifest: None,
+        all_positions: None,
+      });
+      module.visit_mut_with(&mut visitor);
+      (visitor.select_str_vec, visitor.saved_bytes)
+    };
+
+    // `'abcdefghijklmnopqrst'` is 22 bytes, `_EXTRACT_STR[0]` is 15 bytes and the table entry is 23 bytes.
+    let code = "f('abcdefghijklmnopqrst', 'abcdefghijklmnopqrst', 'x');\nf('abcdefghijklmnopqrst', 'abcdefghijklmnopqrst');";
+    assert_eq!(
+      saved_bytes(ExtractStrStrategy::Size, code),
+      (vec!["abcdefghijklmnopqrst".to_string()], 4 * (22 - 15) - 23)
+    );
+    assert_eq!(
+      saved_bytes(ExtractStrStrategy::Length, code),
+      (
+        vec!["abcdefghijklmnopqrst".to_string(), "x".to_string()],
+        4 * (22 - 15) - 23 + (3 - 15) - 4
+      )
+    );
+  }
+
+  #[test]
+  fn test_manifest() {
+    use swc_core::ecma::{parser::parse_file_as_module, visit::VisitMutWith};
+
+    let transform = |visitor: &mut ExtractStrVisitor, code: &str| {
+      let cm: swc_core::common::sync::Lrc<swc_core::common::SourceMap> = Default::default();
+      let fm = cm.new_source_file(swc_core::common::FileName::Anon.into(), code.to_string());
+      let mut module = parse_file_as_module(
+        &fm,
+        Default::default(),
+        Default::default(),
+        None,
+        &mut vec![],
+      )
+      .unwrap();
+      module.visit_mut_with(visitor);
+    };
+    let config = |extracted_str_arr| ExtractStrConfig {
+      str_length: 1,
+      extracted_str_arr,
+      strategy: None,
+      manifest: None,
+      all_positions: None,
+    };
+
+    // The main thread bundle keeps the indexes of the manifest and appends new strings.
+    let mut visitor = ExtractStrVisitor::new(config(None))
+      .with_manifest(ExtractStrManifest::new(vec!["a".into(), "b".into()]));
+    transform(&mut visitor, "f('c', 'b');");
+    assert_eq!(
+      visitor.manifest(),
+      Some(ExtractStrManifest::new(vec![
+        "a".into(),
+        "b".into(),
+        "c".into()
+      ]))
+    );
+    assert_eq!(visitor.unresolved(), None);
+
+    // The background bundle reports the strings missing from `extracted_str_arr`.
+    let mut visitor = ExtractStrVisitor::new(config(Some(vec!["a".into(), "b".into()])));
+    transform(
+      &mut visitor,
+      "__EXTRACT_STR_FLAG__(z, arr); f('c', 'b', 'd', 'c');",
+    );
+    assert_eq!(visitor.manifest(), None);
+    assert_eq!(visitor.unresolved(), Some(vec!["c".into(), "d".into()]));
+  }
 }
@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
+use serde::Serialize;
 use swc_core::{
   atoms::Atom,
   common::{SyntaxContext, DUMMY_SP},
@@ -17,7 +18,7 @@ use swc_core::{
 use crate::calc_hash;
 
 #[napi(object)]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct RefreshVisitorConfig {
   pub library: Option<Vec<String>>,
 }
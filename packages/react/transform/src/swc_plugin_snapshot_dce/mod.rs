@@ -0,0 +1,439 @@
+use std::collections::{HashMap, HashSet};
+
+use swc_core::{
+  common::{Mark, SyntaxContext},
+  ecma::{
+    ast::*,
+    visit::{noop_visit_mut_type, noop_visit_type, Visit, VisitMut, VisitMutWith, VisitWith},
+  },
+};
+
+/// The options of the bundle-level snapshot DCE, see `TransformNodiffBundleOptions.snapshotDCE`.
+#[napi(object)]
+#[derive(Clone, Debug, Default)]
+pub struct SnapshotDCEConfig {
+  /// The ids of the snapshots used by the background bundle, e.g. `__snapshot_da39a_test_1`.
+  /// The background thread creates the snapshots by their ids, so a snapshot is only removed
+  /// if it is referenced in neither the main thread bundle nor this list.
+  pub live_snapshots: Vec<String>,
+}
+
+/// The result of the bundle-level snapshot DCE, see `TransformNodiffBundleOutput.snapshotDCE`.
+#[napi(object)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SnapshotDCEOutput {
+  /// The ids of the snapshots removed since they are referenced in neither bundle.
+  pub removed_snapshots: Vec<String>,
+  /// The number of the no-op PAPI calls removed from the creators of the snapshots,
+  /// e.g. `__SetClasses(el, "")`.
+  pub folded_papi_calls: u32,
+}
+
+/// Removes the snapshots that are referenced in neither the bundle nor the background bundle,
+/// and the no-op PAPI calls in the creators of the others.
+///
+/// This runs on the whole main thread bundle, after `resolver`.
+pub struct SnapshotDCEVisitor {
+  pub result: SnapshotDCEOutput,
+  unresolved_ctxt: SyntaxContext,
+  /// The ids of the snapshots used by the background bundle,
+  /// no snapshot is removed if they are unknown.
+  live_snapshots: Option<HashSet<String>>,
+  /// The number of the occurrences of each identifier, including its declaration.
+  references: HashMap<Id, usize>,
+  /// The number of the occurrences of each string literal, including the ids of the snapshots.
+  strings: HashMap<String, usize>,
+  in_creator: bool,
+}
+
+impl SnapshotDCEVisitor {
+  pub fn new(unresolved_mark: Mark) -> Self {
+    Self {
+      result: Default::default(),
+      unresolved_ctxt: SyntaxContext::empty().apply_mark(unresolved_mark),
+      live_snapshots: None,
+      references: Default::default(),
+      strings: Default::default(),
+      in_creator: false,
+    }
+  }
+
+  pub fn with_live_snapshots(mut self, live_snapshots: Vec<String>) -> Self {
+    self.live_snapshots = Some(live_snapshots.into_iter().collect());
+    self
+  }
+
+  /// Whether `n` declares a snapshot that is referenced in neither the bundle nor the background bundle.
+  fn is_dead_snapshot(&self, n: &VarDeclarator) -> bool {
+    let Some(live_snapshots) = &self.live_snapshots else {
+      return false;
+    };
+    let (Pat::Ident(name), Some(init)) = (&n.name, &n.init) else {
+      return false;
+    };
+    let Expr::Call(call) = &**init else {
+      return false;
+    };
+    let Some(id) = snapshot_id(call) else {
+      return false;
+    };
+    !live_snapshots.contains(&id)
+      // The id may be referenced as a string elsewhere, e.g. by `snapshotManager.values.get(...)`.
+      && self.strings.get(&id) == Some(&1)
+      && self.references.get(&name.to_id()) == Some(&1)
+  }
+
+  /// Remove the dead snapshots declared by `n`, returns whether `n` is empty afterwards.
+  fn remove_dead_snapshots(&mut self, n: &mut VarDecl) -> bool {
+    let len = n.decls.len();
+    let mut removed = vec![];
+    n.decls.retain(|decl| {
+      if !self.is_dead_snapshot(decl) {
+        return true;
+      }
+      if let Some(Expr::Call(call)) = decl.init.as_deref() {
+        removed.extend(snapshot_id(call));
+      }
+      false
+    });
+    self.result.removed_snapshots.extend(removed);
+    len > 0 && n.decls.is_empty()
+  }
+
+  /// Visit the creator of a snapshot, i.e. the second argument of `createSnapshot`.
+  fn visit_mut_creator(&mut self, n: &mut Expr) {
+    let body = match n {
+      Expr::Paren(ParenExpr { expr, .. }) => return self.visit_mut_creator(expr),
+      Expr::Fn(FnExpr { function, .. }) => function.body.as_mut(),
+      Expr::Arrow(ArrowExpr { body, .. }) => body.as_mut_block_stmt(),
+      _ => None,
+    };
+    let Some(body) = body else {
+      n.visit_mut_with(self);
+      return;
+    };
+    let in_creator = std::mem::replace(&mut self.in_creator, true);
+    body.visit_mut_with(self);
+    self.in_creator = in_creator;
+  }
+
+  fn is_undefined(&self, n: &Expr) -> bool {
+    match n {
+      Expr::Ident(ident) => ident.sym == "undefined" && ident.ctxt == self.unresolved_ctxt,
+      Expr::Unary(UnaryExpr {
+        op: op!("void"),
+        arg,
+        ..
+      }) => arg.is_lit(),
+      _ => false,
+    }
+  }
+
+  /// Whether `n` is a PAPI call that does nothing on a newly created element.
+  fn is_noop_papi_call(&self, n: &Expr) -> bool {
+    let Expr::Call(CallExpr {
+      callee: Callee::Expr(callee),
+      args,
+      ..
+    }) = n
+    else {
+      return false;
+    };
+    let Expr::Ident(callee) = &**callee else {
+      return false;
+    };
+    if callee.ctxt != self.unresolved_ctxt || args.iter().any(|arg| arg.spread.is_some()) {
+      return false;
+    }
+    let args = args.iter().map(|arg| &*arg.expr).collect::<Vec<_>>();
+    let is_empty_str = |n: &Expr| matches!(n, Expr::Lit(Lit::Str(s)) if s.value.is_empty());
+    match (callee.sym.as_ref(), args.as_slice()) {
+      ("__SetClasses", [Expr::Ident(_), value]) => is_empty_str(value) || self.is_undefined(value),
+      ("__SetInlineStyles" | "__SetID", [Expr::Ident(_), value]) => {
+        is_empty_str(value) || self.is_undefined(value)
+      }
+      ("__SetAttribute" | "__AddDataset", [Expr::Ident(_), Expr::Lit(Lit::Str(_)), value]) => {
+        self.is_undefined(value)
+      }
+      _ => false,
+    }
+  }
+}
+
+/// The id of the snapshot created by `n`, e.g. `__snapshot_da39a_test_1` of
+/// `ReactLynx.createSnapshot("__snapshot_da39a_test_1", function() { ... }, ...)`.
+fn snapshot_id(n: &CallExpr) -> Option<String> {
+  let Callee::Expr(callee) = &n.callee else {
+    return None;
+  };
+  let mut callee = &**callee;
+  // `(0, _lynx_js_react__WEBPACK_IMPORTED_MODULE_0__.createSnapshot)(...)` of bundlers
+  loop {
+    match callee {
+      Expr::Paren(ParenExpr { expr, .. }) => callee = expr,
+      Expr::Seq(SeqExpr { exprs, .. }) => callee = exprs.last()?,
+      _ => break,
+    }
+  }
+  let is_create_snapshot = match callee {
+    Expr::Ident(ident) => ident.sym == "createSnapshot",
+    Expr::Member(MemberExpr {
+      prop: MemberProp::Ident(prop),
+      ..
+    }) => prop.sym == "createSnapshot",
+    _ => false,
+  };
+  match n.args.first().map(|arg| &*arg.expr) {
+    Some(Expr::Lit(Lit::Str(id))) if is_create_snapshot => Some(id.value.to_string()),
+    _ => None,
+  }
+}
+
+/// Counts the occurrences of each identifier and string literal.
+struct ReferenceCounter<'a> {
+  references: &'a mut HashMap<Id, usize>,
+  strings: &'a mut HashMap<String, usize>,
+}
+
+impl Visit for ReferenceCounter<'_> {
+  noop_visit_type!();
+
+  fn visit_ident(&mut self, n: &Ident) {
+    *self.references.entry(n.to_id()).or_default() += 1;
+  }
+
+  fn visit_str(&mut self, n: &Str) {
+    *self.strings.entry(n.value.to_string()).or_default() += 1;
+  }
+}
+
+impl VisitMut for SnapshotDCEVisitor {
+  noop_visit_mut_type!();
+
+  fn visit_mut_program(&mut self, n: &mut Program) {
+    n.visit_with(&mut ReferenceCounter {
+      references: &mut self.references,
+      strings: &mut self.strings,
+    });
+    match n {
+      Program::Module(module) => module.visit_mut_children_with(self),
+      // The top-level declarations of a script are globals, which may be used by other scripts.
+      Program::Script(script) => {
+        for stmt in &mut script.body {
+          stmt.visit_mut_with(self);
+        }
+      }
+    }
+  }
+
+  fn visit_mut_module_items(&mut self, n: &mut Vec<ModuleItem>) {
+    n.retain_mut(|item| match item {
+      ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => !self.remove_dead_snapshots(var),
+      _ => true,
+    });
+    n.visit_mut_children_with(self);
+  }
+
+  fn visit_mut_stmts(&mut self, n: &mut Vec<Stmt>) {
+    n.retain_mut(|stmt| match stmt {
+      Stmt::Decl(Decl::Var(var)) => !self.remove_dead_snapshots(var),
+      Stmt::Expr(ExprStmt { expr, .. }) if self.in_creator && self.is_noop_papi_call(expr) => {
+        self.result.folded_papi_calls += 1;
+        false
+      }
+      _ => true,
+    });
+    n.visit_mut_children_with(self);
+  }
+
+  fn visit_mut_call_expr(&mut self, n: &mut CallExpr) {
+    if snapshot_id(n).is_none() {
+      n.visit_mut_children_with(self);
+      return;
+    }
+    n.callee.visit_mut_with(self);
+    for (i, arg) in n.args.iter_mut().enumerate() {
+      match i {
+        1 => self.visit_mut_creator(&mut arg.expr),
+        _ => arg.visit_mut_with(self),
+      }
+    }
+  }
+
+  fn visit_mut_function(&mut self, n: &mut Function) {
+    let in_creator = std::mem::replace(&mut self.in_creator, false);
+    n.visit_mut_children_with(self);
+    self.in_creator = in_creator;
+  }
+
+  fn visit_mut_arrow_expr(&mut self, n: &mut ArrowExpr) {
+    let in_creator = std::mem::replace(&mut self.in_creator, false);
+    n.visit_mut_children_with(self);
+    self.in_creator = in_creator;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use swc_core::{
+    common::Mark,
+    ecma::{
+      parser::{EsSyntax, Syntax},
+      transforms::{base::resolver, testing::test},
+      visit::visit_mut_pass,
+    },
+  };
+
+  use super::{SnapshotDCEOutput, SnapshotDCEVisitor};
+
+  const INPUT: &str = r#"
+import * as ReactLynx from "@lynx-js/react";
+const __snapshot_a = ReactLynx.createSnapshot("__snapshot_a", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    __SetClasses(el, "");
+    __SetAttribute(el, "id", undefined);
+    __SetInlineStyles(el, void 0);
+    __SetAttribute(el, "title", "hello");
+    const f = function() {
+        __SetClasses(el, "");
+    };
+    return [
+        el
+    ];
+}, [
+    (snapshot, index, oldValue)=>__SetAttribute(snapshot.__elements[0], "x", undefined)
+], null, undefined, globDynamicComponentEntry, null);
+const __snapshot_b = (0, ReactLynx.createSnapshot)("__snapshot_b", function() {
+    const el = __CreateView(ReactLynx.__pageId);
+    return [
+        el
+    ];
+}, null, null, undefined, globDynamicComponentEntry, null);
+const __snapshot_c = createSnapshot("__snapshot_c", ()=>{
+    return [];
+}, null, null, undefined, globDynamicComponentEntry, null), used = 1;
+function App() {
+    return _jsx(__snapshot_a, {
+        values: [
+            used
+        ]
+    });
+}
+export { App };
+"#;
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      ..Default::default()
+    }),
+    |_| {
+      let unresolved_mark = Mark::new();
+      (
+        resolver(unresolved_mark, Mark::new(), false),
+        visit_mut_pass(SnapshotDCEVisitor::new(unresolved_mark).with_live_snapshots(vec![])),
+      )
+    },
+    should_remove_dead_snapshots_and_papi_calls,
+    INPUT
+  );
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      ..Default::default()
+    }),
+    |_| {
+      let unresolved_mark = Mark::new();
+      (
+        resolver(unresolved_mark, Mark::new(), false),
+        visit_mut_pass(SnapshotDCEVisitor::new(unresolved_mark)),
+      )
+    },
+    should_not_remove_snapshots_without_live_snapshots,
+    r#"
+import * as ReactLynx from "@lynx-js/react";
+const __snapshot_a = ReactLynx.createSnapshot("__snapshot_a", function() {
+    const el = __CreateView(ReactLynx.__pageId);
+    __SetClasses(el, "");
+    return [
+        el
+    ];
+}, null, null, undefined, globDynamicComponentEntry, null);
+"#
+  );
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      ..Default::default()
+    }),
+    |_| {
+      let unresolved_mark = Mark::new();
+      (
+        resolver(unresolved_mark, Mark::new(), false),
+        visit_mut_pass(
+          SnapshotDCEVisitor::new(unresolved_mark).with_live_snapshots(vec!["__snapshot_a".into()]),
+        ),
+      )
+    },
+    should_keep_snapshots_used_by_background,
+    r#"
+import * as ReactLynx from "@lynx-js/react";
+// Only used by `if (__BACKGROUND__) { ... }`, which is removed from the main thread bundle.
+const __snapshot_a = ReactLynx.createSnapshot("__snapshot_a", function() {
+    const el = __CreateView(ReactLynx.__pageId);
+    return [
+        el
+    ];
+}, null, null, undefined, globDynamicComponentEntry, null);
+const __snapshot_b = ReactLynx.createSnapshot("__snapshot_b", function() {
+    const el = __CreateView(ReactLynx.__pageId);
+    return [
+        el
+    ];
+}, null, null, undefined, globDynamicComponentEntry, null);
+const __snapshot_c = ReactLynx.createSnapshot("__snapshot_c", function() {
+    const el = __CreateView(ReactLynx.__pageId);
+    return [
+        el
+    ];
+}, null, null, undefined, globDynamicComponentEntry, null);
+snapshotManager.values.get("__snapshot_b");
+"#
+  );
+
+  #[test]
+  fn test_snapshot_dce_output() {
+    use swc_core::{
+      common::{sync::Lrc, FileName, SourceMap, GLOBALS},
+      ecma::{ast::Pass, parser::parse_file_as_program},
+    };
+
+    GLOBALS.set(&Default::default(), || {
+      let cm: Lrc<SourceMap> = Default::default();
+      let fm = cm.new_source_file(FileName::Anon.into(), INPUT.to_string());
+      let mut program = parse_file_as_program(
+        &fm,
+        Default::default(),
+        Default::default(),
+        None,
+        &mut vec![],
+      )
+      .unwrap();
+      let unresolved_mark = Mark::new();
+      resolver(unresolved_mark, Mark::new(), false).process(&mut program);
+      let mut visitor = SnapshotDCEVisitor::new(unresolved_mark).with_live_snapshots(vec![]);
+      visit_mut_pass(&mut visitor).process(&mut program);
+
+      assert_eq!(
+        visitor.result,
+        SnapshotDCEOutput {
+          removed_snapshots: vec!["__snapshot_b".into(), "__snapshot_c".into()],
+          folded_papi_calls: 3,
+        }
+      );
+    });
+  }
+}
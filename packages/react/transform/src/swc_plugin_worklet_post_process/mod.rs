@@ -1,44 +1,223 @@
+use std::collections::{HashMap, HashSet};
+
+use swc_core::common::{util::take::Take, DUMMY_SP};
 use swc_core::ecma::ast::*;
-use swc_core::ecma::visit::{noop_visit_mut_type, VisitMut, VisitMutWith};
+use swc_core::ecma::visit::{
+  noop_visit_mut_type, noop_visit_type, Visit, VisitMut, VisitMutWith, VisitWith,
+};
+
+/// The functions a worklet is registered with, by `registerWorklet(type, hash, fn)`.
+const REGISTER_WORKLET_FNS: &[&str] = &["registerWorklet", "registerWorkletInternal"];
+
+/// A worklet registered in the bundle, see `WorkletManifest`.
+#[napi(object)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BundleWorklet {
+  /// The hash the worklet is registered with.
+  pub hash: String,
+  #[napi(js_name = "type", ts_type = "'main-thread' | 'ui'")]
+  pub worklet_type: String,
+  /// Whether the hash is used in the bundle besides its registration, e.g. by `_wkltId` of a worklet object.
+  pub referenced: bool,
+}
+
+/// The worklets registered in the bundle, see `TransformNodiffBundleOutput.worklets`.
+#[napi(object)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkletManifest {
+  /// The worklets that are kept, in the order they are registered.
+  pub worklets: Vec<BundleWorklet>,
+  /// The number of the worklets that are kept.
+  pub count: u32,
+  /// The hashes of the worklets removed since they are not in `TransformNodiffBundleOptions.workletAllowList`.
+  pub removed: Vec<String>,
+}
 
 #[derive(Default)]
 pub struct WorkletPostProcessorVisitorResult {
   pub has_worklet: bool,
+  pub worklets: Vec<BundleWorklet>,
+  pub removed: Vec<String>,
+}
+
+impl WorkletPostProcessorVisitorResult {
+  pub fn manifest(&self) -> WorkletManifest {
+    WorkletManifest {
+      worklets: self.worklets.clone(),
+      count: self.worklets.len() as u32,
+      removed: self.removed.clone(),
+    }
+  }
 }
 
 pub struct WorkletPostProcessorVisitor {
   pub result: WorkletPostProcessorVisitorResult,
+  allow_list: Option<HashSet<String>>,
 }
 
 impl Default for WorkletPostProcessorVisitor {
   fn default() -> Self {
-    WorkletPostProcessorVisitor::new(WorkletPostProcessorVisitorResult { has_worklet: false })
+    WorkletPostProcessorVisitor::new(WorkletPostProcessorVisitorResult::default())
   }
 }
 
 impl WorkletPostProcessorVisitor {
   pub fn new(result: WorkletPostProcessorVisitorResult) -> Self {
-    WorkletPostProcessorVisitor { result }
+    WorkletPostProcessorVisitor {
+      result,
+      allow_list: None,
+    }
+  }
+
+  /// Remove the registrations of the worklets whose hashes are not in `allow_list`.
+  pub fn with_allow_list(self, allow_list: Vec<String>) -> Self {
+    Self {
+      allow_list: Some(allow_list.into_iter().collect()),
+      ..self
+    }
+  }
+
+  fn is_removed_stmt(&self, n: &Stmt) -> bool {
+    matches!(n, Stmt::Expr(ExprStmt { expr, .. }) if matches!(&**expr, Expr::Call(call) if self.is_removed(call)))
+  }
+
+  fn is_removed(&self, n: &CallExpr) -> bool {
+    match (&self.allow_list, registration(n)) {
+      (Some(allow_list), Some((_, hash))) => !allow_list.contains(hash),
+      _ => false,
+    }
+  }
+}
+
+/// The type and hash of a `registerWorklet` call.
+///
+/// The type is omitted by `registerWorklet(hash, fn)` of old versions, which is always `main-thread`.
+fn registration(n: &CallExpr) -> Option<(&str, &str)> {
+  let Callee::Expr(callee) = &n.callee else {
+    return None;
+  };
+  let Expr::Ident(Ident { sym, .. }) = &**callee else {
+    return None;
+  };
+  if !REGISTER_WORKLET_FNS.contains(&sym.as_ref()) {
+    return None;
+  }
+  let str_arg = |index: usize| match n.args.get(index).map(|arg| &*arg.expr) {
+    Some(Expr::Lit(Lit::Str(s))) => Some(s.value.as_ref()),
+    _ => None,
+  };
+  match n.args.len() {
+    3 => Some((str_arg(0)?, str_arg(1)?)),
+    2 => Some(("main-thread", str_arg(0)?)),
+    _ => None,
+  }
+}
+
+/// Collects the registered worklets, and the string literals that may reference them.
+#[derive(Default)]
+struct WorkletCollector {
+  has_worklet: bool,
+  /// The type and hash of the registered worklets.
+  registered: Vec<(String, String)>,
+  /// The number of the string literals with each value,
+  /// excluding the hashes in the registrations and the worklets themselves.
+  strings: HashMap<String, usize>,
+  /// The hash of the worklet being visited.
+  current_hash: Option<String>,
+}
+
+impl Visit for WorkletCollector {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, n: &CallExpr) {
+    if let Callee::Expr(callee) = &n.callee {
+      if let Expr::Ident(Ident { sym, .. }) = &**callee {
+        if sym == "registerWorklet" {
+          self.has_worklet = true;
+        }
+      }
+    }
+
+    let Some((worklet_type, hash)) = registration(n) else {
+      n.visit_children_with(self);
+      return;
+    };
+    self
+      .registered
+      .push((worklet_type.to_string(), hash.to_string()));
+    let parent_hash = self.current_hash.replace(hash.to_string());
+    if let Some(worklet) = n.args.last() {
+      worklet.visit_with(self);
+    }
+    self.current_hash = parent_hash;
+  }
+
+  fn visit_str(&mut self, n: &Str) {
+    if self.current_hash.as_deref() != Some(n.value.as_ref()) {
+      *self.strings.entry(n.value.to_string()).or_default() += 1;
+    }
   }
 }
 
 impl VisitMut for WorkletPostProcessorVisitor {
   noop_visit_mut_type!();
 
-  fn visit_mut_call_expr(&mut self, n: &mut CallExpr) {
-    if self.result.has_worklet {
-      return;
+  fn visit_mut_program(&mut self, n: &mut Program) {
+    let mut collector = WorkletCollector::default();
+    n.visit_with(&mut collector);
+    self.result.has_worklet = collector.has_worklet;
+
+    for (worklet_type, hash) in collector.registered {
+      let is_allowed = self
+        .allow_list
+        .as_ref()
+        .is_none_or(|allow_list| allow_list.contains(&hash));
+      if !is_allowed {
+        self.result.removed.push(hash);
+        continue;
+      }
+      self.result.worklets.push(BundleWorklet {
+        referenced: collector.strings.contains_key(&hash),
+        hash,
+        worklet_type,
+      });
     }
 
-    if let Callee::Expr(expr) = &n.callee {
-      if let Expr::Ident(Ident { sym, .. }) = &**expr {
-        if sym == "registerWorklet" {
-          self.result.has_worklet = true;
-        }
+    if !self.result.removed.is_empty() {
+      n.visit_mut_children_with(self);
+    }
+  }
+
+  fn visit_mut_module_items(&mut self, n: &mut Vec<ModuleItem>) {
+    n.retain(|item| !matches!(item, ModuleItem::Stmt(stmt) if self.is_removed_stmt(stmt)));
+    n.visit_mut_children_with(self);
+  }
+
+  fn visit_mut_stmts(&mut self, n: &mut Vec<Stmt>) {
+    n.retain(|stmt| !self.is_removed_stmt(stmt));
+    n.visit_mut_children_with(self);
+  }
+
+  fn visit_mut_expr(&mut self, n: &mut Expr) {
+    // `loadWorkletRuntime(...) && registerWorkletInternal(...)`
+    if let Expr::Bin(BinExpr {
+      op: op!("&&"),
+      left,
+      right,
+      ..
+    }) = n
+    {
+      if matches!(&**right, Expr::Call(call) if self.is_removed(call)) {
+        *n = *left.take();
+        n.visit_mut_with(self);
+        return;
       }
     }
 
     n.visit_mut_children_with(self);
+    if matches!(n, Expr::Call(call) if self.is_removed(call)) {
+      *n = *Expr::undefined(DUMMY_SP);
+    }
   }
 }
 
@@ -97,4 +276,99 @@ function f() {
 }
     "#
   );
+
+  test!(
+    module,
+    Syntax::Typescript(TsSyntax {
+      ..Default::default()
+    }),
+    |_| visit_mut_pass(
+      WorkletPostProcessorVisitor::default().with_allow_list(vec!["a77b:test:1".into()])
+    ),
+    should_remove_worklets_not_in_allow_list,
+    r#"
+let X = { _c: {}, _wkltId: "a77b:test:1" };
+loadWorkletRuntime(typeof globDynamicComponentEntry === 'undefined' ? undefined : globDynamicComponentEntry) && registerWorkletInternal("main-thread", "a77b:test:1", function(event) {
+    const X = lynxWorkletImpl._workletMap["a77b:test:1"].bind(this);
+    "main thread";
+});
+loadWorkletRuntime(typeof globDynamicComponentEntry === 'undefined' ? undefined : globDynamicComponentEntry) && registerWorkletInternal("main-thread", "a77b:test:2", function(event) {
+    const Y = lynxWorkletImpl._workletMap["a77b:test:2"].bind(this);
+    "main thread";
+});
+function f() {
+  registerWorklet("ui", "a77b:test:3", function() {});
+}
+g(registerWorklet("57ba11b1", function() {}));
+    "#
+  );
+
+  #[test]
+  fn test_worklet_manifest() {
+    use super::{BundleWorklet, WorkletManifest};
+    use swc_core::ecma::{parser::parse_file_as_program, visit::VisitMutWith};
+
+    let code = r#"
+let X = { _c: {}, _wkltId: "a77b:test:1" };
+registerWorkletInternal("main-thread", "a77b:test:1", function(event) {
+  const X = lynxWorkletImpl._workletMap["a77b:test:1"].bind(this);
+  let Y = { _wkltId: "a77b:test:3" };
+});
+registerWorklet("ui", "a77b:test:2", function() {
+  const X = lynxWorkletImpl._workletMap["a77b:test:2"].bind(this);
+});
+registerWorklet("main-thread", "a77b:test:3", function() {});
+"#;
+    let manifest = |visitor: WorkletPostProcessorVisitor| {
+      let cm: swc_core::common::sync::Lrc<swc_core::common::SourceMap> = Default::default();
+      let fm = cm.new_source_file(swc_core::common::FileName::Anon.into(), code.to_string());
+      let mut program = parse_file_as_program(
+        &fm,
+        Default::default(),
+        Default::default(),
+        None,
+        &mut vec![],
+      )
+      .unwrap();
+      let mut visitor = visitor;
+      program.visit_mut_with(&mut visitor);
+      (visitor.result.has_worklet, visitor.result.manifest())
+    };
+    let worklet = |hash: &str, worklet_type: &str, referenced: bool| BundleWorklet {
+      hash: hash.into(),
+      worklet_type: worklet_type.into(),
+      referenced,
+    };
+
+    assert_eq!(
+      manifest(WorkletPostProcessorVisitor::default()),
+      (
+        true,
+        WorkletManifest {
+          worklets: vec![
+            worklet("a77b:test:1", "main-thread", true),
+            worklet("a77b:test:2", "ui", false),
+            worklet("a77b:test:3", "main-thread", true),
+          ],
+          count: 3,
+          removed: vec![],
+        }
+      )
+    );
+    assert_eq!(
+      manifest(
+        WorkletPostProcessorVisitor::default()
+          .with_allow_list(vec!["a77b:test:1".into(), "a77b:test:3".into()])
+      )
+      .1,
+      WorkletManifest {
+        worklets: vec![
+          worklet("a77b:test:1", "main-thread", true),
+          worklet("a77b:test:3", "main-thread", true),
+        ],
+        count: 2,
+        removed: vec!["a77b:test:2".into()],
+      }
+    );
+  }
 }
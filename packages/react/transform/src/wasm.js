@@ -32,6 +32,12 @@ const { instance, napiModule } = instantiateNapiModuleSync(mod, {
       },
     };
 
+    importObject.react_transform = {
+      performance_now: function() {
+        return performance.now();
+      },
+    };
+
     return importObject;
   },
 });
@@ -0,0 +1,11 @@
+var _EXTRACT_STR = [
+    "three times, which is the most frequent",
+    "twice, and long enough to save bytes"
+];
+console.log('once, but long enough to be worth nothing');
+console.log(_EXTRACT_STR[1]);
+console.log(_EXTRACT_STR[1]);
+console.log('short', 'short', 'short', 'short');
+console.log(_EXTRACT_STR[0]);
+console.log(_EXTRACT_STR[0]);
+console.log(_EXTRACT_STR[0]);
@@ -0,0 +1,12 @@
+var _EXTRACT_STR = [
+    "  hello world  ",
+    "hello world",
+    "fish & chips"
+];
+const a = <text>{_EXTRACT_STR[0]}</text>;
+const b = <text>{_EXTRACT_STR[1]}</text>;
+const c = <view>
+      <text>ab</text>
+    </view>;
+const d = <>{_EXTRACT_STR[1]}{x}{_EXTRACT_STR[1]}</>;
+const e = <text>{_EXTRACT_STR[2]}</text>;
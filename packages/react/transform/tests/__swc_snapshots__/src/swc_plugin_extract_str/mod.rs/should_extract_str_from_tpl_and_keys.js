@@ -0,0 +1,21 @@
+var _EXTRACT_STR = [
+    "abc",
+    "def",
+    "ghi"
+];
+const a = `${_EXTRACT_STR[0]}${x}${_EXTRACT_STR[1]}${y}`;
+const b = `x${`${_EXTRACT_STR[0]}`}x`;
+const c = tag`abc${_EXTRACT_STR[1]}ghi`;
+const d = {
+    [_EXTRACT_STR[0]]: 1,
+    [_EXTRACT_STR[1]] () {},
+    get [_EXTRACT_STR[2]] () {},
+    set [_EXTRACT_STR[2]] (v){},
+    '__proto__': null,
+    ab: 2,
+    [`${_EXTRACT_STR[1]}`]: 3
+};
+class E {
+    'abc'() {}
+}
+const { 'abc': f } = d;
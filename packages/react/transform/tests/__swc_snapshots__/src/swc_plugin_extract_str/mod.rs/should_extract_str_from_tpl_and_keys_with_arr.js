@@ -0,0 +1,9 @@
+const before = {
+    'abc': `abc${x}`
+};
+z = lynxCoreInject.tt._params.updateData._EXTRACT_STR;
+const a = `${z[0]}${x}ghi`;
+const d = {
+    [z[1]]: 1,
+    'ghi': 2
+};
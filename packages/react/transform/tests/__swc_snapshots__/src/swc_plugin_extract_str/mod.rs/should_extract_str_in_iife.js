@@ -0,0 +1,11 @@
+"use strict";
+var chunk = "ab";
+!function() {
+    "use strict";
+    var _EXTRACT_STR = [
+        "hello",
+        "world "
+    ];
+    var _EXTRACT_STR1 = 1;
+    console.log(_EXTRACT_STR[0], `${_EXTRACT_STR[1]}${chunk}`);
+}();
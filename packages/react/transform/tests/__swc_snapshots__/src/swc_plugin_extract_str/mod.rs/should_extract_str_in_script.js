@@ -0,0 +1,9 @@
+"use strict";
+var _EXTRACT_STR = [
+    "hello",
+    "world"
+];
+(function() {
+    console.log(_EXTRACT_STR[0]);
+}).call(this);
+console.log(_EXTRACT_STR[1]);
@@ -0,0 +1,10 @@
+"use strict";
+var _EXTRACT_STR = [
+    "hello"
+];
+(function() {
+    console.log(_EXTRACT_STR[0]);
+})();
+globalThis.abc = {
+    _EXTRACT_STR: _EXTRACT_STR
+};
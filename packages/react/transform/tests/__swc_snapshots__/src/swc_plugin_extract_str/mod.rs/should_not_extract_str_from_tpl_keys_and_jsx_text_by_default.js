@@ -0,0 +1,8 @@
+var _EXTRACT_STR = [
+    "hello world"
+];
+const a = `hello world${x}` + _EXTRACT_STR[0];
+const b = {
+    'hello world': `hello world`
+};
+const c = <text>hello world</text>;
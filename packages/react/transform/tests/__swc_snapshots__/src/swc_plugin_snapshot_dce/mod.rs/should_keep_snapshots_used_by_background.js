@@ -0,0 +1,15 @@
+import * as ReactLynx from "@lynx-js/react";
+// Only used by `if (__BACKGROUND__) { ... }`, which is removed from the main thread bundle.
+const __snapshot_a = ReactLynx.createSnapshot("__snapshot_a", function() {
+    const el = __CreateView(ReactLynx.__pageId);
+    return [
+        el
+    ];
+}, null, null, undefined, globDynamicComponentEntry, null);
+const __snapshot_b = ReactLynx.createSnapshot("__snapshot_b", function() {
+    const el = __CreateView(ReactLynx.__pageId);
+    return [
+        el
+    ];
+}, null, null, undefined, globDynamicComponentEntry, null);
+snapshotManager.values.get("__snapshot_b");
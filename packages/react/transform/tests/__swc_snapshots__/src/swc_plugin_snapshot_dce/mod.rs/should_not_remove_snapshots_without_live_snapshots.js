@@ -0,0 +1,7 @@
+import * as ReactLynx from "@lynx-js/react";
+const __snapshot_a = ReactLynx.createSnapshot("__snapshot_a", function() {
+    const el = __CreateView(ReactLynx.__pageId);
+    return [
+        el
+    ];
+}, null, null, undefined, globDynamicComponentEntry, null);
@@ -0,0 +1,23 @@
+import * as ReactLynx from "@lynx-js/react";
+const __snapshot_a = ReactLynx.createSnapshot("__snapshot_a", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    __SetAttribute(el, "title", "hello");
+    const f = function() {
+        __SetClasses(el, "");
+    };
+    return [
+        el
+    ];
+}, [
+    (snapshot, index, oldValue)=>__SetAttribute(snapshot.__elements[0], "x", undefined)
+], null, undefined, globDynamicComponentEntry, null);
+const used = 1;
+function App() {
+    return _jsx(__snapshot_a, {
+        values: [
+            used
+        ]
+    });
+}
+export { App };
@@ -0,0 +1,11 @@
+let X = {
+    _c: {},
+    _wkltId: "a77b:test:1"
+};
+loadWorkletRuntime(typeof globDynamicComponentEntry === 'undefined' ? undefined : globDynamicComponentEntry) && registerWorkletInternal("main-thread", "a77b:test:1", function(event) {
+    const X = lynxWorkletImpl._workletMap["a77b:test:1"].bind(this);
+    "main thread";
+});
+loadWorkletRuntime(typeof globDynamicComponentEntry === 'undefined' ? undefined : globDynamicComponentEntry);
+function f() {}
+g(void 0);